@@ -98,7 +98,7 @@ fn render_dashboard_header(app_state: &AppState, ui: &Ui) -> impl ratatui::widge
     
     // Create stylish header
     let title = " 🔧 CRANKSHAFT MONITORING DASHBOARD ";
-    let subtitle = format!(" CPU: {:.1}% | Memory: {:.1}% | Mode: {} ", 
+    let subtitle = format!(" CPU: {} | Memory: {} | Mode: {} ", 
         cpu_usage, 
         mem_usage,
         match app_state.temporality {
@@ -455,22 +455,22 @@ async fn main() -> Result<(), Box<dyn Error>> {
         // Poll for task updates
         if let Some(task_update) = task_monitor.poll().await {
             // Convert to state update type
-            let state_update: TaskUpdate = task_update.into();
+            let state_updates: Vec<TaskUpdate> = task_update.into();
             
             // Update state
             if let Ok(mut state) = app_state.lock() {
-                state.update_tasks(vec![state_update]);
+                state.update_tasks(state_updates);
             }
         }
         
         // Poll for backend updates
         if let Some(backend_update) = backend_monitor.poll().await {
             // Convert to state update type
-            let state_update: BackendUpdate = backend_update.into();
+            let state_updates: Vec<BackendUpdate> = backend_update.into();
             
             // Update state
             if let Ok(mut state) = app_state.lock() {
-                state.update_backends(vec![state_update]);
+                state.update_backends(state_updates);
             }
         }
     }
@@ -3,15 +3,18 @@
 //! This module contains the main application state and handles the
 //! integration between Crankshaft engine, UI components, and event handling.
 
+use std::net::SocketAddr;
+use std::time::Duration;
 use color_eyre::Result;
 use crossterm::event::KeyEvent;
 use ratatui::backend::Backend;
 use ratatui::Terminal;
-use crossterm::event::KeyCode;
 use crate::event::{Event, EventHandler};
-use crate::monitor::{TaskMonitor, BackendMonitor};
-use crate::state::{AppState, Temporality};
-use crate::ui::{self, Ui};
+use crate::metrics::MetricsExporter;
+use crate::monitor::MonitorManager;
+use crate::notify::{Notifier, NotifyConfig};
+use crate::state::{AppState, StallPolicy, Temporality, WorkflowDescription};
+use crate::ui::{self, Keymap, Theme, Ui};
 
 use futures::StreamExt;
 
@@ -20,6 +23,17 @@ pub struct AppConfig {
     pub tick_rate_ms: u64,
     pub refresh_rate_ms: u64,
     pub debug_mode: bool,
+    pub notify: NotifyConfig,
+    /// Address to serve Prometheus metrics on (disabled if `None`)
+    pub metrics_addr: Option<SocketAddr>,
+    /// Thresholds for flagging stalled and straggling tasks
+    pub stall_policy: StallPolicy,
+    /// Call dependencies used to draw the workflow graph
+    pub workflow: WorkflowDescription,
+    /// UI theme
+    pub theme: Theme,
+    /// Key bindings for global shortcuts
+    pub keymap: Keymap,
 }
 
 impl Default for AppConfig {
@@ -28,6 +42,12 @@ impl Default for AppConfig {
             tick_rate_ms: 250,
             refresh_rate_ms: 1000,
             debug_mode: false,
+            notify: NotifyConfig::default(),
+            metrics_addr: None,
+            stall_policy: StallPolicy::default(),
+            workflow: WorkflowDescription::default(),
+            theme: Theme::default(),
+            keymap: Keymap::default(),
         }
     }
 }
@@ -44,10 +64,12 @@ pub struct App {
     state: AppState,
     /// Application configuration
     config: AppConfig,
-    /// Task monitor
-    task_monitor: TaskMonitor,
-    /// Backend monitor
-    backend_monitor: BackendMonitor,
+    /// Task and backend monitors for the Crankshaft engine
+    monitors: MonitorManager,
+    /// Alert delivery
+    notifier: Notifier,
+    /// Prometheus metrics exporter
+    metrics: Option<MetricsExporter>,
     /// Current view controller
     ui: Ui,
     /// Should the application exit?
@@ -56,22 +78,35 @@ pub struct App {
 
 impl App {
     /// Creates a new application instance.
-    pub async fn new(config: AppConfig) -> Result<Self> {
+    pub async fn new(config: AppConfig, mut monitors: MonitorManager) -> Result<Self> {
         // Initialize app state with the Entity-Component pattern from tokio-console
-        let state = AppState::new();
+        let state = AppState::new()
+            .with_stall_policy(config.stall_policy)
+            .with_workflow_description(config.workflow.clone());
         
-        // Initialize monitors for crankshaft engine
-        let task_monitor = TaskMonitor::new();
-        let backend_monitor = BackendMonitor::new();
+        // Connect the monitors to the crankshaft engine
+        monitors.connect().await?;
+        
+        // Initialize alert delivery
+        let notifier = Notifier::new(config.notify.clone())?;
+        
+        // Start the metrics server if requested
+        let metrics = match config.metrics_addr {
+            Some(addr) => Some(MetricsExporter::start(addr, Duration::from_millis(config.refresh_rate_ms)).await?),
+            None => None,
+        };
         
         // Initialize UI controller
-        let ui = Ui::new();
+        let mut ui = Ui::new();
+        ui.set_theme(config.theme.clone());
+        ui.set_keymap(config.keymap.clone());
         
         Ok(Self {
             state,
             config,
-            task_monitor,
-            backend_monitor,
+            monitors,
+            notifier,
+            metrics,
             ui,
             should_quit: false,
         })
@@ -124,19 +159,18 @@ impl App {
     async fn update(&mut self) -> Result<()> {
         // Skip updates if paused
         if let Temporality::Live = self.state.temporality {
-            // Update task states - convert to state::TaskUpdate with .into()
-            if let Some(task_updates) = self.task_monitor.poll().await {
-                // Convert monitor::task::TaskUpdate to state::TaskUpdate
-                let state_update: crate::state::TaskUpdate = task_updates.into();
-                self.state.update_tasks(vec![state_update]);
-            }
-            
-            // Update backend states - convert to state::BackendUpdate with .into()
-            if let Some(backend_updates) = self.backend_monitor.poll().await {
-                // Convert monitor::backend::BackendUpdate to state::BackendUpdate
-                let state_update: crate::state::BackendUpdate = backend_updates.into();
-                self.state.update_backends(vec![state_update]);
-            }
+            // Apply all pending task and backend updates
+            self.monitors.drain_into(&mut self.state).await;
+        }
+        
+        // Deliver any alerts raised by the updates
+        for alert in self.state.take_alerts() {
+            self.notifier.notify(&alert);
+        }
+        
+        // Publish a fresh metrics snapshot for scrapes
+        if let Some(metrics) = &mut self.metrics {
+            metrics.publish(&self.state);
         }
         
         Ok(())
@@ -157,36 +191,19 @@ impl App {
     
     /// Handles keyboard input.
     fn handle_key_event(&mut self, key: KeyEvent) -> Result<()> {
-        // Handle global keys first - similar to tokio-console's multi-level delegation
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => {
-                self.should_quit = true;
-                Ok(())
-            },
-            KeyCode::Char('p') => {
-                self.toggle_pause();
-                Ok(())
-            },
-            KeyCode::F(1) => {
-                self.toggle_help();
-                Ok(())
-            },
-            // Delegate to UI controller for view-specific handling
-            _ => {
-                // Process the UpdateKind result
-                let update_kind = self.ui.handle_key_event(key, &mut self.state)?;
-                
-                // Process the update kind (if needed)
-                match update_kind {
-                    ui::UpdateKind::Quit => self.should_quit = true,
-                    ui::UpdateKind::TogglePause => self.toggle_pause(),
-                    ui::UpdateKind::ToggleHelp => self.toggle_help(),
-                    _ => {} // Ignore other update kinds
-                }
-                
-                Ok(())
-            }
+        // Global shortcuts are resolved by the UI controller through the keymap,
+        // similar to tokio-console's multi-level delegation
+        let update_kind = self.ui.handle_key_event(key, &mut self.state)?;
+        
+        // Process the update kind (if needed)
+        match update_kind {
+            ui::UpdateKind::Quit => self.should_quit = true,
+            ui::UpdateKind::TogglePause => self.toggle_pause(),
+            ui::UpdateKind::ToggleHelp => self.toggle_help(),
+            _ => {} // Ignore other update kinds
         }
+        
+        Ok(())
     }
     
     /// Toggles pause state.
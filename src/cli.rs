@@ -0,0 +1,199 @@
+//! Command-line interface for the Crankshaft TUI.
+//!
+//! Without a subcommand the interactive dashboard is started. Subcommands
+//! provide non-interactive modes for scripts and cron jobs. The global
+//! options override the config file and can also be set through
+//! `CRANKSHAFT_TUI_*` environment variables (see `config`). Boolean options
+//! take their value after an equals sign (`--demo=false`), so a subcommand
+//! can follow a bare `--demo`.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use clap::{Args, Parser, Subcommand};
+use clap::builder::BoolishValueParser;
+
+use crate::plain::DEFAULT_SUMMARY_INTERVAL;
+use crate::snapshot::{SnapshotFormat, SnapshotSection};
+
+/// Real-time monitoring dashboard for Crankshaft task execution.
+#[derive(Debug, Parser)]
+#[command(name = "crankshaft-tui", version, about)]
+pub struct Cli {
+    /// Config file to read instead of the default location
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_CONFIG")]
+    pub config: Option<PathBuf>,
+    /// URL of the Crankshaft engine to connect to
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_ENGINE_URL")]
+    pub engine_url: Option<String>,
+    /// Use the built-in simulator instead of a live engine
+    #[arg(
+        long,
+        global = true,
+        env = "CRANKSHAFT_TUI_DEMO",
+        value_name = "BOOL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub demo: Option<bool>,
+    /// Task monitor polling interval in milliseconds
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_TASK_POLL_INTERVAL_MS", value_name = "MS")]
+    pub task_poll_interval_ms: Option<u64>,
+    /// Backend monitor polling interval in milliseconds
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_BACKEND_POLL_INTERVAL_MS", value_name = "MS")]
+    pub backend_poll_interval_ms: Option<u64>,
+    /// UI theme
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_THEME")]
+    pub theme: Option<String>,
+    /// Keymap file for global shortcuts
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_KEYMAP")]
+    pub keymap: Option<PathBuf>,
+    /// Workflow description listing the calls each call depends on
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_WORKFLOW_FILE")]
+    pub workflow_file: Option<PathBuf>,
+    /// Write logs to this file (logging is off by default)
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+    /// Log at debug level
+    #[arg(
+        long,
+        global = true,
+        env = "CRANKSHAFT_TUI_DEBUG",
+        value_name = "BOOL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub debug: Option<bool>,
+    /// UI tick rate in milliseconds
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_TICK_RATE_MS", value_name = "MS")]
+    pub tick_rate_ms: Option<u64>,
+    /// State refresh rate in milliseconds
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_REFRESH_RATE_MS", value_name = "MS")]
+    pub refresh_rate_ms: Option<u64>,
+    /// Serve Prometheus metrics on this address, e.g. 127.0.0.1:9090
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_METRICS_ADDR", value_name = "ADDR")]
+    pub metrics_addr: Option<SocketAddr>,
+    /// Seconds a running task's progress may stay unchanged before it is flagged as stalled
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_STALL_AFTER_SECS", value_name = "SECS")]
+    pub stall_after_secs: Option<u64>,
+    /// URL to POST alert payloads to
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_NOTIFY_WEBHOOK_URL", value_name = "URL")]
+    pub notify_webhook_url: Option<String>,
+    /// Ring the terminal bell on alerts
+    #[arg(
+        long,
+        global = true,
+        env = "CRANKSHAFT_TUI_NOTIFY_BELL",
+        value_name = "BOOL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub notify_bell: Option<bool>,
+    /// Emit desktop notifications on alerts
+    #[arg(
+        long,
+        global = true,
+        env = "CRANKSHAFT_TUI_NOTIFY_DESKTOP",
+        value_name = "BOOL",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "true",
+        value_parser = BoolishValueParser::new()
+    )]
+    pub notify_desktop: Option<bool>,
+    /// Seconds during which repeated alerts from one source are suppressed
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_NOTIFY_DEDUP_WINDOW_SECS", value_name = "SECS")]
+    pub notify_dedup_window_secs: Option<u64>,
+    /// Number of webhook retries after the first attempt
+    #[arg(long, global = true, env = "CRANKSHAFT_TUI_NOTIFY_MAX_RETRIES", value_name = "N")]
+    pub notify_max_retries: Option<u32>,
+    /// Draw a compact progress summary below the prompt instead of the full-screen dashboard
+    #[arg(long)]
+    pub inline: bool,
+    /// Print timestamped lines instead of drawing (the default when stdout is not a terminal)
+    #[arg(long)]
+    pub plain: bool,
+    /// Print lines until no tasks are active, then exit non-zero if any task failed
+    #[arg(long)]
+    pub wait: bool,
+    /// Seconds between summary lines in line-oriented output
+    #[arg(long, default_value_t = DEFAULT_SUMMARY_INTERVAL.as_secs())]
+    pub summary_interval: u64,
+    /// Non-interactive mode to run instead of the dashboard
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Available subcommands.
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Print the current tasks and backends once and exit
+    Snapshot(SnapshotArgs),
+    /// Write a self-contained HTML run report and exit
+    Report(ReportArgs),
+}
+
+/// Arguments for the `snapshot` subcommand.
+#[derive(Debug, Args)]
+pub struct SnapshotArgs {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = SnapshotFormat::Table)]
+    pub format: SnapshotFormat,
+    /// Only print tasks or only print backends
+    #[arg(long, value_enum)]
+    pub only: Option<SnapshotSection>,
+    /// Seconds to wait for the initial sync before giving up
+    #[arg(long, default_value_t = 10)]
+    pub timeout: u64,
+}
+
+/// Arguments for the `report` subcommand.
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    /// Path of the HTML file to write
+    #[arg(short, long, default_value = "crankshaft-report.html")]
+    pub output: PathBuf,
+    /// Seconds to wait for the initial sync before giving up
+    #[arg(long, default_value_t = 10)]
+    pub timeout: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use rstest::rstest;
+
+    #[test]
+    fn definition_is_valid() {
+        Cli::command().debug_assert();
+    }
+
+    #[rstest]
+    #[case(&["--demo"], Some(true))]
+    #[case(&["--demo=false"], Some(false))]
+    #[case(&["--demo=no"], Some(false))]
+    #[case(&["--demo", "snapshot"], Some(true))]
+    fn demo_flag(#[case] args: &[&str], #[case] expected: Option<bool>) {
+        let cli = Cli::try_parse_from(["crankshaft-tui"].iter().chain(args)).unwrap();
+        assert_eq!(cli.demo, expected);
+    }
+
+    #[test]
+    fn bare_bool_flags_before_a_subcommand() {
+        let cli = Cli::try_parse_from([
+            "crankshaft-tui", "--debug", "--notify-bell", "--notify-desktop", "--demo", "snapshot", "--format", "json",
+        ]).unwrap();
+        assert_eq!((cli.debug, cli.notify_bell, cli.notify_desktop), (Some(true), Some(true), Some(true)));
+        assert!(matches!(cli.command, Some(Command::Snapshot(SnapshotArgs { format: SnapshotFormat::Json, .. }))));
+
+        let cli = Cli::try_parse_from(["crankshaft-tui", "report", "--debug"]).unwrap();
+        assert_eq!(cli.debug, Some(true));
+        assert!(matches!(cli.command, Some(Command::Report(_))));
+    }
+}
@@ -0,0 +1,311 @@
+//! Layered application configuration.
+//!
+//! Settings are resolved in order, each layer overriding the one before:
+//!
+//! 1. Built-in defaults
+//! 2. The user config file (`--config`, or `crankshaft-tui/config.toml` in
+//!    the platform config directory)
+//! 3. `CRANKSHAFT_TUI_*` environment variables
+//! 4. Command-line flags
+//!
+//! Environment variables and flags are both handled by `clap`, which already
+//! gives flags precedence over the environment.
+
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use eyre::{Result, WrapErr};
+use serde::Deserialize;
+
+use crate::app::AppConfig;
+use crate::cli::Cli;
+use crate::monitor::{MonitorManager, DEFAULT_BACKEND_POLL_INTERVAL, DEFAULT_ENGINE_URL, DEFAULT_TASK_POLL_INTERVAL};
+use crate::notify::{NotifyConfig, DEFAULT_DEDUP_WINDOW, DEFAULT_MAX_RETRIES};
+use crate::state::{StallPolicy, WorkflowDescription, DEFAULT_STALL_AFTER};
+use crate::ui::{Keymap, Theme};
+
+/// Name of the config file inside the config directory.
+const CONFIG_FILE: &str = "crankshaft-tui/config.toml";
+
+/// Resolved configuration.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// URL of the Crankshaft engine
+    pub engine_url: String,
+    /// Use the built-in simulator instead of a live engine
+    pub demo: bool,
+    /// Task monitor polling interval in milliseconds
+    pub task_poll_interval_ms: u64,
+    /// Backend monitor polling interval in milliseconds
+    pub backend_poll_interval_ms: u64,
+    /// UI tick rate in milliseconds
+    pub tick_rate_ms: u64,
+    /// State refresh rate in milliseconds
+    pub refresh_rate_ms: u64,
+    /// Built-in theme name, theme file path, or name of a file in the themes directory
+    pub theme: String,
+    /// Path of a keymap file
+    pub keymap: Option<PathBuf>,
+    /// Path of a workflow description with call dependencies
+    pub workflow_file: Option<PathBuf>,
+    /// Path of the log file (logging is disabled if unset)
+    pub log_file: Option<PathBuf>,
+    /// Log at debug level
+    pub debug: bool,
+    /// Address to serve Prometheus metrics on
+    pub metrics_addr: Option<SocketAddr>,
+    /// Seconds a running task's progress may stay unchanged before it is
+    /// flagged as stalled
+    pub stall_after_secs: u64,
+    /// Alert delivery settings
+    pub notify: NotifySettings,
+}
+
+/// Alert delivery settings in the config file.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotifySettings {
+    /// URL to POST alert payloads to
+    pub webhook_url: Option<String>,
+    /// Ring the terminal bell on alerts
+    pub bell: bool,
+    /// Emit desktop notification escape sequences on alerts
+    pub desktop: bool,
+    /// Seconds during which repeated alerts from one source are suppressed
+    pub dedup_window_secs: u64,
+    /// Number of webhook retries after the first attempt
+    pub max_retries: u32,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            engine_url: DEFAULT_ENGINE_URL.to_string(),
+            demo: true,
+            task_poll_interval_ms: DEFAULT_TASK_POLL_INTERVAL.as_millis() as u64,
+            backend_poll_interval_ms: DEFAULT_BACKEND_POLL_INTERVAL.as_millis() as u64,
+            tick_rate_ms: 250,
+            refresh_rate_ms: 1000,
+            theme: "dark".to_string(),
+            keymap: None,
+            workflow_file: None,
+            log_file: None,
+            debug: false,
+            metrics_addr: None,
+            stall_after_secs: DEFAULT_STALL_AFTER.as_secs(),
+            notify: NotifySettings::default(),
+        }
+    }
+}
+
+impl Default for NotifySettings {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            bell: false,
+            desktop: false,
+            dedup_window_secs: DEFAULT_DEDUP_WINDOW.as_secs(),
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+}
+
+impl Config {
+    /// Resolve the configuration from the config file, environment and flags.
+    pub fn load(cli: &Cli) -> Result<Self> {
+        // An explicitly requested file must exist; the default one is optional
+        let mut config = match &cli.config {
+            Some(path) => Self::from_file(path)?,
+            None => match default_path() {
+                Some(path) if path.exists() => Self::from_file(&path)?,
+                _ => Self::default(),
+            },
+        };
+
+        config.apply_cli(cli);
+        Ok(config)
+    }
+
+    /// Read a config file, using defaults for missing settings.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read config file {}", path.display()))?;
+        toml::from_str(&contents).wrap_err_with(|| format!("invalid config file {}", path.display()))
+    }
+
+    /// Override settings with flags and environment variables.
+    fn apply_cli(&mut self, cli: &Cli) {
+        if let Some(engine_url) = &cli.engine_url {
+            self.engine_url = engine_url.clone();
+        }
+        if let Some(demo) = cli.demo {
+            self.demo = demo;
+        }
+        if let Some(interval) = cli.task_poll_interval_ms {
+            self.task_poll_interval_ms = interval;
+        }
+        if let Some(interval) = cli.backend_poll_interval_ms {
+            self.backend_poll_interval_ms = interval;
+        }
+        if let Some(theme) = &cli.theme {
+            self.theme = theme.clone();
+        }
+        if let Some(keymap) = &cli.keymap {
+            self.keymap = Some(keymap.clone());
+        }
+        if let Some(workflow_file) = &cli.workflow_file {
+            self.workflow_file = Some(workflow_file.clone());
+        }
+        if let Some(log_file) = &cli.log_file {
+            self.log_file = Some(log_file.clone());
+        }
+        if let Some(debug) = cli.debug {
+            self.debug = debug;
+        }
+        if let Some(rate) = cli.tick_rate_ms {
+            self.tick_rate_ms = rate;
+        }
+        if let Some(rate) = cli.refresh_rate_ms {
+            self.refresh_rate_ms = rate;
+        }
+        if let Some(addr) = cli.metrics_addr {
+            self.metrics_addr = Some(addr);
+        }
+        if let Some(secs) = cli.stall_after_secs {
+            self.stall_after_secs = secs;
+        }
+        if let Some(url) = &cli.notify_webhook_url {
+            self.notify.webhook_url = Some(url.clone());
+        }
+        if let Some(bell) = cli.notify_bell {
+            self.notify.bell = bell;
+        }
+        if let Some(desktop) = cli.notify_desktop {
+            self.notify.desktop = desktop;
+        }
+        if let Some(secs) = cli.notify_dedup_window_secs {
+            self.notify.dedup_window_secs = secs;
+        }
+        if let Some(retries) = cli.notify_max_retries {
+            self.notify.max_retries = retries;
+        }
+    }
+
+    /// Create a monitor manager for the configured engine.
+    pub fn monitor_manager(&self) -> MonitorManager {
+        MonitorManager::new(self.engine_url.clone())
+            .with_demo_mode(self.demo)
+            .with_poll_intervals(
+                Duration::from_millis(self.task_poll_interval_ms),
+                Duration::from_millis(self.backend_poll_interval_ms),
+            )
+    }
+
+    /// Build the application configuration, loading the theme, keymap and
+    /// workflow description.
+    pub fn app_config(&self) -> Result<AppConfig> {
+        let keymap = match &self.keymap {
+            Some(path) => Keymap::load(path)?,
+            None => Keymap::default(),
+        };
+        let workflow = match &self.workflow_file {
+            Some(path) => WorkflowDescription::load(path)?,
+            None => WorkflowDescription::default(),
+        };
+
+        Ok(AppConfig {
+            tick_rate_ms: self.tick_rate_ms,
+            refresh_rate_ms: self.refresh_rate_ms,
+            debug_mode: self.debug,
+            notify: NotifyConfig {
+                webhook_url: self.notify.webhook_url.clone(),
+                bell: self.notify.bell,
+                desktop: self.notify.desktop,
+                dedup_window: Duration::from_secs(self.notify.dedup_window_secs),
+                max_retries: self.notify.max_retries,
+                ..NotifyConfig::default()
+            },
+            metrics_addr: self.metrics_addr,
+            stall_policy: StallPolicy::default()
+                .with_stall_after(Duration::from_secs(self.stall_after_secs)),
+            workflow,
+            theme: Theme::resolve(&self.theme)?,
+            keymap,
+        })
+    }
+}
+
+/// Default location of the user config file.
+pub fn default_path() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(CONFIG_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+    use pretty_assertions::assert_eq;
+
+    fn parse(toml: &str) -> Result<Config, toml::de::Error> {
+        toml::from_str(toml)
+    }
+
+    #[test]
+    fn empty_file_gives_defaults() {
+        let config = parse("").unwrap();
+        let defaults = Config::default();
+        assert_eq!(config.engine_url, defaults.engine_url);
+        assert_eq!(config.tick_rate_ms, 250);
+        assert_eq!(config.stall_after_secs, DEFAULT_STALL_AFTER.as_secs());
+        assert_eq!(config.notify.dedup_window_secs, DEFAULT_DEDUP_WINDOW.as_secs());
+        assert_eq!(config.notify.max_retries, DEFAULT_MAX_RETRIES);
+        assert!(config.demo);
+    }
+
+    #[test]
+    fn file_overrides_only_the_settings_it_sets() {
+        let config = parse("demo = false\ntick_rate_ms = 100\n[notify]\nbell = true\n").unwrap();
+        assert!(!config.demo);
+        assert_eq!(config.tick_rate_ms, 100);
+        assert_eq!(config.refresh_rate_ms, 1000);
+        assert!(config.notify.bell);
+        assert_eq!(config.notify.max_retries, DEFAULT_MAX_RETRIES);
+    }
+
+    #[test]
+    fn rejects_unknown_settings() {
+        let error = parse("engine_uri = \"http://localhost\"").unwrap_err();
+        assert!(error.to_string().contains("engine_uri"));
+        assert!(parse("[notify]\nwebhook = \"http://localhost\"").is_err());
+    }
+
+    #[test]
+    fn flags_override_environment_over_file_over_defaults() {
+        let path = std::env::temp_dir().join(format!("crankshaft-tui-config-{}.toml", std::process::id()));
+        std::fs::write(&path, "tick_rate_ms = 100\nrefresh_rate_ms = 200\nstall_after_secs = 5\n").unwrap();
+        // Only this test reads these variables
+        std::env::set_var("CRANKSHAFT_TUI_TICK_RATE_MS", "300");
+        std::env::set_var("CRANKSHAFT_TUI_REFRESH_RATE_MS", "400");
+
+        let cli = Cli::try_parse_from([
+            "crankshaft-tui", "--config", path.to_str().unwrap(), "--refresh-rate-ms", "500",
+        ]);
+        std::env::remove_var("CRANKSHAFT_TUI_TICK_RATE_MS");
+        std::env::remove_var("CRANKSHAFT_TUI_REFRESH_RATE_MS");
+        let config = Config::load(&cli.unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        let config = config.unwrap();
+        assert_eq!(config.refresh_rate_ms, 500);
+        assert_eq!(config.tick_rate_ms, 300);
+        assert_eq!(config.stall_after_secs, 5);
+        assert_eq!(config.backend_poll_interval_ms, Config::default().backend_poll_interval_ms);
+    }
+
+    #[test]
+    fn explicit_config_file_must_exist() {
+        let cli = Cli::try_parse_from(["crankshaft-tui", "--config", "/nonexistent/crankshaft-tui.toml"]).unwrap();
+        assert!(Config::load(&cli).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+//! Export of task data and run reports.
+//!
+//! The task list exports exactly the rows and columns it shows, in the order
+//! it shows them, as CSV or JSON. Snapshots write full task rows with a fixed
+//! schema. The run report is a self-contained HTML file that summarises a
+//! whole run and can be attached to tickets.
+
+pub mod report;
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use eyre::Result;
+use serde_json::{Map, Value};
+
+use crate::state::TaskState;
+
+/// Export file format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// File extension for the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Rows as a view shows them, under its column names.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExportTable {
+    /// Column names, in display order
+    pub columns: Vec<&'static str>,
+    /// One value per column for each row, `Null` for empty cells
+    pub rows: Vec<Vec<Value>>,
+}
+
+/// Column headers for full task rows.
+pub const TASK_EXPORT_COLUMNS: [&str; 16] = [
+    "id", "name", "workflow_id", "call_name", "shard_index", "status", "progress",
+    "duration_secs", "backend", "cpu_cores", "memory_bytes", "requested_cpu_cores",
+    "requested_memory_bytes", "start_time", "end_time", "error",
+];
+
+/// A full task row with a fixed schema.
+#[derive(Debug)]
+pub struct TaskExportRow<'a> {
+    pub id: u64,
+    pub name: &'a str,
+    pub workflow_id: Option<&'a str>,
+    pub call_name: Option<&'a str>,
+    pub shard_index: Option<u32>,
+    pub status: &'static str,
+    pub progress: Option<f32>,
+    pub duration_secs: i64,
+    pub backend: &'a str,
+    pub cpu_cores: f32,
+    pub memory_bytes: u64,
+    pub requested_cpu_cores: Option<f32>,
+    pub requested_memory_bytes: Option<u64>,
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    pub error: Option<&'a str>,
+}
+
+impl<'a> From<&'a TaskState> for TaskExportRow<'a> {
+    fn from(task: &'a TaskState) -> Self {
+        Self {
+            id: task.id,
+            name: &task.name,
+            workflow_id: task.workflow_id.as_deref(),
+            call_name: task.call_name.as_deref(),
+            shard_index: task.shard_index,
+            status: task.status.to_string(),
+            progress: task.progress,
+            duration_secs: task.duration().num_seconds(),
+            backend: &task.backend,
+            cpu_cores: task.cpu_usage.0,
+            memory_bytes: task.memory_usage.0,
+            requested_cpu_cores: task.requested_cpu.map(|c| c.0),
+            requested_memory_bytes: task.requested_memory.map(|m| m.0),
+            start_time: task.start_time,
+            end_time: task.end_time,
+            error: task.error.as_deref(),
+        }
+    }
+}
+
+impl TaskExportRow<'_> {
+    /// Format the row as cells matching `TASK_EXPORT_COLUMNS`.
+    pub fn cells(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.name.to_string(),
+            self.workflow_id.unwrap_or_default().to_string(),
+            self.call_name.unwrap_or_default().to_string(),
+            self.shard_index.map(|i| i.to_string()).unwrap_or_default(),
+            self.status.to_string(),
+            self.progress.map(|p| format!("{:.3}", p)).unwrap_or_default(),
+            self.duration_secs.to_string(),
+            self.backend.to_string(),
+            format!("{:.3}", self.cpu_cores),
+            self.memory_bytes.to_string(),
+            self.requested_cpu_cores.map(|c| format!("{:.3}", c)).unwrap_or_default(),
+            self.requested_memory_bytes.map(|m| m.to_string()).unwrap_or_default(),
+            self.start_time.to_rfc3339(),
+            self.end_time.map(|t| t.to_rfc3339()).unwrap_or_default(),
+            self.error.unwrap_or_default().to_string(),
+        ]
+    }
+}
+
+/// Write a table in the given format, preserving its row order. JSON rows
+/// are objects keyed by column name.
+pub fn write_table(out: &mut impl Write, table: &ExportTable, format: ExportFormat) -> Result<()> {
+    match format {
+        ExportFormat::Csv => {
+            let rows = table.rows.iter().map(|row| row.iter().map(csv_value).collect());
+            write_csv(out, &table.columns, rows)?;
+        }
+        ExportFormat::Json => {
+            let rows: Vec<Map<String, Value>> = table.rows.iter()
+                .map(|row| table.columns.iter().map(|c| c.to_string()).zip(row.iter().cloned()).collect())
+                .collect();
+            serde_json::to_writer_pretty(&mut *out, &rows)?;
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Export a table to a timestamped file in the current directory.
+///
+/// Returns the path of the written file.
+pub fn export_table(table: &ExportTable, format: ExportFormat) -> Result<PathBuf> {
+    let path = timestamped_path("crankshaft-tasks", format.extension());
+    let mut out = BufWriter::new(File::create(&path)?);
+    write_table(&mut out, table, format)?;
+    out.flush()?;
+    Ok(path)
+}
+
+/// A number rounded to three decimals, so that `f32` values export as
+/// they read rather than with conversion noise.
+pub fn rounded(value: f64) -> Value {
+    Value::from((value * 1000.0).round() / 1000.0)
+}
+
+/// Format a value as a CSV cell, empty for `Null`.
+fn csv_value(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Build a file name like `prefix-20240101-120000.ext`.
+pub fn timestamped_path(prefix: &str, extension: &str) -> PathBuf {
+    PathBuf::from(format!("{}-{}.{}", prefix, Utc::now().format("%Y%m%d-%H%M%S"), extension))
+}
+
+/// Write rows as CSV with a header line.
+pub fn write_csv(
+    out: &mut impl Write,
+    columns: &[&str],
+    rows: impl Iterator<Item = Vec<String>>,
+) -> io::Result<()> {
+    writeln!(out, "{}", columns.join(","))?;
+    for row in rows {
+        let fields: Vec<Cow<str>> = row.iter().map(|f| csv_field(f)).collect();
+        writeln!(out, "{}", fields.join(","))?;
+    }
+    Ok(())
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break.
+pub fn csv_field(field: &str) -> Cow<'_, str> {
+    if field.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", field.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use serde_json::json;
+
+    #[rstest]
+    #[case("plain", "plain")]
+    #[case("", "")]
+    #[case("a,b", "\"a,b\"")]
+    #[case("say \"hi\"", "\"say \"\"hi\"\"\"")]
+    #[case("line\nbreak", "\"line\nbreak\"")]
+    #[case("carriage\rreturn", "\"carriage\rreturn\"")]
+    fn csv_field_quotes_when_needed(#[case] field: &str, #[case] expected: &str) {
+        assert_eq!(csv_field(field), expected);
+    }
+
+    fn table() -> ExportTable {
+        ExportTable {
+            columns: vec!["id", "name", "progress", "error"],
+            rows: vec![
+                vec![json!(1), json!("align-1"), rounded(0.1f32 as f64), Value::Null],
+                vec![json!(2), json!("call-2"), Value::Null, json!("exit 1, \"oom\"")],
+            ],
+        }
+    }
+
+    #[test]
+    fn writes_csv_in_row_order() {
+        let mut out = Vec::new();
+        write_table(&mut out, &table(), ExportFormat::Csv).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "id,name,progress,error\n1,align-1,0.1,\n2,call-2,,\"exit 1, \"\"oom\"\"\"\n",
+        );
+    }
+
+    #[test]
+    fn writes_json_objects_by_column() {
+        let mut out = Vec::new();
+        write_table(&mut out, &table(), ExportFormat::Json).unwrap();
+        let json: Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json, json!([
+            {"id": 1, "name": "align-1", "progress": 0.1, "error": null},
+            {"id": 2, "name": "call-2", "progress": null, "error": "exit 1, \"oom\""},
+        ]));
+    }
+}
@@ -0,0 +1,298 @@
+//! HTML run report.
+//!
+//! Renders a single self-contained HTML file (inline CSS, no scripts or
+//! external assets) with summary counts, a per-backend breakdown, the
+//! distribution of task durations and the list of failed tasks.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::Duration;
+use chrono::Utc;
+use eyre::Result;
+
+use crate::cli::ReportArgs;
+use crate::config::Config;
+use crate::metrics::DURATION_BUCKETS;
+use crate::snapshot::sync_state;
+use crate::state::{AppState, TaskState, TaskStatus};
+use crate::ui::task_list::format_duration;
+
+/// Stylesheet embedded in every report.
+const STYLE: &str = "\
+body { font-family: -apple-system, 'Segoe UI', Helvetica, Arial, sans-serif; margin: 2em; color: #222; }
+h1 { margin-bottom: 0; }
+.generated { color: #666; margin-top: 0.2em; }
+.cards { display: flex; gap: 1em; flex-wrap: wrap; }
+.card { border: 1px solid #ddd; border-radius: 6px; padding: 0.8em 1.2em; min-width: 7em; }
+.card .value { font-size: 1.8em; font-weight: bold; }
+.card .label { color: #666; }
+table { border-collapse: collapse; margin: 1em 0; }
+th, td { border-bottom: 1px solid #ddd; padding: 0.35em 0.8em; text-align: left; }
+th { background: #f5f5f5; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+.bar { background: #4a90d9; height: 1em; }
+.status-Created { color: #1f5fbf; }
+.status-Queued { color: #a07800; }
+.status-Running { color: #1d8a2e; }
+.status-Completed { color: #0a7f8c; }
+.status-Failed { color: #c0392b; }
+.status-Cancelled { color: #777; }
+";
+
+/// Run the report subcommand.
+pub async fn run(args: ReportArgs, config: &Config) -> Result<()> {
+    let state = sync_state(config.monitor_manager(), Duration::from_secs(args.timeout)).await?;
+    write_report_file(&args.output, &state)?;
+    println!("Wrote run report to {}", args.output.display());
+    Ok(())
+}
+
+/// Write the run report to a file.
+pub fn write_report_file(path: &Path, state: &AppState) -> Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(render(state).as_bytes())?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Render the run report as an HTML document.
+pub fn render(state: &AppState) -> String {
+    let mut tasks: Vec<&TaskState> = state.tasks.values().collect();
+    tasks.sort_by_key(|t| t.id);
+
+    let mut html = String::new();
+    let _ = writeln!(html, "<!DOCTYPE html>");
+    let _ = writeln!(html, "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">");
+    let _ = writeln!(html, "<title>Crankshaft run report</title>");
+    let _ = writeln!(html, "<style>\n{}</style>\n</head>\n<body>", STYLE);
+    let _ = writeln!(html, "<h1>Crankshaft run report</h1>");
+    let _ = writeln!(html, "<p class=\"generated\">Generated {}</p>", Utc::now().format("%Y-%m-%d %H:%M:%S UTC"));
+
+    render_summary(&mut html, &tasks);
+    render_backends(&mut html, state, &tasks);
+    render_durations(&mut html, &tasks);
+    render_failures(&mut html, &tasks);
+
+    let _ = writeln!(html, "</body>\n</html>");
+    html
+}
+
+/// Summary cards with task counts by status.
+fn render_summary(html: &mut String, tasks: &[&TaskState]) {
+    let _ = writeln!(html, "<h2>Summary</h2>\n<div class=\"cards\">");
+    card(html, "Total", tasks.len(), "");
+    for status in TaskStatus::ALL {
+        let count = tasks.iter().filter(|t| t.status == status).count();
+        card(html, status.to_string(), count, status.to_string());
+    }
+    let _ = writeln!(html, "</div>");
+}
+
+/// Write a single summary card.
+fn card(html: &mut String, label: &str, value: usize, status: &str) {
+    let _ = writeln!(
+        html,
+        "<div class=\"card\"><div class=\"value status-{}\">{}</div><div class=\"label\">{}</div></div>",
+        status, value, label
+    );
+}
+
+/// Per-backend breakdown of task counts, durations and failure rate.
+fn render_backends(html: &mut String, state: &AppState, tasks: &[&TaskState]) {
+    let mut names: Vec<&str> = state.backends.keys().map(String::as_str).collect();
+    names.extend(tasks.iter().map(|t| t.backend.as_str()));
+    names.sort_unstable();
+    names.dedup();
+
+    let _ = writeln!(html, "<h2>Backends</h2>\n<table>");
+    let _ = writeln!(
+        html,
+        "<tr><th>Backend</th><th>Kind</th><th>Health</th><th>Tasks</th><th>Running</th>\
+         <th>Completed</th><th>Failed</th><th>Failure rate</th><th>Mean duration</th></tr>"
+    );
+    for name in names {
+        let backend_tasks: Vec<&TaskState> = tasks.iter().copied().filter(|t| t.backend == name).collect();
+        let count = |status: TaskStatus| backend_tasks.iter().filter(|t| t.status == status).count();
+        let completed = count(TaskStatus::Completed);
+        let failed = count(TaskStatus::Failed);
+
+        // Failure rate is relative to the tasks that reached a final result
+        let failure_rate = if completed + failed > 0 {
+            format!("{:.1}%", failed as f64 * 100.0 / (completed + failed) as f64)
+        } else {
+            "-".to_string()
+        };
+
+        let finished: Vec<chrono::Duration> = backend_tasks.iter()
+            .filter(|t| t.status.is_terminal())
+            .map(|t| t.duration())
+            .collect();
+        let mean_duration = if finished.is_empty() {
+            "-".to_string()
+        } else {
+            let total: i64 = finished.iter().map(|d| d.num_seconds()).sum();
+            format_duration(&chrono::Duration::seconds(total / finished.len() as i64))
+        };
+
+        let (kind, health) = match state.backends.get(name) {
+            Some(backend) => (backend.kind.to_string(), backend.health.to_string()),
+            None => ("-".to_string(), "-".to_string()),
+        };
+
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td>\
+             <td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td><td class=\"num\">{}</td></tr>",
+            escape(name),
+            kind,
+            health,
+            backend_tasks.len(),
+            count(TaskStatus::Running),
+            completed,
+            failed,
+            failure_rate,
+            mean_duration
+        );
+    }
+    let _ = writeln!(html, "</table>");
+}
+
+/// Histogram of finished task durations.
+fn render_durations(html: &mut String, tasks: &[&TaskState]) {
+    let durations: Vec<i64> = tasks.iter()
+        .filter(|t| t.status.is_terminal())
+        .map(|t| t.duration().num_seconds())
+        .collect();
+
+    let _ = writeln!(html, "<h2>Duration distribution</h2>");
+    if durations.is_empty() {
+        let _ = writeln!(html, "<p>No finished tasks.</p>");
+        return;
+    }
+
+    // Count tasks per bucket, with a final open-ended bucket
+    let mut counts = vec![0usize; DURATION_BUCKETS.len() + 1];
+    for duration in &durations {
+        let index = DURATION_BUCKETS.iter()
+            .position(|upper| *duration as f64 <= *upper)
+            .unwrap_or(DURATION_BUCKETS.len());
+        counts[index] += 1;
+    }
+
+    let mut buckets: Vec<(String, usize)> = DURATION_BUCKETS.iter()
+        .zip(&counts)
+        .map(|(upper, count)| (format!("≤ {}", format_duration(&chrono::Duration::seconds(*upper as i64))), *count))
+        .collect();
+    let longest = DURATION_BUCKETS[DURATION_BUCKETS.len() - 1] as i64;
+    buckets.push((format!("> {}", format_duration(&chrono::Duration::seconds(longest))), counts[DURATION_BUCKETS.len()]));
+
+    let max = buckets.iter().map(|(_, c)| *c).max().unwrap_or(1).max(1);
+    let _ = writeln!(html, "<table>\n<tr><th>Duration</th><th>Tasks</th><th></th></tr>");
+    for (label, count) in buckets {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td class=\"num\">{}</td><td><div class=\"bar\" style=\"width: {}px\"></div></td></tr>",
+            label,
+            count,
+            count * 300 / max
+        );
+    }
+    let _ = writeln!(html, "</table>");
+}
+
+/// List of failed tasks with their failure reasons.
+fn render_failures(html: &mut String, tasks: &[&TaskState]) {
+    let failed: Vec<&TaskState> = tasks.iter().copied().filter(|t| t.status == TaskStatus::Failed).collect();
+
+    let _ = writeln!(html, "<h2>Failures ({})</h2>", failed.len());
+    if failed.is_empty() {
+        let _ = writeln!(html, "<p>No failed tasks.</p>");
+        return;
+    }
+
+    let _ = writeln!(
+        html,
+        "<table>\n<tr><th>ID</th><th>Name</th><th>Backend</th><th>Started</th><th>Duration</th><th>Error</th></tr>"
+    );
+    for task in failed {
+        let _ = writeln!(
+            html,
+            "<tr><td class=\"num\">{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"num\">{}</td><td>{}</td></tr>",
+            task.id,
+            escape(&task.name),
+            escape(&task.backend),
+            task.start_time.format("%Y-%m-%d %H:%M:%S"),
+            format_duration(&task.duration()),
+            escape(task.error.as_deref().unwrap_or("-"))
+        );
+    }
+    let _ = writeln!(html, "</table>");
+}
+
+/// Escape text for inclusion in HTML.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use crate::state::testing::{self, state_with};
+
+    #[rstest]
+    #[case("plain text", "plain text")]
+    #[case("", "")]
+    #[case("a < b && c > d", "a &lt; b &amp;&amp; c &gt; d")]
+    #[case("say \"hi\" & 'bye'", "say &quot;hi&quot; &amp; &#39;bye&#39;")]
+    #[case("<script>alert(1)</script>", "&lt;script&gt;alert(1)&lt;/script&gt;")]
+    fn escape_html(#[case] text: &str, #[case] expected: &str) {
+        assert_eq!(escape(text), expected);
+    }
+
+    #[test]
+    fn renders_empty_run() {
+        let html = render(&AppState::new());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.trim_end().ends_with("</html>"));
+        assert!(html.contains("<div class=\"value status-\">0</div><div class=\"label\">Total</div>"));
+        assert!(html.contains("<p>No finished tasks.</p>"));
+        assert!(html.contains("<h2>Failures (0)</h2>\n<p>No failed tasks.</p>"));
+    }
+
+    #[test]
+    fn renders_counts_and_escaped_failures() {
+        let mut failed = testing::task(2, "align<&>");
+        failed.transition(TaskStatus::Failed, Utc::now());
+        failed.error = Some("missing <input> \"ref.fa\"".into());
+        let mut done = testing::task(1, "align");
+        done.transition(TaskStatus::Completed, Utc::now());
+        let html = render(&state_with([done, failed, testing::task(3, "call")]));
+
+        assert!(html.contains("<div class=\"value status-\">3</div><div class=\"label\">Total</div>"));
+        assert!(html.contains("<div class=\"value status-Failed\">1</div>"));
+        assert!(html.contains(
+            "<tr><td>docker</td><td>-</td><td>-</td><td class=\"num\">3</td><td class=\"num\">0</td>\
+             <td class=\"num\">1</td><td class=\"num\">1</td><td class=\"num\">50.0%</td>"
+        ));
+        assert!(html.contains("<tr><td>≤ 10s</td><td class=\"num\">2</td>"));
+        assert!(html.contains("<h2>Failures (1)</h2>"));
+        assert!(html.contains("<td>align&lt;&amp;&gt;-2</td>"));
+        assert!(html.contains("<td>missing &lt;input&gt; &quot;ref.fa&quot;</td>"));
+        assert!(!html.contains("<input>"));
+    }
+}
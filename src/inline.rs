@@ -0,0 +1,87 @@
+//! Inline progress mode.
+//!
+//! Draws a compact summary in a few lines below the shell prompt using an
+//! inline viewport, so the terminal keeps its scrollback and the summary is
+//! left in place when the program exits.
+
+use std::io::{self, Stdout};
+use std::time::Duration;
+use crossterm::event::{self, Event as CrosstermEvent, KeyCode, KeyEventKind, KeyModifiers};
+use eyre::Result;
+use ratatui::backend::CrosstermBackend;
+use ratatui::{Terminal, TerminalOptions, Viewport};
+
+use crate::monitor::MonitorManager;
+use crate::state::AppState;
+use crate::ui::{InlineView, Theme};
+use crate::ui::inline::INLINE_HEIGHT;
+
+/// Interval between redraws.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Run the inline progress mode until the user presses `q`, `Esc` or Ctrl-C.
+pub async fn run(mut manager: MonitorManager, theme: Theme) -> Result<()> {
+    manager.connect().await?;
+
+    let mut terminal = Terminal::with_options(
+        CrosstermBackend::new(io::stdout()),
+        TerminalOptions {
+            viewport: Viewport::Inline(INLINE_HEIGHT),
+        },
+    )?;
+
+    // Raw mode lets us read keys without echoing them into the summary
+    crossterm::terminal::enable_raw_mode()?;
+    let result = run_loop(&mut terminal, &mut manager, &theme).await;
+    let restored = restore(&mut terminal);
+
+    manager.disconnect().await?;
+    result.and(restored)
+}
+
+/// Redraw the summary until asked to quit.
+async fn run_loop(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    manager: &mut MonitorManager,
+    theme: &Theme,
+) -> Result<()> {
+    let mut state = AppState::new();
+
+    loop {
+        manager.drain_into(&mut state).await;
+        terminal.draw(|frame| InlineView::render(frame, frame.size(), &state, theme))?;
+
+        // Wait for input on a blocking thread so the monitors keep running
+        let key = tokio::task::spawn_blocking(|| -> io::Result<Option<crossterm::event::KeyEvent>> {
+            if event::poll(REFRESH_INTERVAL)? {
+                if let CrosstermEvent::Key(key) = event::read()? {
+                    return Ok(Some(key));
+                }
+            }
+            Ok(None)
+        }).await??;
+
+        if let Some(key) = key {
+            let quit = match key.code {
+                KeyCode::Char('q') | KeyCode::Esc => true,
+                KeyCode::Char('c') => key.modifiers.contains(KeyModifiers::CONTROL),
+                _ => false,
+            };
+            if quit && key.kind == KeyEventKind::Press {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Leave raw mode and move the cursor below the summary so it stays in scrollback.
+fn restore(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+    crossterm::terminal::disable_raw_mode()?;
+
+    let area = terminal.get_frame().size();
+    terminal.set_cursor(0, area.bottom().saturating_sub(1))?;
+    terminal.show_cursor()?;
+    println!();
+
+    Ok(())
+}
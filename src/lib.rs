@@ -3,7 +3,15 @@
 //! Core components for the Crankshaft monitoring dashboard.
 
 pub mod app;
+pub mod cli;
+pub mod config;
 pub mod event;
+pub mod export;
+pub mod inline;
+pub mod metrics;
 pub mod monitor;
+pub mod notify;
+pub mod plain;
+pub mod snapshot;
 pub mod state;
 pub mod ui;
\ No newline at end of file
@@ -3,24 +3,63 @@
 //! This application provides a terminal user interface for monitoring 
 //! Crankshaft task execution across different backends.
 
-use std::io;
+use std::fs::File;
+use std::io::{self, IsTerminal};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use clap::Parser;
 use color_eyre::Result;
-use crankshaft_tui::app::{App, AppConfig};
+use crankshaft_tui::app::App;
+use crankshaft_tui::cli::{Cli, Command};
+use crankshaft_tui::config::Config;
 use crankshaft_tui::event::EventHandler;
-use crankshaft_tui::ui::Ui;
+use crankshaft_tui::export::report;
+use crankshaft_tui::inline;
+use crankshaft_tui::plain::{self, PlainOptions};
+use crankshaft_tui::snapshot;
+use crankshaft_tui::ui::{Theme, Ui};
 use crankshaft_tui::state::AppState; 
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // Set up error handling
+    color_eyre::install()?;
+
+    // Resolve the config file, environment and flags
+    let cli = Cli::parse();
+    let config = Config::load(&cli)?;
+    init_logging(&config)?;
+
+    // Headless modes write to stdout, so they skip terminal setup
+    match cli.command {
+        Some(Command::Snapshot(args)) => return snapshot::run(args, &config).await,
+        Some(Command::Report(args)) => return report::run(args, &config).await,
+        None => {}
+    }
+    
+    // Without a terminal to draw on, fall back to line-oriented output
+    if cli.plain || cli.wait || !io::stdout().is_terminal() {
+        let options = PlainOptions {
+            wait: cli.wait,
+            summary_interval: Duration::from_secs(cli.summary_interval),
+        };
+        let any_failed = plain::run(config.monitor_manager(), options).await?;
+        if cli.wait && any_failed {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+    
+    // The inline summary draws below the prompt, so it also skips the alternate screen
+    if cli.inline {
+        return inline::run(config.monitor_manager(), Theme::resolve(&config.theme)?).await;
+    }
+
     // Setup terminal
     setup_terminal()?;
     
-    // Create app configuration
-    let config = AppConfig::new();
-
     // Create app instance and connect to Crankshaft engine
-    let mut app = App::new(config).await?;
+    let mut app = App::new(config.app_config()?, config.monitor_manager()).await?;
 
     // Create shared app state and UI components
     let app_state = Arc::new(Mutex::new(AppState::new()));
@@ -38,15 +77,25 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-fn setup_terminal() -> Result<()> {
-    // Set up error handling
-    color_eyre::install()?;
-    
-    // Setup logging
+/// Send logs to the configured log file; without one, logging stays off so
+/// nothing is written over the dashboard or into piped output.
+fn init_logging(config: &Config) -> Result<()> {
+    let Some(path) = &config.log_file else {
+        return Ok(());
+    };
+
+    let file = File::options().create(true).append(true).open(path)?;
+    let level = if config.debug { tracing::Level::DEBUG } else { tracing::Level::INFO };
     tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::DEBUG)
+        .with_max_level(level)
+        .with_ansi(false)
+        .with_writer(Mutex::new(file))
         .init();
-    
+
+    Ok(())
+}
+
+fn setup_terminal() -> Result<()> {
     // Configure terminal
     crossterm::terminal::enable_raw_mode()?;
     crossterm::execute!(
@@ -0,0 +1,245 @@
+//! Prometheus metrics export.
+//!
+//! This module renders the numbers shown in the TUI in the Prometheus text
+//! exposition format and serves them from an optional embedded HTTP server.
+//!
+//! `AppState` is not `Send`, so the server never touches it directly. Instead,
+//! the application loop periodically renders a snapshot and publishes it
+//! through a `MetricsExporter`, and the server answers scrapes with the most
+//! recent snapshot.
+
+pub mod server;
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+use eyre::Result;
+use tokio::sync::watch;
+
+use crate::state::{AppState, HealthStatus, TaskStatus};
+
+/// Upper bounds (in seconds) of the task duration histogram buckets.
+pub const DURATION_BUCKETS: [f64; 10] = [
+    10.0, 30.0, 60.0, 300.0, 600.0, 1800.0, 3600.0, 7200.0, 14400.0, 43200.0,
+];
+
+/// All backend health states, in display order.
+const HEALTH_STATES: [HealthStatus; 4] = [
+    HealthStatus::Healthy,
+    HealthStatus::Degraded,
+    HealthStatus::Unhealthy,
+    HealthStatus::Unknown,
+];
+
+/// Publishes metrics snapshots to the embedded HTTP server.
+pub struct MetricsExporter {
+    /// Channel holding the latest rendered snapshot
+    sender: watch::Sender<String>,
+    /// Minimum time between snapshots
+    interval: Duration,
+    /// When the last snapshot was published
+    last_publish: Option<Instant>,
+}
+
+impl MetricsExporter {
+    /// Start the metrics server on the given address.
+    pub async fn start(addr: SocketAddr, interval: Duration) -> Result<Self> {
+        let (sender, receiver) = watch::channel(String::new());
+        server::serve(addr, receiver).await?;
+
+        Ok(Self {
+            sender,
+            interval,
+            last_publish: None,
+        })
+    }
+
+    /// Render and publish a snapshot if the publish interval has elapsed.
+    pub fn publish(&mut self, state: &AppState) {
+        let due = match self.last_publish {
+            Some(last) => last.elapsed() >= self.interval,
+            None => true,
+        };
+        if due {
+            self.sender.send_replace(render(state));
+            self.last_publish = Some(Instant::now());
+        }
+    }
+}
+
+/// Render the application state in the Prometheus text exposition format.
+pub fn render(state: &AppState) -> String {
+    let mut out = String::new();
+
+    // Task counts by status and backend
+    let mut task_counts: BTreeMap<(&str, &str), usize> = BTreeMap::new();
+    for task in state.tasks.values() {
+        *task_counts.entry((task.backend.as_str(), task.status.to_string())).or_insert(0) += 1;
+    }
+    header(&mut out, "crankshaft_tasks", "gauge", "Number of tasks by status and backend.");
+    let mut backend_names: Vec<&str> = state.backends.keys().map(String::as_str).collect();
+    backend_names.extend(state.tasks.values().map(|t| t.backend.as_str()));
+    backend_names.sort_unstable();
+    backend_names.dedup();
+    for backend in &backend_names {
+        for status in TaskStatus::ALL {
+            let count = task_counts.get(&(*backend, status.to_string())).copied().unwrap_or(0);
+            let _ = writeln!(
+                out,
+                "crankshaft_tasks{{backend=\"{}\",status=\"{}\"}} {}",
+                escape(backend),
+                status.to_string().to_lowercase(),
+                count
+            );
+        }
+    }
+
+    // Backend gauges, sorted by name for stable output
+    let mut backends: Vec<_> = state.backends.values().collect();
+    backends.sort_by(|a, b| a.name.cmp(&b.name));
+
+    header(&mut out, "crankshaft_backend_health", "gauge", "Backend health state (1 for the current state, 0 otherwise).");
+    for backend in &backends {
+        for health in HEALTH_STATES {
+            let _ = writeln!(
+                out,
+                "crankshaft_backend_health{{backend=\"{}\",kind=\"{}\",state=\"{}\"}} {}",
+                escape(&backend.name),
+                backend.kind,
+                health.to_string().to_lowercase(),
+                u8::from(backend.health == health)
+            );
+        }
+    }
+
+    header(&mut out, "crankshaft_backend_cpu_usage_percent", "gauge", "Backend CPU usage in percent.");
+    for backend in &backends {
+        let _ = writeln!(out, "crankshaft_backend_cpu_usage_percent{{backend=\"{}\"}} {}", escape(&backend.name), backend.cpu_usage.0);
+    }
+
+    header(&mut out, "crankshaft_backend_memory_usage_percent", "gauge", "Backend memory usage in percent.");
+    for backend in &backends {
+        let _ = writeln!(out, "crankshaft_backend_memory_usage_percent{{backend=\"{}\"}} {}", escape(&backend.name), backend.memory_usage.0);
+    }
+
+    header(&mut out, "crankshaft_backend_running_tasks", "gauge", "Tasks currently running on the backend, as reported by the backend.");
+    for backend in &backends {
+        let _ = writeln!(out, "crankshaft_backend_running_tasks{{backend=\"{}\"}} {}", escape(&backend.name), backend.running_tasks);
+    }
+
+    // Duration histogram of finished tasks
+    header(&mut out, "crankshaft_task_duration_seconds", "histogram", "Duration of finished tasks.");
+    for backend in &backend_names {
+        let durations: Vec<f64> = state.tasks.values()
+            .filter(|t| t.backend == *backend)
+            .filter_map(|t| t.finished_at().map(|end| (end - t.start_time).num_milliseconds() as f64 / 1000.0))
+            .collect();
+
+        for bound in DURATION_BUCKETS {
+            let count = durations.iter().filter(|d| **d <= bound).count();
+            let _ = writeln!(
+                out,
+                "crankshaft_task_duration_seconds_bucket{{backend=\"{}\",le=\"{}\"}} {}",
+                escape(backend),
+                bound,
+                count
+            );
+        }
+        let _ = writeln!(out, "crankshaft_task_duration_seconds_bucket{{backend=\"{}\",le=\"+Inf\"}} {}", escape(backend), durations.len());
+        let _ = writeln!(out, "crankshaft_task_duration_seconds_sum{{backend=\"{}\"}} {}", escape(backend), durations.iter().sum::<f64>());
+        let _ = writeln!(out, "crankshaft_task_duration_seconds_count{{backend=\"{}\"}} {}", escape(backend), durations.len());
+    }
+
+    // Failure counters (tasks are never removed, so the failed count only grows)
+    header(&mut out, "crankshaft_task_failures_total", "counter", "Total number of failed tasks.");
+    for backend in &backend_names {
+        let count = task_counts.get(&(*backend, TaskStatus::Failed.to_string())).copied().unwrap_or(0);
+        let _ = writeln!(out, "crankshaft_task_failures_total{{backend=\"{}\"}} {}", escape(backend), count);
+    }
+
+    out
+}
+
+/// Write the `HELP` and `TYPE` lines for a metric family.
+fn header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+}
+
+/// Escape a label value as required by the exposition format.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use crate::state::testing::{self, state_with};
+
+    #[rstest]
+    #[case("docker", "docker")]
+    #[case("say \"hi\"", "say \\\"hi\\\"")]
+    #[case("C:\\runner", "C:\\\\runner")]
+    #[case("two\nlines", "two\\nlines")]
+    fn escape_label_value(#[case] value: &str, #[case] expected: &str) {
+        assert_eq!(escape(value), expected);
+    }
+
+    /// Lines of one metric family, without its `HELP` and `TYPE` lines.
+    fn family<'a>(metrics: &'a str, name: &str) -> Vec<&'a str> {
+        metrics.lines().filter(|l| l.starts_with(name) && l[name.len()..].starts_with(['{', '_'])).collect()
+    }
+
+    #[test]
+    fn renders_cumulative_duration_histogram() {
+        let now = Utc::now();
+        let finished = |id: u64, secs: i64, status: TaskStatus| {
+            let mut task = testing::task(id, "align");
+            task.start_time = now - Duration::seconds(secs);
+            task.transition(status, now);
+            task
+        };
+        let mut running = testing::task(4, "align");
+        running.start_time = now - Duration::hours(1);
+        running.transition(TaskStatus::Running, now - Duration::hours(1));
+        let metrics = render(&state_with([
+            finished(1, 5, TaskStatus::Completed),
+            finished(2, 45, TaskStatus::Failed),
+            finished(3, 10, TaskStatus::Cancelled),
+            running,
+        ]));
+
+        let buckets = family(&metrics, "crankshaft_task_duration_seconds");
+        assert_eq!(buckets[..4], [
+            "crankshaft_task_duration_seconds_bucket{backend=\"docker\",le=\"10\"} 2",
+            "crankshaft_task_duration_seconds_bucket{backend=\"docker\",le=\"30\"} 2",
+            "crankshaft_task_duration_seconds_bucket{backend=\"docker\",le=\"60\"} 3",
+            "crankshaft_task_duration_seconds_bucket{backend=\"docker\",le=\"300\"} 3",
+        ]);
+        assert_eq!(buckets[buckets.len() - 3..], [
+            "crankshaft_task_duration_seconds_bucket{backend=\"docker\",le=\"+Inf\"} 3",
+            "crankshaft_task_duration_seconds_sum{backend=\"docker\"} 60",
+            "crankshaft_task_duration_seconds_count{backend=\"docker\"} 3",
+        ]);
+        assert!(metrics.contains("# TYPE crankshaft_task_duration_seconds histogram\n"));
+        assert!(metrics.contains("crankshaft_tasks{backend=\"docker\",status=\"running\"} 1\n"));
+        assert!(metrics.contains("crankshaft_task_failures_total{backend=\"docker\"} 1\n"));
+    }
+
+    #[test]
+    fn escapes_backend_labels() {
+        let mut task = testing::task(1, "align");
+        task.backend = "gpu \"a\"\nrack".into();
+        let metrics = render(&state_with([task]));
+
+        assert_eq!(family(&metrics, "crankshaft_tasks")[0], "crankshaft_tasks{backend=\"gpu \\\"a\\\"\\nrack\",status=\"created\"} 1");
+        assert!(metrics.lines().all(|l| l.starts_with('#') || l.starts_with("crankshaft_")));
+    }
+}
@@ -0,0 +1,102 @@
+//! Embedded HTTP server for Prometheus scrapes.
+//!
+//! A deliberately small HTTP/1.1 responder: it answers `GET /metrics` with the
+//! latest snapshot and everything else with `404`, closing the connection
+//! after each response. Clients that do not send a request in time are
+//! dropped.
+
+use std::io;
+use std::net::SocketAddr;
+use std::time::Duration;
+use eyre::{eyre, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::watch;
+
+/// Maximum size of a request head that will be read.
+const MAX_REQUEST_SIZE: usize = 8 * 1024;
+
+/// Time a client has to send its request head.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Content type of the Prometheus text exposition format.
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Bind the server and start accepting connections in the background.
+pub async fn serve(addr: SocketAddr, snapshot: watch::Receiver<String>) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    tracing::info!("serving Prometheus metrics on http://{}/metrics", listener.local_addr()?);
+
+    tokio::spawn(async move {
+        loop {
+            match listener.accept().await {
+                Ok((stream, _)) => {
+                    let snapshot = snapshot.clone();
+                    tokio::spawn(async move {
+                        if let Err(err) = handle_connection(stream, snapshot).await {
+                            tracing::debug!("metrics connection error: {err}");
+                        }
+                    });
+                }
+                Err(err) => tracing::warn!("failed to accept metrics connection: {err}"),
+            }
+        }
+    });
+
+    Ok(())
+}
+
+/// Read one request and write the response.
+async fn handle_connection(mut stream: TcpStream, snapshot: watch::Receiver<String>) -> Result<()> {
+    let request = tokio::time::timeout(READ_TIMEOUT, read_request_head(&mut stream))
+        .await
+        .map_err(|_| eyre!("no request within {:?}", READ_TIMEOUT))??;
+
+    let request = String::from_utf8_lossy(&request);
+    let mut request_line = request.lines().next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default();
+    let path = request_line.next().unwrap_or_default();
+
+    let response = match (method, path.split('?').next().unwrap_or_default()) {
+        ("GET", "/metrics") => {
+            let body = snapshot.borrow().clone();
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                CONTENT_TYPE,
+                body.len(),
+                body
+            )
+        }
+        ("GET", _) => not_found(),
+        _ => "HTTP/1.1 405 Method Not Allowed\r\nAllow: GET\r\nContent-Length: 0\r\nConnection: close\r\n\r\n".to_string(),
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await?;
+    Ok(())
+}
+
+/// Read until the end of the request head, the end of the stream or the
+/// size limit.
+async fn read_request_head(stream: &mut TcpStream) -> io::Result<Vec<u8>> {
+    let mut request = Vec::with_capacity(1024);
+    let mut buf = [0u8; 1024];
+    while !request.windows(4).any(|w| w == b"\r\n\r\n") && request.len() < MAX_REQUEST_SIZE {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        request.extend_from_slice(&buf[..read]);
+    }
+    Ok(request)
+}
+
+/// Response for unknown paths.
+fn not_found() -> String {
+    let body = "Not Found. Metrics are served at /metrics\n";
+    format!(
+        "HTTP/1.1 404 Not Found\r\nContent-Type: text/plain\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    )
+}
@@ -11,7 +11,7 @@ use eyre::Result;
 use rand::{Rng, rngs::StdRng, SeedableRng};
 use chrono::{DateTime, Utc};
 
-use crate::state::{BackendState, HealthStatus, BackendKind, ResourceSample};
+use crate::state::{BackendCapacity, BackendState, HealthStatus, BackendKind, Bytes, Cores, Percent, ResourceHistory, TaskActivity};
 use super::DEFAULT_BACKEND_POLL_INTERVAL;
 
 /// Update containing backend state information.
@@ -69,9 +69,15 @@ impl BackendMonitor {
                 health: HealthStatus::Healthy,
                 running_tasks: 3,
                 total_tasks: 5,
-                cpu_usage: 45.2,
-                memory_usage: 32.8,
-                resource_history: Vec::new(),
+                cpu_usage: Percent(45.2),
+                memory_usage: Percent(32.8),
+                capacity: Some(BackendCapacity {
+                    cores: Cores(8.0),
+                    memory: Bytes::from_gib(32.0),
+                    slots: 5,
+                }),
+                resource_history: ResourceHistory::new(),
+                task_activity: TaskActivity::new(),
                 last_update: Utc::now(),
             });
             
@@ -82,9 +88,15 @@ impl BackendMonitor {
                 health: HealthStatus::Degraded,
                 running_tasks: 12,
                 total_tasks: 30,
-                cpu_usage: 78.5,
-                memory_usage: 65.3,
-                resource_history: Vec::new(),
+                cpu_usage: Percent(78.5),
+                memory_usage: Percent(65.3),
+                capacity: Some(BackendCapacity {
+                    cores: Cores(64.0),
+                    memory: Bytes::from_gib(256.0),
+                    slots: 30,
+                }),
+                resource_history: ResourceHistory::new(),
+                task_activity: TaskActivity::new(),
                 last_update: Utc::now(),
             });
             
@@ -95,9 +107,15 @@ impl BackendMonitor {
                 health: HealthStatus::Healthy,
                 running_tasks: 1,
                 total_tasks: 2,
-                cpu_usage: 12.3,
-                memory_usage: 8.7,
-                resource_history: Vec::new(),
+                cpu_usage: Percent(12.3),
+                memory_usage: Percent(8.7),
+                capacity: Some(BackendCapacity {
+                    cores: Cores(4.0),
+                    memory: Bytes::from_gib(16.0),
+                    slots: 2,
+                }),
+                resource_history: ResourceHistory::new(),
+                task_activity: TaskActivity::new(),
                 last_update: Utc::now(),
             });
             
@@ -113,6 +131,7 @@ impl BackendMonitor {
             // In a real implementation, this would connect to a real Crankshaft engine
             // and start polling for backend status
             // self.start_real_polling().await?;
+            tracing::warn!("live engine connections are not supported yet; no backend updates will arrive from {}", url);
         }
         
         Ok(())
@@ -141,11 +160,11 @@ impl BackendMonitor {
                 for (_, backend) in states.iter_mut() {
                     // Randomly adjust CPU usage
                     let cpu_delta = rng.gen_range(-5.0..5.0);
-                    backend.cpu_usage = (backend.cpu_usage + cpu_delta).clamp(0.0, 100.0);
+                    backend.cpu_usage = Percent::new(backend.cpu_usage.0 + cpu_delta);
                     
                     // Randomly adjust memory usage
                     let mem_delta = rng.gen_range(-3.0..3.0);
-                    backend.memory_usage = (backend.memory_usage + mem_delta).clamp(0.0, 100.0);
+                    backend.memory_usage = Percent::new(backend.memory_usage.0 + mem_delta);
                     
                     // Occasionally change health status for the TES backend (to simulate issues)
                     if backend.kind == BackendKind::TES && rng.gen_ratio(1, 20) {
@@ -153,20 +172,8 @@ impl BackendMonitor {
                         backend.health = statuses[rng.gen_range(0..3)];
                     }
                     
-                    // Update the timestamp
+                    // Update the timestamp; the history is recorded by AppState
                     backend.last_update = Utc::now();
-                    
-                    // Add resource sample
-                    backend.resource_history.push(ResourceSample {
-                        timestamp: Utc::now(),
-                        cpu: backend.cpu_usage,
-                        memory: backend.memory_usage,
-                    });
-                    
-                    // Keep only the last 60 samples (5 minutes at 5s interval)
-                    if backend.resource_history.len() > 60 {
-                        backend.resource_history.remove(0);
-                    }
                 }
                 
                 // Create the update
@@ -197,6 +204,11 @@ impl BackendMonitor {
         self.poll_interval = interval;
     }
     
+    /// Enable or disable the built-in simulator.
+    pub fn set_demo_mode(&mut self, demo_mode: bool) {
+        self.demo_mode = demo_mode;
+    }
+    
     /// Poll for updates.
     pub async fn poll(&mut self) -> Option<BackendUpdate> {
         if let Some(receiver) = &mut self.update_receiver {
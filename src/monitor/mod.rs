@@ -15,7 +15,9 @@ pub use backend::{BackendMonitor, BackendUpdate};
 pub use task::{TaskMonitor, TaskUpdate};
 
 use std::time::Duration;
-use eyre::Result;
+use eyre::{bail, Result};
+
+use crate::state::AppState;
 
 /// Default polling interval for backend status.
 pub const DEFAULT_BACKEND_POLL_INTERVAL: Duration = Duration::from_secs(5);
@@ -23,6 +25,12 @@ pub const DEFAULT_BACKEND_POLL_INTERVAL: Duration = Duration::from_secs(5);
 /// Default polling interval for task status.
 pub const DEFAULT_TASK_POLL_INTERVAL: Duration = Duration::from_secs(1);
 
+/// Default engine URL (the built-in simulator).
+pub const DEFAULT_ENGINE_URL: &str = "demo://localhost";
+
+/// Interval between checks while waiting for the initial sync.
+const SYNC_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
 /// Monitor manager that handles connections to Crankshaft engines.
 pub struct MonitorManager {
     /// Task monitor instance
@@ -46,6 +54,20 @@ impl MonitorManager {
         }
     }
     
+    /// Enable or disable the built-in simulator.
+    pub fn with_demo_mode(mut self, demo_mode: bool) -> Self {
+        self.task_monitor.set_demo_mode(demo_mode);
+        self.backend_monitor.set_demo_mode(demo_mode);
+        self
+    }
+    
+    /// Set the polling intervals of the task and backend monitors.
+    pub fn with_poll_intervals(mut self, task_interval: Duration, backend_interval: Duration) -> Self {
+        self.task_monitor.set_poll_interval(task_interval);
+        self.backend_monitor.set_poll_interval(backend_interval);
+        self
+    }
+    
     /// Connect to the Crankshaft engine.
     pub async fn connect(&mut self) -> Result<()> {
         // In a real implementation, this would establish a connection to the Crankshaft engine
@@ -80,4 +102,55 @@ impl MonitorManager {
     pub fn is_active(&self) -> bool {
         self.active
     }
+    
+    /// Apply all pending task and backend updates to the application state.
+    ///
+    /// Returns the number of task and backend updates that were applied.
+    pub async fn drain_into(&mut self, state: &mut AppState) -> (usize, usize) {
+        let mut task_updates = 0;
+        while let Some(update) = self.task_monitor.poll().await {
+            let updates: Vec<crate::state::TaskUpdate> = update.into();
+            state.update_tasks(updates);
+            task_updates += 1;
+        }
+        
+        let mut backend_updates = 0;
+        while let Some(update) = self.backend_monitor.poll().await {
+            let updates: Vec<crate::state::BackendUpdate> = update.into();
+            state.update_backends(updates);
+            backend_updates += 1;
+        }
+        
+        // Record cluster totals once per batch rather than once per update
+        if task_updates + backend_updates > 0 {
+            state.refresh_resources();
+        }
+        
+        (task_updates, backend_updates)
+    }
+    
+    /// Wait until both monitors have delivered their initial state.
+    ///
+    /// Updates received while waiting are applied to `state`.
+    pub async fn initial_sync(&mut self, state: &mut AppState, timeout: Duration) -> Result<()> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut tasks_synced = false;
+        let mut backends_synced = false;
+        
+        while !(tasks_synced && backends_synced) {
+            if tokio::time::Instant::now() >= deadline {
+                bail!("timed out after {:?} waiting for the initial sync with {}", timeout, self.engine_url);
+            }
+            
+            let (task_updates, backend_updates) = self.drain_into(state).await;
+            tasks_synced |= task_updates > 0;
+            backends_synced |= backend_updates > 0;
+            
+            if !(tasks_synced && backends_synced) {
+                tokio::time::sleep(SYNC_CHECK_INTERVAL).await;
+            }
+        }
+        
+        Ok(())
+    }
 }
\ No newline at end of file
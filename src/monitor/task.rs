@@ -13,9 +13,30 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 use chrono::{DateTime, Utc, Duration as ChronoDuration};
 
-use crate::state::{TaskState, TaskStatus, ResourceSample};
+use crate::state::{Bytes, Cores, StatusTransition, TaskState, TaskStatus, ResourceSample};
 use super::DEFAULT_TASK_POLL_INTERVAL;
 
+/// Failure reasons reported by the demo simulator. Each `#` is replaced
+/// with a random number, so repeats of one failure differ in their details.
+const DEMO_ERRORS: [&str; 5] = [
+    "exit code 137: container # killed (out of memory)",
+    "exit code 1: input file /data/sample-#.bam not found",
+    "exit code 2: reference index /ref/hg38.# is out of date",
+    "backend timeout after #s",
+    "exit code 139: segmentation fault at 0x7f#",
+];
+
+/// A random demo failure reason.
+fn demo_error(rng: &mut impl Rng) -> String {
+    DEMO_ERRORS.choose(rng).unwrap().replace('#', &rng.gen_range(1000..99999).to_string())
+}
+
+/// Every this many demo tasks, one hangs part way through.
+const DEMO_HANG_EVERY: u64 = 17;
+
+/// Number of consecutive demo tasks that make up one workflow run.
+const DEMO_RUN_SIZE: u64 = 40;
+
 /// Update containing task state information.
 #[derive(Debug, Clone)]
 pub struct TaskUpdate {
@@ -134,6 +155,22 @@ impl TaskMonitor {
                 Some(now - ChronoDuration::hours(1))
             );
             
+            // Send the initial snapshot first so consumers learn about pre-existing tasks
+            let mut initial_ids: Vec<u64> = tasks.keys().copied().collect();
+            initial_ids.sort_unstable();
+            let snapshot = TaskUpdate {
+                tasks: tasks.clone(),
+                timestamp: now,
+                new_tasks: initial_ids,
+                updated_tasks: Vec::new(),
+                completed_tasks: Vec::new(),
+                resource_usage: None,
+                logs: None,
+            };
+            if let Some(sender) = &self.update_sender {
+                sender.send(snapshot).await?;
+            }
+            
             // Store the tasks and set the next task ID
             {
                 let mut state = self.task_states.lock().await;
@@ -149,6 +186,7 @@ impl TaskMonitor {
             // In a real implementation, this would connect to a real Crankshaft engine
             // and start polling for task status
             // self.start_real_polling().await?;
+            tracing::warn!("live engine connections are not supported yet; no task updates will arrive from {}", url);
         }
         
         Ok(())
@@ -171,8 +209,10 @@ impl TaskMonitor {
         let sample_count = rng.gen_range(10..30);
         let mut resource_samples = Vec::with_capacity(sample_count);
         
-        let cpu_base = rng.gen_range(10.0_f32..50.0_f32);
-        let mem_base = rng.gen_range(50.0_f32..200.0_f32);
+        // CPU in cores, memory in MiB
+        let cpu_base = rng.gen_range(0.2_f32..2.0_f32);
+        let mem_base = rng.gen_range(128.0_f64..2048.0_f64);
+        let (requested_cpu, requested_memory) = demo_requests(&mut rng, cpu_base, mem_base);
         
         for i in 0..sample_count {
             let timestamp = start_time + ChronoDuration::seconds(i as i64 * 10);
@@ -184,13 +224,13 @@ impl TaskMonitor {
                 }
             }
             
-            let cpu_jitter = rng.gen_range(-5.0_f32..5.0_f32);
-            let mem_jitter = rng.gen_range(-10.0_f32..10.0_f32);
+            let cpu_jitter = rng.gen_range(-0.1_f32..0.1_f32);
+            let mem_jitter = rng.gen_range(-32.0_f64..32.0_f64);
             
             resource_samples.push(ResourceSample {
                 timestamp,
-                cpu: (cpu_base + cpu_jitter).max(0.0_f32),    
-                memory: (mem_base + mem_jitter).max(0.0_f32), 
+                cpu: Cores((cpu_base + cpu_jitter).max(0.0_f32)),
+                memory: Bytes::from_mib(mem_base + mem_jitter),
             });
         }
         
@@ -201,10 +241,20 @@ impl TaskMonitor {
             backend,
             status,
             progress,
-            cpu_usage: resource_samples.last().map_or(0.0, |s| s.cpu),
-            memory_usage: resource_samples.last().map_or(0.0, |s| s.memory),
+            cpu_usage: resource_samples.last().map_or(Cores::default(), |s| s.cpu),
+            memory_usage: resource_samples.last().map_or(Bytes::default(), |s| s.memory),
+            requested_cpu: Some(requested_cpu),
+            requested_memory: Some(requested_memory),
             start_time,
             end_time,
+            workflow_id: None,
+            call_name: None,
+            shard_index: None,
+            depends_on: Vec::new(),
+            transitions: demo_transitions(&mut rng, status, start_time, end_time),
+            progress_changed_at: None,
+            log_tail: Default::default(),
+            error: (status == TaskStatus::Failed).then(|| demo_error(&mut rng)),
             cancellation_token: None,
         };
         
@@ -240,15 +290,17 @@ impl TaskMonitor {
                     let id = *next_id;
                     *next_id += 1;
                     
+                    // In pipeline order, each step consuming earlier ones
                     let task_names = [
-                        "sequence-analysis", "data-processing", "variant-calling", 
-                        "alignment-job", "fastq-conversion", "sam-to-bam", 
-                        "quality-control", "trim-adapters", "demultiplexing"
+                        "demultiplexing", "fastq-conversion", "trim-adapters",
+                        "quality-control", "alignment-job", "sam-to-bam",
+                        "variant-calling", "sequence-analysis", "data-processing"
                     ];
                     
                     let backend_names = ["docker-local", "tes-cloud", "local-runner"];
                     
-                    let name = format!("{}-{}", task_names.choose(&mut rng).unwrap(), id);
+                    let call = task_names.choose(&mut rng).unwrap();
+                    let name = format!("{}-{}", call, id);
                     let backend = backend_names.choose(&mut rng).unwrap().to_string();
                     
                     // Use the same pattern in add_demo_task_static
@@ -257,6 +309,29 @@ impl TaskMonitor {
                         None, Utc::now(), None
                     );
                     
+                    // Every DEMO_RUN_SIZE consecutive tasks form one workflow
+                    // run, with repeated calls scattered into shards
+                    let workflow_id = format!("run-{:03}", id / DEMO_RUN_SIZE + 1);
+                    let shard = states.values()
+                        .filter(|t| t.workflow_id.as_deref() == Some(workflow_id.as_str()) && t.call_name.as_deref() == Some(*call))
+                        .count() as u32;
+                    
+                    // Depend on up to two tasks of earlier pipeline steps in the run
+                    let step = task_names.iter().position(|n| n == call).unwrap_or(0);
+                    let upstream: Vec<u64> = states.values()
+                        .filter(|t| t.workflow_id.as_deref() == Some(workflow_id.as_str()))
+                        .filter(|t| t.call_name.as_deref().and_then(|c| task_names.iter().position(|n| *n == c)).is_some_and(|s| s < step))
+                        .map(|t| t.id)
+                        .collect();
+                    let depends_on: Vec<u64> = upstream.choose_multiple(&mut rng, 2).copied().collect();
+                    
+                    if let Some(task) = states.get_mut(&id) {
+                        task.workflow_id = Some(workflow_id);
+                        task.call_name = Some(call.to_string());
+                        task.shard_index = Some(shard);
+                        task.depends_on = depends_on;
+                    }
+                    
                     new_tasks.push(id);
                 }
                 
@@ -277,7 +352,21 @@ impl TaskMonitor {
                                 updated = true;
                             }
                             
-                            if task.status == TaskStatus::Running {
+                            // Some demo tasks hang part way through, with no progress and idle CPU
+                            let hung = *id % DEMO_HANG_EVERY == 0 && task.progress.is_some_and(|p| p >= 0.4);
+                            
+                            if task.status == TaskStatus::Running && hung {
+                                task.cpu_usage = Cores(task.cpu_usage.0 * 0.5);
+                                
+                                // Eventually time out
+                                if rng.gen_ratio(1, 200) {
+                                    task.status = TaskStatus::Failed;
+                                    task.error = Some(format!("backend timeout after {}s", rng.gen_range(3600..3700)));
+                                    task.end_time = Some(Utc::now());
+                                    completed_tasks.push(*id);
+                                }
+                                updated = true;
+                            } else if task.status == TaskStatus::Running {
                                 // Update progress
                                 if let Some(progress) = &mut task.progress {
                                     *progress += rng.gen_range(0.01..0.05);
@@ -286,11 +375,12 @@ impl TaskMonitor {
                                     // Complete or fail the task
                                     if *progress >= 1.0 {
                                         *progress = 1.0;
-                                        task.status = if rng.gen_ratio(8, 10) {
-                                            TaskStatus::Completed
+                                        if rng.gen_ratio(8, 10) {
+                                            task.status = TaskStatus::Completed;
                                         } else {
-                                            TaskStatus::Failed
-                                        };
+                                            task.status = TaskStatus::Failed;
+                                            task.error = Some(demo_error(&mut rng));
+                                        }
                                         task.end_time = Some(Utc::now());
                                         completed_tasks.push(*id);
                                     }
@@ -300,11 +390,11 @@ impl TaskMonitor {
                                 }
                                 
                                 // Update resource usage
-                                let cpu_delta = rng.gen_range(-3.0..3.0);
-                                task.cpu_usage = (task.cpu_usage + cpu_delta).max(0.0_f32);
+                                let cpu_delta = rng.gen_range(-0.05..0.05);
+                                task.cpu_usage = Cores((task.cpu_usage.0 + cpu_delta).max(0.0_f32));
                                 
-                                let mem_delta = rng.gen_range(-5.0..5.0);
-                                task.memory_usage = (task.memory_usage + mem_delta).max(0.0_f32);
+                                let mem_delta = rng.gen_range(-8.0..8.0);
+                                task.memory_usage = Bytes::from_mib(task.memory_usage.as_mib() + mem_delta);
                             }
                         }
                         
@@ -372,8 +462,9 @@ impl TaskMonitor {
                 }
                 
                 // Send the combined update
+                // This carries the post-update states so completions report their final status
                 let update = TaskUpdate {
-                    tasks: states_snapshot.clone(), // Use snapshot here too
+                    tasks: states.clone(),
                     timestamp: Utc::now(),
                     new_tasks,
                     updated_tasks,
@@ -407,8 +498,10 @@ impl TaskMonitor {
         let sample_count = rng.gen_range(5..15);
         let mut resource_samples = Vec::with_capacity(sample_count);
         
-        let cpu_base = rng.gen_range(10.0_f32..50.0_f32);
-        let mem_base = rng.gen_range(50.0_f32..200.0_f32);
+        // CPU in cores, memory in MiB
+        let cpu_base = rng.gen_range(0.2_f32..2.0_f32);
+        let mem_base = rng.gen_range(128.0_f64..2048.0_f64);
+        let (requested_cpu, requested_memory) = demo_requests(&mut rng, cpu_base, mem_base);
         
         for i in 0..sample_count {
             let timestamp = start_time + ChronoDuration::seconds(i as i64 * 10);
@@ -420,13 +513,13 @@ impl TaskMonitor {
                 }
             }
             
-            let cpu_jitter = rng.gen_range(-5.0_f32..5.0_f32);
-            let mem_jitter = rng.gen_range(-10.0_f32..10.0_f32);
+            let cpu_jitter = rng.gen_range(-0.1_f32..0.1_f32);
+            let mem_jitter = rng.gen_range(-32.0_f64..32.0_f64);
             
             resource_samples.push(ResourceSample {
                 timestamp,
-                cpu: (cpu_base + cpu_jitter).max(0.0_f32),    
-                memory: (mem_base + mem_jitter).max(0.0_f32), 
+                cpu: Cores((cpu_base + cpu_jitter).max(0.0_f32)),
+                memory: Bytes::from_mib(mem_base + mem_jitter),
             });
         }
         
@@ -437,10 +530,20 @@ impl TaskMonitor {
             backend,
             status,
             progress,
-            cpu_usage: resource_samples.last().map_or(0.0, |s| s.cpu),
-            memory_usage: resource_samples.last().map_or(0.0, |s| s.memory),
+            cpu_usage: resource_samples.last().map_or(Cores::default(), |s| s.cpu),
+            memory_usage: resource_samples.last().map_or(Bytes::default(), |s| s.memory),
+            requested_cpu: Some(requested_cpu),
+            requested_memory: Some(requested_memory),
             start_time,
             end_time,
+            workflow_id: None,
+            call_name: None,
+            shard_index: None,
+            depends_on: Vec::new(),
+            transitions: demo_transitions(&mut rng, status, start_time, end_time),
+            progress_changed_at: None,
+            log_tail: Default::default(),
+            error: (status == TaskStatus::Failed).then(|| demo_error(&mut rng)),
             cancellation_token: None, 
         };
         
@@ -458,6 +561,11 @@ impl TaskMonitor {
         self.poll_interval = interval;
     }
     
+    /// Enable or disable the built-in simulator.
+    pub fn set_demo_mode(&mut self, demo_mode: bool) {
+        self.demo_mode = demo_mode;
+    }
+    
     /// Poll for updates.
     pub async fn poll(&mut self) -> Option<TaskUpdate> {
         if let Some(receiver) = &mut self.update_receiver {
@@ -472,4 +580,46 @@ impl Default for TaskMonitor {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+
+/// Requests for a demo task, rounded up and padded the way hand-written
+/// WDL runtime sections tend to be.
+fn demo_requests(rng: &mut impl Rng, cpu_base: f32, mem_base_mib: f64) -> (Cores, Bytes) {
+    let cores = [1.0, 2.0, 4.0, 8.0].into_iter()
+        .find(|&c| c >= cpu_base * rng.gen_range(1.0..2.0))
+        .unwrap_or(8.0);
+    let gib = [2.0, 4.0, 8.0, 16.0].into_iter()
+        .find(|&g| g * 1024.0 >= mem_base_mib * rng.gen_range(1.5..3.0))
+        .unwrap_or(16.0);
+    (Cores(cores), Bytes::from_gib(gib))
+}
+
+/// Status history for a demo task that has reached `status`: created at
+/// `start`, queued shortly after, running after a queue wait, and finished
+/// at `end`.
+fn demo_transitions(
+    rng: &mut impl Rng,
+    status: TaskStatus,
+    start: DateTime<Utc>,
+    end: Option<DateTime<Utc>>,
+) -> Vec<StatusTransition> {
+    let mut transitions = vec![StatusTransition { status: TaskStatus::Created, at: start }];
+    if status == TaskStatus::Created {
+        return transitions;
+    }
+
+    let queued = start + ChronoDuration::seconds(rng.gen_range(1..5));
+    transitions.push(StatusTransition { status: TaskStatus::Queued, at: queued });
+    if status == TaskStatus::Queued {
+        return transitions;
+    }
+
+    // Keep the queue wait within the task's lifetime
+    let lifetime = end.unwrap_or_else(Utc::now) - queued;
+    let wait = ChronoDuration::seconds(rng.gen_range(5..180)).min(lifetime / 2);
+    transitions.push(StatusTransition { status: TaskStatus::Running, at: queued + wait });
+    if status.is_terminal() {
+        transitions.push(StatusTransition { status, at: end.unwrap_or_else(Utc::now) });
+    }
+    transitions
+}
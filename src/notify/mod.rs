@@ -0,0 +1,187 @@
+//! Alert delivery for the Crankshaft TUI.
+//!
+//! Alerts raised by the state layer are delivered to any of the configured sinks:
+//!
+//! - A webhook receiving a JSON payload (with retry and exponential backoff)
+//! - The terminal bell
+//! - OSC 9 / OSC 777 desktop notification escape sequences
+//!
+//! Repeated alerts from the same source are de-duplicated, so a flapping
+//! backend does not flood the team channel.
+
+pub mod terminal;
+pub mod webhook;
+
+pub use webhook::{WebhookClient, WebhookPayload};
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use eyre::Result;
+
+use crate::state::{Alert, AlertSeverity};
+
+/// Default window during which repeated alerts from one source are suppressed.
+pub const DEFAULT_DEDUP_WINDOW: Duration = Duration::from_secs(300);
+
+/// Default number of webhook retries after the first attempt.
+pub const DEFAULT_MAX_RETRIES: u32 = 4;
+
+/// Default delay before the first webhook retry (doubled on each retry).
+pub const DEFAULT_INITIAL_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Alert delivery configuration.
+#[derive(Debug, Clone)]
+pub struct NotifyConfig {
+    /// URL to POST alert payloads to
+    pub webhook_url: Option<String>,
+    /// Ring the terminal bell on alerts
+    pub bell: bool,
+    /// Emit desktop notification escape sequences on alerts
+    pub desktop: bool,
+    /// Window during which repeated alerts from one source are suppressed
+    pub dedup_window: Duration,
+    /// Number of webhook retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first webhook retry
+    pub initial_backoff: Duration,
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            bell: false,
+            desktop: false,
+            dedup_window: DEFAULT_DEDUP_WINDOW,
+            max_retries: DEFAULT_MAX_RETRIES,
+            initial_backoff: DEFAULT_INITIAL_BACKOFF,
+        }
+    }
+}
+
+/// Delivers alerts to the configured sinks.
+pub struct Notifier {
+    /// Delivery configuration
+    config: NotifyConfig,
+    /// Webhook client, if a webhook URL is configured
+    webhook: Option<WebhookClient>,
+    /// Last delivery time and severity per alert source
+    last_sent: HashMap<String, (Instant, AlertSeverity)>,
+}
+
+impl Notifier {
+    /// Create a new notifier from the given configuration.
+    pub fn new(config: NotifyConfig) -> Result<Self> {
+        let webhook = match &config.webhook_url {
+            Some(url) => Some(WebhookClient::new(url, config.max_retries, config.initial_backoff)?),
+            None => None,
+        };
+
+        Ok(Self {
+            config,
+            webhook,
+            last_sent: HashMap::new(),
+        })
+    }
+
+    /// Whether any delivery sink is enabled.
+    pub fn is_enabled(&self) -> bool {
+        self.webhook.is_some() || self.config.bell || self.config.desktop
+    }
+
+    /// Deliver an alert to all enabled sinks.
+    ///
+    /// Returns `false` if the alert was suppressed as a duplicate.
+    pub fn notify(&mut self, alert: &Alert) -> bool {
+        if !self.is_enabled() || self.is_duplicate(alert) {
+            return false;
+        }
+
+        // Terminal sinks write escape sequences that do not disturb the screen
+        if self.config.bell || self.config.desktop {
+            let mut stdout = std::io::stdout();
+            if self.config.bell {
+                let _ = terminal::ring_bell(&mut stdout);
+            }
+            if self.config.desktop {
+                let _ = terminal::desktop_notification(&mut stdout, &alert.title, &alert.message);
+            }
+        }
+
+        // Webhook delivery runs in the background so retries never block the UI
+        if let Some(client) = &self.webhook {
+            let client = client.clone();
+            let payload = WebhookPayload::from(alert);
+            tokio::spawn(async move {
+                if let Err(err) = client.send(&payload).await {
+                    tracing::warn!("failed to deliver alert webhook: {err}");
+                }
+            });
+        }
+
+        true
+    }
+
+    /// Check whether an alert repeats a recent one and record it otherwise.
+    ///
+    /// An alert is a duplicate if the same source sent an alert of equal or
+    /// higher severity within the de-duplication window. Escalations always
+    /// get through.
+    fn is_duplicate(&mut self, alert: &Alert) -> bool {
+        let now = Instant::now();
+        
+        // Forget sources whose window has passed, so the map stays small
+        let window = self.config.dedup_window;
+        self.last_sent.retain(|_, (sent_at, _)| now.duration_since(*sent_at) < window);
+
+        if let Some((_, severity)) = self.last_sent.get(&alert.source) {
+            if alert.severity <= *severity {
+                return true;
+            }
+        }
+
+        self.last_sent.insert(alert.source.clone(), (now, alert.severity));
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notifier(dedup_window: Duration) -> Notifier {
+        Notifier::new(NotifyConfig { dedup_window, ..NotifyConfig::default() }).unwrap()
+    }
+
+    fn backend_alert(severity: AlertSeverity, title: &str) -> Alert {
+        Alert::new(severity, "backend:tes-cloud", title, "health changed")
+    }
+
+    #[test]
+    fn suppresses_flapping_backend_within_window() {
+        let mut notifier = notifier(Duration::from_secs(300));
+
+        assert!(!notifier.is_duplicate(&backend_alert(AlertSeverity::Warning, "Backend degraded")));
+        assert!(notifier.is_duplicate(&backend_alert(AlertSeverity::Info, "Backend recovered")));
+        assert!(notifier.is_duplicate(&backend_alert(AlertSeverity::Warning, "Backend degraded")));
+    }
+
+    #[test]
+    fn lets_escalations_and_other_sources_through() {
+        let mut notifier = notifier(Duration::from_secs(300));
+
+        assert!(!notifier.is_duplicate(&backend_alert(AlertSeverity::Warning, "Backend degraded")));
+        assert!(!notifier.is_duplicate(&backend_alert(AlertSeverity::Critical, "Backend unhealthy")));
+        let other = Alert::new(AlertSeverity::Warning, "backend:docker", "Backend degraded", "");
+        assert!(!notifier.is_duplicate(&other));
+    }
+
+    #[test]
+    fn forgets_sources_after_window() {
+        let mut notifier = notifier(Duration::ZERO);
+
+        assert!(!notifier.is_duplicate(&backend_alert(AlertSeverity::Warning, "Backend degraded")));
+        assert!(!notifier.is_duplicate(&backend_alert(AlertSeverity::Warning, "Backend degraded")));
+        assert_eq!(notifier.last_sent.len(), 1);
+    }
+}
@@ -0,0 +1,33 @@
+//! Terminal notification sinks.
+//!
+//! Writes the terminal bell and desktop notification escape sequences.
+//! OSC 9 is understood by iTerm2, Windows Terminal and others, while OSC 777
+//! is understood by urxvt, foot and VTE-based terminals; both are emitted
+//! since terminals silently ignore sequences they do not support.
+
+use std::io::{self, Write};
+
+/// Ring the terminal bell.
+pub fn ring_bell(out: &mut impl Write) -> io::Result<()> {
+    out.write_all(b"\x07")?;
+    out.flush()
+}
+
+/// Emit OSC 9 and OSC 777 desktop notification escape sequences.
+pub fn desktop_notification(out: &mut impl Write, title: &str, body: &str) -> io::Result<()> {
+    let title = sanitize(title);
+    let body = sanitize(body);
+
+    // OSC 9 only carries a message
+    write!(out, "\x1b]9;{}: {}\x07", title, body)?;
+
+    // OSC 777 separates fields with ';', so it must not appear in the title
+    write!(out, "\x1b]777;notify;{};{}\x07", title.replace(';', ","), body)?;
+
+    out.flush()
+}
+
+/// Strip control characters that would terminate or corrupt the escape sequence.
+fn sanitize(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
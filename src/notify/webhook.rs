@@ -0,0 +1,179 @@
+//! Webhook alert delivery.
+//!
+//! Posts alerts as JSON to a configured URL. The payload carries a `text`
+//! field so it can be sent directly to Slack- and Mattermost-style incoming
+//! webhooks, alongside the structured alert for other receivers.
+
+use std::time::Duration;
+use eyre::{eyre, Result};
+use serde::Serialize;
+
+use crate::state::Alert;
+
+/// Timeout for a single webhook request.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// JSON payload posted to the webhook.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    /// Human-readable summary of the alert
+    pub text: String,
+    /// The alert itself
+    pub alert: Alert,
+}
+
+impl From<&Alert> for WebhookPayload {
+    fn from(alert: &Alert) -> Self {
+        Self {
+            text: format!("[{}] {}: {}", alert.severity, alert.title, alert.message),
+            alert: alert.clone(),
+        }
+    }
+}
+
+/// Client for posting alert payloads to a webhook.
+#[derive(Debug, Clone)]
+pub struct WebhookClient {
+    /// HTTP client (cheap to clone)
+    client: reqwest::Client,
+    /// Webhook URL
+    url: reqwest::Url,
+    /// Number of retries after the first attempt
+    max_retries: u32,
+    /// Delay before the first retry
+    initial_backoff: Duration,
+}
+
+impl WebhookClient {
+    /// Create a new webhook client.
+    pub fn new(url: &str, max_retries: u32, initial_backoff: Duration) -> Result<Self> {
+        let url = reqwest::Url::parse(url).map_err(|err| eyre!("invalid webhook URL '{url}': {err}"))?;
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+
+        Ok(Self {
+            client,
+            url,
+            max_retries,
+            initial_backoff,
+        })
+    }
+
+    /// Post a payload, retrying with exponential backoff on failure.
+    ///
+    /// Client errors other than `429 Too Many Requests` are not retried,
+    /// since repeating the same request will not change the outcome.
+    pub async fn send(&self, payload: &WebhookPayload) -> Result<()> {
+        let mut backoff = self.initial_backoff;
+        let mut attempt = 0;
+
+        loop {
+            let error = match self.client.post(self.url.clone()).json(payload).send().await {
+                Ok(response) if response.status().is_success() => return Ok(()),
+                Ok(response) => {
+                    let status = response.status();
+                    if status.is_client_error() && status != reqwest::StatusCode::TOO_MANY_REQUESTS {
+                        return Err(eyre!("webhook rejected alert with status {status}"));
+                    }
+                    eyre!("webhook returned status {status}")
+                }
+                Err(err) => eyre!("webhook request failed: {err}"),
+            };
+
+            if attempt >= self.max_retries {
+                return Err(error.wrap_err(format!("giving up after {} attempts", attempt + 1)));
+            }
+
+            tracing::debug!("retrying webhook in {:?}: {error}", backoff);
+            tokio::time::sleep(backoff).await;
+            backoff = backoff.saturating_mul(2);
+            attempt += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AlertSeverity;
+    use pretty_assertions::assert_eq;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Read one HTTP request and return its body.
+    async fn read_body(stream: &mut TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut chunk = [0; 4096];
+        loop {
+            let n = stream.read(&mut chunk).await.unwrap();
+            assert!(n > 0, "connection closed before the request was complete");
+            request.extend_from_slice(&chunk[..n]);
+
+            let text = String::from_utf8_lossy(&request);
+            let Some(header_end) = text.find("\r\n\r\n") else {
+                continue;
+            };
+            let length = text[..header_end].lines()
+                .find_map(|line| {
+                    let (name, value) = line.split_once(':')?;
+                    name.eq_ignore_ascii_case("content-length").then(|| value.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+            if request.len() >= header_end + 4 + length {
+                return text[header_end + 4..header_end + 4 + length].to_string();
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_server_errors_and_posts_json() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+
+        // Answer 500 to the first request and 200 to the second
+        let stub = tokio::spawn(async move {
+            let mut bodies = Vec::new();
+            for status in ["500 Internal Server Error", "200 OK"] {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                bodies.push(read_body(&mut stream).await);
+                let response = format!("HTTP/1.1 {status}\r\ncontent-length: 0\r\nconnection: close\r\n\r\n");
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+            bodies
+        });
+
+        let client = WebhookClient::new(&url, 2, Duration::from_millis(10)).unwrap();
+        let alert = Alert::new(AlertSeverity::Critical, "backend:tes-cloud", "Backend unhealthy", "it broke");
+        client.send(&WebhookPayload::from(&alert)).await.unwrap();
+
+        let bodies = stub.await.unwrap();
+        assert_eq!(bodies.len(), 2);
+        assert_eq!(bodies[0], bodies[1]);
+        let payload: serde_json::Value = serde_json::from_str(&bodies[1]).unwrap();
+        assert_eq!(payload["text"], "[Critical] Backend unhealthy: it broke");
+        assert_eq!(payload["alert"]["severity"], "critical");
+        assert_eq!(payload["alert"]["source"], "backend:tes-cloud");
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_retries() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        let stub = tokio::spawn(async move {
+            for _ in 0..2 {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                read_body(&mut stream).await;
+                let response = "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+                stream.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        let client = WebhookClient::new(&url, 1, Duration::from_millis(10)).unwrap();
+        let alert = Alert::new(AlertSeverity::Warning, "backend:docker", "Backend degraded", "slow");
+        let error = client.send(&WebhookPayload::from(&alert)).await.unwrap_err();
+
+        stub.await.unwrap();
+        assert!(format!("{error:#}").contains("giving up after 2 attempts"));
+    }
+}
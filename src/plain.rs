@@ -0,0 +1,225 @@
+//! Plain line-oriented output.
+//!
+//! Used when stdout is not a terminal (CI logs, pipes, files), where raw mode
+//! and the alternate screen make no sense. Prints one timestamped line per
+//! significant state transition and a periodic summary line. With `--wait`
+//! it exits once no tasks are active, with a non-zero status if any failed.
+
+use std::collections::HashMap;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+use chrono::{DateTime, Utc};
+use eyre::Result;
+
+use crate::monitor::MonitorManager;
+use crate::state::{AppState, HealthStatus, StatusTransition, TaskState, TaskStatus};
+
+/// Interval between checks for new updates.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Default interval between summary lines.
+pub const DEFAULT_SUMMARY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Options for the plain output mode.
+#[derive(Debug, Clone)]
+pub struct PlainOptions {
+    /// Exit once no tasks are active
+    pub wait: bool,
+    /// Interval between summary lines
+    pub summary_interval: Duration,
+}
+
+/// Run the plain output mode.
+///
+/// Returns `true` if any task failed, so the caller can set the exit status.
+pub async fn run(mut manager: MonitorManager, options: PlainOptions) -> Result<bool> {
+    manager.connect().await?;
+
+    let mut state = AppState::new();
+    let mut reporter = PlainReporter::new();
+    let mut out = io::stdout();
+    let mut last_summary = Instant::now();
+
+    loop {
+        manager.drain_into(&mut state).await;
+        reporter.report_transitions(&mut out, &state)?;
+
+        // Once the engine has reported tasks and none are active, the batch is done
+        let batch_done = !state.tasks.is_empty() && state.active_task_count() == 0;
+        if options.wait && batch_done {
+            write_summary(&mut out, &state)?;
+            break;
+        }
+
+        if last_summary.elapsed() >= options.summary_interval {
+            write_summary(&mut out, &state)?;
+            last_summary = Instant::now();
+        }
+
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+
+    manager.disconnect().await?;
+    Ok(state.tasks.values().any(|t| t.status == TaskStatus::Failed))
+}
+
+/// Tracks the last reported state and prints a line for each transition.
+#[derive(Debug, Default)]
+pub struct PlainReporter {
+    /// Number of status transitions already reported for each task
+    task_transitions: HashMap<u64, usize>,
+    /// Last reported health of each backend
+    backend_health: HashMap<String, HealthStatus>,
+}
+
+impl PlainReporter {
+    /// Create a new reporter that has not reported anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Print a line for every transition since the last call.
+    pub fn report_transitions(&mut self, out: &mut impl Write, state: &AppState) -> io::Result<()> {
+        // Backends first, sorted by name for stable output
+        let mut backends: Vec<_> = state.backends.values().collect();
+        backends.sort_by(|a, b| a.name.cmp(&b.name));
+        for backend in backends {
+            match self.backend_health.insert(backend.name.clone(), backend.health) {
+                None => line(out, Utc::now(), format!("backend {} ({}) is {}", backend.name, backend.kind, backend.health))?,
+                Some(old) if old != backend.health => line(
+                    out,
+                    Utc::now(),
+                    format!("backend {} health changed from {} to {}", backend.name, old, backend.health),
+                )?,
+                _ => {}
+            }
+        }
+
+        // Tasks in ID order
+        let mut tasks: Vec<&TaskState> = state.tasks.values().collect();
+        tasks.sort_by_key(|t| t.id);
+        for task in tasks {
+            // Every transition since the last poll is reported, so a task
+            // that queued, ran and finished in between prints all three,
+            // each stamped with when it happened
+            let reported = match self.task_transitions.insert(task.id, task.transitions.len()) {
+                Some(reported) => reported,
+                None => {
+                    line(out, task.start_time, format!("task {} {} created on {}", task.id, task.name, task.backend))?;
+                    0
+                }
+            };
+            for transition in task.transitions.get(reported..).unwrap_or_default() {
+                task_transition(out, task, transition)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Print the line for a task transition.
+fn task_transition(out: &mut impl Write, task: &TaskState, transition: &StatusTransition) -> io::Result<()> {
+    let at = transition.at;
+    let elapsed = (task.finished_at().unwrap_or(at) - task.start_time).num_seconds();
+    match transition.status {
+        TaskStatus::Created => Ok(()),
+        TaskStatus::Queued => line(out, at, format!("task {} {} queued", task.id, task.name)),
+        TaskStatus::Running => line(out, at, format!("task {} {} started on {}", task.id, task.name, task.backend)),
+        TaskStatus::Completed => line(out, at, format!("task {} {} finished after {}s", task.id, task.name, elapsed)),
+        TaskStatus::Failed => line(
+            out,
+            at,
+            format!(
+                "task {} {} failed after {}s: {}",
+                task.id,
+                task.name,
+                elapsed,
+                task.error.as_deref().unwrap_or("unknown error")
+            ),
+        ),
+        TaskStatus::Cancelled => line(out, at, format!("task {} {} cancelled after {}s", task.id, task.name, elapsed)),
+    }
+}
+
+/// Print a summary line with task counts by status.
+pub fn write_summary(out: &mut impl Write, state: &AppState) -> io::Result<()> {
+    let count = |status: TaskStatus| state.tasks.values().filter(|t| t.status == status).count();
+    line(
+        out,
+        Utc::now(),
+        format!(
+            "summary: {} tasks, {} created, {} queued, {} running, {} completed, {} failed, {} cancelled",
+            state.tasks.len(),
+            count(TaskStatus::Created),
+            count(TaskStatus::Queued),
+            count(TaskStatus::Running),
+            count(TaskStatus::Completed),
+            count(TaskStatus::Failed),
+            count(TaskStatus::Cancelled),
+        ),
+    )
+}
+
+/// Print a single line stamped with the time it reports.
+fn line(out: &mut impl Write, at: DateTime<Utc>, message: String) -> io::Result<()> {
+    writeln!(out, "{} {}", at.format("%Y-%m-%dT%H:%M:%SZ"), message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::state::{testing, TaskUpdate};
+
+    fn report(reporter: &mut PlainReporter, state: &AppState) -> Vec<String> {
+        let mut out = Vec::new();
+        reporter.report_transitions(&mut out, state).unwrap();
+        // Drop the timestamps
+        String::from_utf8(out).unwrap()
+            .lines()
+            .map(|l| l.split_once(' ').unwrap().1.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn reports_every_transition_between_polls() {
+        let mut state = AppState::new();
+        let mut reporter = PlainReporter::new();
+        state.update_tasks(vec![TaskUpdate::Created(Box::new(testing::task(1, "align")))]);
+        assert_eq!(report(&mut reporter, &state), ["task 1 align-1 created on docker"]);
+
+        state.update_tasks(vec![
+            TaskUpdate::StatusChanged(1, TaskStatus::Queued),
+            TaskUpdate::StatusChanged(1, TaskStatus::Running),
+            TaskUpdate::Completed(1, Ok(())),
+        ]);
+        let lines = report(&mut reporter, &state);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "task 1 align-1 queued");
+        assert_eq!(lines[1], "task 1 align-1 started on docker");
+        assert!(lines[2].starts_with("task 1 align-1 finished after"));
+
+        assert!(report(&mut reporter, &state).is_empty());
+    }
+
+    #[test]
+    fn stamps_replayed_transitions_with_their_time() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 10, 0, 0).unwrap();
+        let mut task = testing::task(1, "align");
+        task.start_time = start;
+        task.transitions = vec![StatusTransition { status: TaskStatus::Created, at: start }];
+        task.transition(TaskStatus::Running, start + chrono::Duration::minutes(1));
+        task.transition(TaskStatus::Cancelled, start + chrono::Duration::minutes(5));
+        let mut state = AppState::new();
+        state.tasks.insert(1, task);
+
+        let mut out = Vec::new();
+        PlainReporter::new().report_transitions(&mut out, &state).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap().lines().collect::<Vec<_>>(), [
+            "2024-01-01T10:00:00Z task 1 align-1 created on docker",
+            "2024-01-01T10:01:00Z task 1 align-1 started on docker",
+            "2024-01-01T10:05:00Z task 1 align-1 cancelled after 300s",
+        ]);
+    }
+}
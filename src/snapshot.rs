@@ -0,0 +1,250 @@
+//! Headless snapshot mode.
+//!
+//! Connects to the engine through a `MonitorManager`, waits for the initial
+//! sync, prints the current tasks and backends and exits. The JSON and CSV
+//! formats are meant for shell scripts and cron jobs; the table format is
+//! meant for people. JSON carries the full task state, including status
+//! transitions, dependencies and errors; CSV and table rows share their
+//! columns with the task list export.
+
+use std::io::{self, Write};
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use eyre::Result;
+use serde::Serialize;
+
+use crate::cli::SnapshotArgs;
+use crate::config::Config;
+use crate::export::{write_csv, TaskExportRow, TASK_EXPORT_COLUMNS};
+use crate::monitor::MonitorManager;
+use crate::state::{AppState, BackendState, TaskState};
+
+/// Output format for snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SnapshotFormat {
+    Json,
+    Csv,
+    Table,
+}
+
+/// Section of the snapshot to print.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SnapshotSection {
+    Tasks,
+    Backends,
+}
+
+/// Serializable snapshot of the application state.
+#[derive(Debug, Serialize)]
+struct Snapshot<'a> {
+    timestamp: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tasks: Option<Vec<&'a TaskState>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    backends: Option<Vec<&'a BackendState>>,
+}
+
+/// Column headers for backend rows.
+const BACKEND_COLUMNS: [&str; 11] = [
+    "name", "kind", "health", "running_tasks", "total_tasks", "cpu_percent",
+    "memory_percent", "capacity_cores", "capacity_memory_bytes", "capacity_slots",
+    "last_update",
+];
+
+/// Run the snapshot subcommand.
+pub async fn run(args: SnapshotArgs, config: &Config) -> Result<()> {
+    let state = sync_state(config.monitor_manager(), Duration::from_secs(args.timeout)).await?;
+
+    let mut out = io::stdout().lock();
+    write_snapshot(&mut out, &state, args.format, args.only)?;
+    out.flush()?;
+
+    Ok(())
+}
+
+/// Connect to the engine, wait for the initial sync and disconnect again.
+pub async fn sync_state(mut manager: MonitorManager, timeout: Duration) -> Result<AppState> {
+    manager.connect().await?;
+
+    let mut state = AppState::new();
+    manager.initial_sync(&mut state, timeout).await?;
+    manager.disconnect().await?;
+
+    Ok(state)
+}
+
+/// Write a snapshot of the state in the given format.
+pub fn write_snapshot(
+    out: &mut impl Write,
+    state: &AppState,
+    format: SnapshotFormat,
+    only: Option<SnapshotSection>,
+) -> Result<()> {
+    let show_tasks = only != Some(SnapshotSection::Backends);
+    let show_backends = only != Some(SnapshotSection::Tasks);
+
+    let tasks = sorted_tasks(state);
+    let backends = sorted_backends(state);
+
+    match format {
+        SnapshotFormat::Json => {
+            let snapshot = Snapshot {
+                timestamp: Utc::now(),
+                tasks: show_tasks.then_some(tasks),
+                backends: show_backends.then_some(backends),
+            };
+            serde_json::to_writer_pretty(&mut *out, &snapshot)?;
+            writeln!(out)?;
+        }
+        SnapshotFormat::Csv => {
+            if show_tasks {
+                write_csv(out, &TASK_EXPORT_COLUMNS, tasks.iter().map(|t| TaskExportRow::from(*t).cells()))?;
+            }
+            if show_tasks && show_backends {
+                writeln!(out)?;
+            }
+            if show_backends {
+                write_csv(out, &BACKEND_COLUMNS, backends.iter().map(|b| backend_row(b)))?;
+            }
+        }
+        SnapshotFormat::Table => {
+            if show_tasks {
+                writeln!(out, "Tasks ({})", tasks.len())?;
+                write_table(out, &TASK_EXPORT_COLUMNS, tasks.iter().map(|t| TaskExportRow::from(*t).cells()).collect())?;
+            }
+            if show_tasks && show_backends {
+                writeln!(out)?;
+            }
+            if show_backends {
+                writeln!(out, "Backends ({})", backends.len())?;
+                write_table(out, &BACKEND_COLUMNS, backends.iter().map(|b| backend_row(b)).collect())?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Tasks sorted by ID.
+fn sorted_tasks(state: &AppState) -> Vec<&TaskState> {
+    let mut tasks: Vec<&TaskState> = state.tasks.values().collect();
+    tasks.sort_by_key(|t| t.id);
+    tasks
+}
+
+/// Backends sorted by name.
+fn sorted_backends(state: &AppState) -> Vec<&BackendState> {
+    let mut backends: Vec<&BackendState> = state.backends.values().collect();
+    backends.sort_by(|a, b| a.name.cmp(&b.name));
+    backends
+}
+
+/// Format a backend as a row of cells matching `BACKEND_COLUMNS`.
+fn backend_row(backend: &BackendState) -> Vec<String> {
+    vec![
+        backend.name.clone(),
+        backend.kind.to_string(),
+        backend.health.to_string(),
+        backend.running_tasks.to_string(),
+        backend.total_tasks.to_string(),
+        format!("{:.1}", backend.cpu_usage.0),
+        format!("{:.1}", backend.memory_usage.0),
+        backend.capacity.map(|c| format!("{:.3}", c.cores.0)).unwrap_or_default(),
+        backend.capacity.map(|c| c.memory.0.to_string()).unwrap_or_default(),
+        backend.capacity.map(|c| c.slots.to_string()).unwrap_or_default(),
+        backend.last_update.to_rfc3339(),
+    ]
+}
+
+/// Write rows as a plain-text table with aligned columns.
+fn write_table(out: &mut impl Write, columns: &[&str], rows: Vec<Vec<String>>) -> io::Result<()> {
+    // Compute the width of each column from the header and all cells
+    let mut widths: Vec<usize> = columns.iter().map(|c| c.len()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let header: Vec<String> = columns.iter()
+        .zip(&widths)
+        .map(|(c, w)| format!("{:<width$}", c.to_uppercase(), width = *w))
+        .collect();
+    writeln!(out, "{}", header.join("  ").trim_end())?;
+
+    for row in rows {
+        let cells: Vec<String> = row.iter()
+            .zip(&widths)
+            .map(|(c, w)| format!("{:<width$}", c, width = *w))
+            .collect();
+        writeln!(out, "{}", cells.join("  ").trim_end())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use crate::state::{testing, BackendKind, TaskStatus};
+
+    fn state() -> AppState {
+        let mut upstream = testing::task(1, "align");
+        upstream.transition(TaskStatus::Completed, Utc::now());
+        let mut failed = testing::task(2, "call");
+        failed.depends_on = vec![1];
+        failed.transition(TaskStatus::Failed, Utc::now());
+        failed.error = Some("exit code 1".into());
+        let mut state = testing::state_with([failed, upstream]);
+        state.backends.insert("docker".into(), BackendState::new("docker".into(), BackendKind::Docker));
+        state
+    }
+
+    fn snapshot(format: SnapshotFormat, only: Option<SnapshotSection>) -> String {
+        let mut out = Vec::new();
+        write_snapshot(&mut out, &state(), format, only).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn json_carries_full_task_state() {
+        let json: serde_json::Value = serde_json::from_str(&snapshot(SnapshotFormat::Json, None)).unwrap();
+        let tasks = json["tasks"].as_array().unwrap();
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[1]["id"], 2);
+        assert_eq!(tasks[1]["status"], "Failed");
+        assert_eq!(tasks[1]["error"], "exit code 1");
+        assert_eq!(tasks[1]["depends_on"], serde_json::json!([1]));
+        assert_eq!(tasks[1]["transitions"].as_array().unwrap().len(), 2);
+        assert_eq!(json["backends"][0]["name"], "docker");
+
+        let json: serde_json::Value = serde_json::from_str(&snapshot(SnapshotFormat::Json, Some(SnapshotSection::Backends))).unwrap();
+        assert!(json.get("tasks").is_none());
+    }
+
+    #[test]
+    fn csv_uses_export_columns() {
+        let csv = snapshot(SnapshotFormat::Csv, None);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], TASK_EXPORT_COLUMNS.join(","));
+        assert!(lines[1].starts_with("1,align-1,"));
+        assert!(lines[2].starts_with("2,call-2,"));
+        assert!(lines[2].ends_with(",exit code 1"));
+        assert_eq!(lines[3], "");
+        assert_eq!(lines[4], BACKEND_COLUMNS.join(","));
+        assert_eq!(lines.len(), 6);
+    }
+
+    #[test]
+    fn table_aligns_columns() {
+        let table = snapshot(SnapshotFormat::Table, Some(SnapshotSection::Tasks));
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "Tasks (2)");
+        assert!(lines[1].starts_with("ID  NAME     "));
+        assert!(lines[2].starts_with("1   align-1  "));
+        assert!(lines[3].starts_with("2   call-2   "));
+        assert_eq!(lines.len(), 4);
+    }
+}
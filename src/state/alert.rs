@@ -0,0 +1,55 @@
+//! Alert state management.
+//!
+//! Alerts are raised by state transitions that deserve the user's attention,
+//! such as a task failing or a backend becoming unhealthy.
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// Severity of an alert.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertSeverity {
+    Info,
+    Warning,
+    Critical,
+}
+
+impl std::fmt::Display for AlertSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AlertSeverity::Info => write!(f, "Info"),
+            AlertSeverity::Warning => write!(f, "Warning"),
+            AlertSeverity::Critical => write!(f, "Critical"),
+        }
+    }
+}
+
+/// An alert raised by a state transition.
+#[derive(Debug, Clone, Serialize)]
+pub struct Alert {
+    pub timestamp: DateTime<Utc>,
+    pub severity: AlertSeverity,
+    /// Entity that raised the alert (e.g. `backend:tes-cloud`, or a backend and
+    /// failure signature for task failures), used for de-duplication
+    pub source: String,
+    pub title: String,
+    pub message: String,
+}
+
+impl Alert {
+    pub fn new(
+        severity: AlertSeverity,
+        source: impl Into<String>,
+        title: impl Into<String>,
+        message: impl Into<String>,
+    ) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            severity,
+            source: source.into(),
+            title: title.into(),
+            message: message.into(),
+        }
+    }
+}
@@ -3,11 +3,12 @@
 //! Manages the state of Crankshaft execution backends.
 
 /// Backend type.
-use chrono::{DateTime, Utc};
-use crate::state::ResourceSample;
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use crate::state::{Bytes, Cores, Percent, ResourceHistory, TaskStatus, TimeSeries};
 
 /// Health status of a backend.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub enum BackendKind {
     Docker,
     TES,
@@ -16,7 +17,7 @@ pub enum BackendKind {
     Unknown,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 pub enum HealthStatus {
     Healthy,
     Degraded,
@@ -47,17 +48,53 @@ impl std::fmt::Display for BackendKind {
     }
 }
 
+/// Resources a backend can hand out to tasks.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct BackendCapacity {
+    pub cores: Cores,
+    pub memory: Bytes,
+    /// Number of tasks that can run at once
+    pub slots: usize,
+}
+
+/// Resources requested and used by the running tasks of a backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Allocation {
+    pub running: usize,
+    pub requested_cpu: Cores,
+    pub requested_memory: Bytes,
+    pub used_cpu: Cores,
+    pub used_memory: Bytes,
+}
+
+impl Allocation {
+    /// Share of the requested CPU that is actually used.
+    pub fn cpu_efficiency(&self) -> Percent {
+        Percent::of(self.used_cpu.0 as f64, self.requested_cpu.0 as f64)
+    }
+
+    /// Share of the requested memory that is actually used.
+    pub fn memory_efficiency(&self) -> Percent {
+        Percent::of(self.used_memory.0 as f64, self.requested_memory.0 as f64)
+    }
+}
+
 /// Backend state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BackendState {
     pub name: String,
     pub kind: BackendKind,
     pub running_tasks: usize,
     pub total_tasks: usize,
-    pub cpu_usage: f32,
-    pub memory_usage: f32,
+    pub cpu_usage: Percent,
+    pub memory_usage: Percent,
     pub health: HealthStatus,
-    pub resource_history: Vec<ResourceSample>,
+    /// Capacity, if the backend reports it
+    pub capacity: Option<BackendCapacity>,
+    #[serde(skip)]
+    pub resource_history: ResourceHistory,
+    #[serde(skip)]
+    pub task_activity: TaskActivity,
     pub last_update: DateTime<Utc>,
 }
 
@@ -68,19 +105,126 @@ impl BackendState {
             kind,
             running_tasks: 0,
             total_tasks: 0,
-            cpu_usage: 0.0,
-            memory_usage: 0.0,
+            cpu_usage: Percent::default(),
+            memory_usage: Percent::default(),
             health: HealthStatus::Unknown,
-            resource_history: Vec::new(),
+            capacity: None,
+            resource_history: ResourceHistory::new(),
+            task_activity: TaskActivity::new(),
             last_update: Utc::now(),
         }
     }
     
+    /// Builder method to set the capacity.
+    pub fn with_capacity(mut self, capacity: BackendCapacity) -> Self {
+        self.capacity = Some(capacity);
+        self
+    }
+    
+    /// Number of tasks that can run at once, falling back to the reported
+    /// task total when the capacity is unknown.
+    pub fn slots(&self) -> usize {
+        self.capacity.map_or(self.total_tasks, |c| c.slots)
+    }
+    
+    /// Relative capacity used to weight this backend in cluster totals:
+    /// cores when known, otherwise task slots.
+    pub fn capacity_weight(&self) -> f32 {
+        match self.capacity {
+            Some(capacity) if capacity.cores.0 > 0.0 => capacity.cores.0,
+            _ => self.slots().max(1) as f32,
+        }
+    }
+    
+    /// Share of task slots in use, from 0.0 to 1.0.
     pub fn utilization(&self) -> f32 {
-        if self.total_tasks == 0 {
-            0.0
-        } else {
-            self.running_tasks as f32 / self.total_tasks as f32
+        match self.slots() {
+            0 => 0.0,
+            slots => (self.running_tasks as f32 / slots as f32).min(1.0),
+        }
+    }
+    
+    /// Utilization over the window ending at `end` split into `columns`
+    /// equal parts, each from 0.0 to 1.0, or `None` where nothing was recorded.
+    pub fn utilization_history(
+        &self,
+        metric: UtilizationMetric,
+        end: DateTime<Utc>,
+        window: Duration,
+        columns: usize,
+    ) -> Vec<Option<f32>> {
+        let (series, scale) = match metric {
+            UtilizationMetric::Cpu => (&self.resource_history.cpu, 100.0),
+            UtilizationMetric::Memory => (&self.resource_history.memory, 100.0),
+            UtilizationMetric::Slots => match self.slots() {
+                0 => return vec![None; columns],
+                slots => (&self.task_activity.running, slots as f32),
+            },
+        };
+        series.binned(end, window, columns)
+            .into_iter()
+            .map(|value| value.map(|v| (v / scale).clamp(0.0, 1.0)))
+            .collect()
+    }
+}
+
+/// Measure of how busy a backend is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UtilizationMetric {
+    #[default]
+    Cpu,
+    Memory,
+    /// Share of task slots in use
+    Slots,
+}
+
+impl UtilizationMetric {
+    pub fn label(&self) -> &'static str {
+        match self {
+            UtilizationMetric::Cpu => "CPU",
+            UtilizationMetric::Memory => "Memory",
+            UtilizationMetric::Slots => "Slots",
+        }
+    }
+
+    /// The next metric, wrapping around.
+    pub fn next(self) -> Self {
+        match self {
+            UtilizationMetric::Cpu => UtilizationMetric::Memory,
+            UtilizationMetric::Memory => UtilizationMetric::Slots,
+            UtilizationMetric::Slots => UtilizationMetric::Cpu,
+        }
+    }
+}
+
+/// Task counts of a backend over time.
+///
+/// Running tasks are sampled on every status update; completions and
+/// failures are recorded as one sample per transition, so a bucket's sum is
+/// the number of tasks that finished in it.
+#[derive(Debug, Clone, Default)]
+pub struct TaskActivity {
+    pub running: TimeSeries,
+    pub completed: TimeSeries,
+    pub failed: TimeSeries,
+}
+
+impl TaskActivity {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record the number of running tasks.
+    pub fn record_running(&mut self, timestamp: DateTime<Utc>, running: usize) {
+        self.running.push(timestamp, running as f32);
+    }
+
+    /// Record a task reaching a terminal status.
+    pub fn record_finished(&mut self, timestamp: DateTime<Utc>, status: TaskStatus) {
+        match status {
+            TaskStatus::Completed => self.completed.push(timestamp, 1.0),
+            TaskStatus::Failed => self.failed.push(timestamp, 1.0),
+            _ => {}
         }
     }
-}
\ No newline at end of file
+}
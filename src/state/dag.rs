@@ -0,0 +1,429 @@
+//! Task dependency graph.
+//!
+//! Dependency edges come from the engine, as the `depends_on` of each task,
+//! or from a workflow description listing the calls each call consumes. The
+//! graph is laid out in layers, every task one layer right of its deepest
+//! upstream task, and the critical path is the chain with the longest total
+//! run time. A call consuming every task of another call is linked through
+//! one gather node for that call, so a scatter of N tasks gathered by M
+//! tasks takes N + M edges rather than N × M.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
+use std::path::Path;
+use chrono::{Duration, Utc};
+use eyre::{Result, WrapErr};
+use serde::Deserialize;
+
+use crate::state::{AppState, TaskState, TaskStatus};
+
+/// Call dependencies from a workflow description file.
+///
+/// ```toml
+/// [dependencies]
+/// alignment-job = ["fastq-conversion", "trim-adapters"]
+/// variant-calling = ["alignment-job"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WorkflowDescription {
+    /// Upstream calls of each call
+    pub dependencies: HashMap<String, Vec<String>>,
+}
+
+impl WorkflowDescription {
+    /// Load a workflow description file.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read workflow description {}", path.display()))?;
+        Self::parse(&contents).wrap_err_with(|| format!("invalid workflow description {}", path.display()))
+    }
+
+    /// Parse workflow description TOML.
+    pub fn parse(contents: &str) -> Result<Self> {
+        Ok(toml::from_str(contents)?)
+    }
+}
+
+/// IDs of gather nodes start here, above any task ID the engine hands out.
+pub const GATHER_ID_BASE: u64 = 1 << 63;
+
+/// Dependency graph of the tasks that have upstream or downstream tasks.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Dag {
+    /// Task IDs per layer, upstream layers first, each ordered to keep
+    /// tasks near their upstream tasks
+    pub layers: Vec<Vec<u64>>,
+    /// Upstream tasks of each task
+    pub parents: HashMap<u64, Vec<u64>>,
+    /// Downstream tasks of each task
+    pub children: HashMap<u64, Vec<u64>>,
+    /// Chain with the longest total run time, upstream first
+    pub critical_path: Vec<u64>,
+    /// Total run time of the critical path
+    pub critical_time: Duration,
+    /// Gather nodes, each standing for all tasks of a call, with the call name
+    pub gathers: HashMap<u64, String>,
+    /// Layer and position within it of each task
+    positions: HashMap<u64, (usize, usize)>,
+    /// Tasks in topological order, for recomputing the critical path
+    order: Vec<u64>,
+}
+
+impl Dag {
+    /// Build the graph from the tasks' reported dependencies and the
+    /// workflow description.
+    pub fn from_state(state: &AppState) -> Self {
+        let (edges, gathers) = edges(state);
+        let mut parents: HashMap<u64, Vec<u64>> = HashMap::new();
+        let mut children: HashMap<u64, Vec<u64>> = HashMap::new();
+        for &(from, to) in &edges {
+            parents.entry(to).or_default().push(from);
+            children.entry(from).or_default().push(to);
+            parents.entry(from).or_default();
+            children.entry(to).or_default();
+        }
+
+        // Kahn's algorithm; tasks caught in a cycle are left in the first layer
+        let mut pending: HashMap<u64, usize> = parents.iter().map(|(&id, p)| (id, p.len())).collect();
+        let mut roots: Vec<u64> = pending.iter().filter(|(_, &n)| n == 0).map(|(&id, _)| id).collect();
+        roots.sort_unstable();
+        let mut queue: VecDeque<u64> = roots.into();
+        let mut order = Vec::with_capacity(parents.len());
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+            for child in &children[&id] {
+                let n = pending.get_mut(child).unwrap();
+                *n -= 1;
+                if *n == 0 {
+                    queue.push_back(*child);
+                }
+            }
+        }
+        let ordered: HashSet<u64> = order.iter().copied().collect();
+        let mut cyclic: Vec<u64> = parents.keys().filter(|id| !ordered.contains(id)).copied().collect();
+        cyclic.sort_unstable();
+
+        let mut layer_of: HashMap<u64, usize> = cyclic.iter().map(|&id| (id, 0)).collect();
+        for &id in &order {
+            let layer = parents[&id].iter()
+                .filter_map(|p| layer_of.get(p))
+                .map(|l| l + 1)
+                .max()
+                .unwrap_or(0);
+            layer_of.insert(id, layer);
+        }
+
+        let mut layers: Vec<Vec<u64>> = vec![Vec::new(); layer_of.values().max().map_or(0, |l| l + 1)];
+        for &id in cyclic.iter().chain(&order) {
+            layers[layer_of[&id]].push(id);
+        }
+
+        // Order each layer by the mean position of its tasks' upstream tasks
+        let mut positions: HashMap<u64, (usize, usize)> = HashMap::new();
+        for (index, layer) in layers.iter_mut().enumerate() {
+            let barycenter = |id: &u64| {
+                let placed: Vec<usize> = parents[id].iter().filter_map(|p| positions.get(p)).map(|&(_, i)| i).collect();
+                if placed.is_empty() {
+                    f64::MAX
+                } else {
+                    placed.iter().sum::<usize>() as f64 / placed.len() as f64
+                }
+            };
+            let mut keyed: Vec<(f64, u64)> = layer.iter().map(|id| (barycenter(id), *id)).collect();
+            keyed.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+            *layer = keyed.into_iter().map(|(_, id)| id).collect();
+            for (i, &id) in layer.iter().enumerate() {
+                positions.insert(id, (index, i));
+            }
+        }
+
+        let (critical_path, critical_time) = critical_path(state, &order, &parents);
+        Self { layers, parents, children, critical_path, critical_time, gathers, positions, order }
+    }
+
+    /// Recompute the critical path from the tasks' current run times,
+    /// keeping the layout.
+    pub fn update_critical_path(&mut self, state: &AppState) {
+        (self.critical_path, self.critical_time) = critical_path(state, &self.order, &self.parents);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Number of nodes in the graph, gather nodes included.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Number of tasks in the graph.
+    pub fn task_count(&self) -> usize {
+        self.positions.len() - self.gathers.len()
+    }
+
+    /// Whether a node is a gather node rather than a task.
+    pub fn is_gather(&self, id: u64) -> bool {
+        self.gathers.contains_key(&id)
+    }
+
+    /// Number of dependency edges.
+    pub fn edge_count(&self) -> usize {
+        self.parents.values().map(Vec::len).sum()
+    }
+
+    /// Layer and position within the layer of a task.
+    pub fn position(&self, id: u64) -> Option<(usize, usize)> {
+        self.positions.get(&id).copied()
+    }
+
+    /// A task with all its upstream and downstream tasks.
+    pub fn lineage(&self, id: u64) -> HashSet<u64> {
+        let mut lineage = HashSet::from([id]);
+        for edges in [&self.parents, &self.children] {
+            let mut stack = vec![id];
+            while let Some(next) = stack.pop() {
+                for &other in edges.get(&next).into_iter().flatten() {
+                    if lineage.insert(other) {
+                        stack.push(other);
+                    }
+                }
+            }
+        }
+        lineage
+    }
+}
+
+/// Dependency edges as (upstream, downstream) node IDs, and the gather
+/// nodes they pass through.
+fn edges(state: &AppState) -> (BTreeSet<(u64, u64)>, HashMap<u64, String>) {
+    let mut edges = BTreeSet::new();
+    for task in state.tasks.values() {
+        for &parent in &task.depends_on {
+            if parent != task.id && state.tasks.contains_key(&parent) {
+                edges.insert((parent, task.id));
+            }
+        }
+    }
+
+    let dependencies = &state.workflow_description.dependencies;
+    if dependencies.is_empty() {
+        return (edges, HashMap::new());
+    }
+
+    // Tasks of each call within each workflow run, ordered so gather node
+    // IDs are stable
+    let mut calls: BTreeMap<(Option<&str>, &str), Vec<&TaskState>> = BTreeMap::new();
+    for task in state.tasks.values() {
+        calls.entry((task.workflow_id.as_deref(), task.call())).or_default().push(task);
+    }
+    for tasks in calls.values_mut() {
+        tasks.sort_by_key(|t| t.id);
+    }
+
+    let mut gathers: BTreeMap<(Option<&str>, &str), u64> = BTreeMap::new();
+    for (&(workflow, call), tasks) in &calls {
+        let Some(upstream_calls) = dependencies.get(call) else {
+            continue;
+        };
+        for upstream_call in upstream_calls {
+            let Some(upstream) = calls.get(&(workflow, upstream_call.as_str())) else {
+                continue;
+            };
+            for task in tasks {
+                // A scattered call consumes the matching shard of a call
+                // scattered the same way, and every task of any other call
+                let shard = upstream.iter()
+                    .find(|u| u.shard_index.is_some() && u.shard_index == task.shard_index);
+                if let Some(shard) = shard {
+                    edges.insert((shard.id, task.id));
+                } else if upstream.len() > 1 && tasks.len() > 1 {
+                    let next = GATHER_ID_BASE + gathers.len() as u64;
+                    let gather = *gathers.entry((workflow, upstream_call.as_str())).or_insert_with(|| {
+                        edges.extend(upstream.iter().map(|u| (u.id, next)));
+                        next
+                    });
+                    edges.insert((gather, task.id));
+                } else {
+                    edges.extend(upstream.iter().map(|u| (u.id, task.id)));
+                }
+            }
+        }
+    }
+
+    let gathers = gathers.into_iter().map(|((_, call), id)| (id, call.to_string())).collect();
+    (edges, gathers)
+}
+
+/// The chain of tasks with the longest total run time, counting the
+/// expected remaining time of running tasks, and that total.
+fn critical_path(state: &AppState, order: &[u64], parents: &HashMap<u64, Vec<u64>>) -> (Vec<u64>, Duration) {
+    let now = Utc::now();
+    let weight = |id: u64| -> i64 {
+        let Some(task) = state.tasks.get(&id) else {
+            return 0;
+        };
+        let run = task.run_time().map_or(0, |d| d.num_milliseconds());
+        let remaining = if task.status == TaskStatus::Running {
+            task.eta(now).map_or(0, |d| d.num_milliseconds())
+        } else {
+            0
+        };
+        run + remaining
+    };
+
+    // Longest path ending at each task, with the upstream task it came from
+    let mut longest: HashMap<u64, (i64, Option<u64>)> = HashMap::new();
+    for &id in order {
+        let best = parents[&id].iter()
+            .filter_map(|p| longest.get(p).map(|&(length, _)| (length, *p)))
+            .max();
+        let (length, from) = best.map_or((0, None), |(length, p)| (length, Some(p)));
+        longest.insert(id, (length + weight(id), from));
+    }
+
+    let Some((&end, &(length, _))) = longest.iter()
+        .max_by_key(|(id, (length, _))| (*length, std::cmp::Reverse(**id)))
+    else {
+        return (Vec::new(), Duration::zero());
+    };
+    let mut path = Vec::new();
+    let mut next = Some(end);
+    while let Some(id) = next {
+        path.push(id);
+        next = longest[&id].1;
+    }
+    path.reverse();
+    (path, Duration::milliseconds(length))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    use std::rc::Rc;
+    use crate::state::testing::{self, state_with};
+    use crate::state::TaskUpdate;
+
+    fn task(id: u64, depends_on: &[u64]) -> TaskState {
+        let mut task = testing::task(id, "task");
+        task.depends_on = depends_on.to_vec();
+        task
+    }
+
+    /// A finished task that ran for `secs` seconds.
+    fn ran(mut task: TaskState, secs: i64) -> TaskState {
+        let start = Utc::now() - Duration::hours(1);
+        task.transition(TaskStatus::Running, start);
+        task.transition(TaskStatus::Completed, start + Duration::seconds(secs));
+        task
+    }
+
+    fn shard(id: u64, call: &str, index: Option<u32>) -> TaskState {
+        testing::task(id, call).with_workflow("run-1", call, index)
+    }
+
+    #[test]
+    fn empty_without_dependencies() {
+        let dag = Dag::from_state(&state_with(vec![task(1, &[]), task(2, &[])]));
+        assert!(dag.is_empty());
+        assert_eq!(dag.len(), 0);
+        assert!(dag.critical_path.is_empty());
+        assert_eq!(dag.critical_time, Duration::zero());
+    }
+
+    #[test]
+    fn layers_by_longest_upstream_chain() {
+        // 1 -> 2 -> 3 and 1 -> 3
+        let dag = Dag::from_state(&state_with(vec![task(1, &[]), task(2, &[1]), task(3, &[1, 2])]));
+        assert_eq!(dag.layers, vec![vec![1], vec![2], vec![3]]);
+        assert_eq!(dag.edge_count(), 3);
+        assert_eq!(dag.position(3), Some((2, 0)));
+        assert_eq!(dag.lineage(2), HashSet::from([1, 2, 3]));
+    }
+
+    #[test]
+    fn ignores_unknown_and_self_dependencies() {
+        let dag = Dag::from_state(&state_with(vec![task(1, &[1, 99]), task(2, &[1])]));
+        assert_eq!(dag.edge_count(), 1);
+    }
+
+    #[test]
+    fn cycles_stay_in_the_first_layer() {
+        // 1 <-> 2, and 3 downstream of the cycle
+        let dag = Dag::from_state(&state_with(vec![task(1, &[2]), task(2, &[1]), task(3, &[2])]));
+        assert_eq!(dag.len(), 3);
+        assert_eq!(dag.layers[0], vec![1, 2, 3]);
+        assert!(dag.critical_path.is_empty());
+    }
+
+    #[test]
+    fn critical_path_follows_longest_run_time() {
+        // 1 -> 2 -> 4 takes 30s, 1 -> 3 -> 4 takes 70s
+        let dag = Dag::from_state(&state_with(vec![
+            ran(task(1, &[]), 10),
+            ran(task(2, &[1]), 10),
+            ran(task(3, &[1]), 50),
+            ran(task(4, &[2, 3]), 10),
+        ]));
+        assert_eq!(dag.critical_path, vec![1, 3, 4]);
+        assert_eq!(dag.critical_time, Duration::seconds(70));
+    }
+
+    #[test]
+    fn matching_shards_link_one_to_one() {
+        let mut state = state_with(vec![
+            shard(1, "align", Some(0)),
+            shard(2, "align", Some(1)),
+            shard(3, "call", Some(0)),
+            shard(4, "call", Some(1)),
+        ]);
+        state.workflow_description = WorkflowDescription::parse("[dependencies]\ncall = [\"align\"]").unwrap();
+
+        let dag = Dag::from_state(&state);
+        assert_eq!(dag.parents[&3], vec![1]);
+        assert_eq!(dag.parents[&4], vec![2]);
+        assert!(dag.gathers.is_empty());
+    }
+
+    #[test]
+    fn gathers_collapse_all_to_all_edges() {
+        let mut tasks: Vec<TaskState> = (1..=30).map(|id| shard(id, "align", Some(id as u32))).collect();
+        tasks.extend((31..=50).map(|id| shard(id, "merge", None)));
+        let mut state = state_with(tasks);
+        state.workflow_description = WorkflowDescription::parse("[dependencies]\nmerge = [\"align\"]").unwrap();
+
+        let dag = Dag::from_state(&state);
+        assert_eq!(dag.gathers, HashMap::from([(GATHER_ID_BASE, "align".to_string())]));
+        assert_eq!(dag.edge_count(), 30 + 20);
+        assert_eq!(dag.task_count(), 50);
+        assert_eq!(dag.layers.len(), 3);
+        assert!(dag.lineage(31).contains(&1));
+    }
+
+    #[test]
+    fn single_upstream_task_links_directly() {
+        let mut state = state_with(vec![shard(1, "index", None), shard(2, "call", Some(0)), shard(3, "call", Some(1))]);
+        state.workflow_description = WorkflowDescription::parse("[dependencies]\ncall = [\"index\"]").unwrap();
+
+        let dag = Dag::from_state(&state);
+        assert!(dag.gathers.is_empty());
+        assert_eq!(dag.children[&1], vec![2, 3]);
+    }
+
+    #[test]
+    fn state_caches_the_graph_until_tasks_change() {
+        let mut state = AppState::new();
+        state.update_tasks(vec![TaskUpdate::Created(Box::new(task(1, &[]))), TaskUpdate::Created(Box::new(task(2, &[1])))]);
+        let first = state.dag();
+        assert!(Rc::ptr_eq(&first, &state.dag()));
+
+        state.update_tasks(vec![TaskUpdate::Progress(2, 0.5)]);
+        assert!(Rc::ptr_eq(&first, &state.dag()));
+
+        state.update_tasks(vec![TaskUpdate::Created(Box::new(task(3, &[2])))]);
+        let rebuilt = state.dag();
+        assert!(!Rc::ptr_eq(&first, &rebuilt));
+        assert_eq!(rebuilt.len(), 3);
+    }
+}
@@ -0,0 +1,311 @@
+//! Resource efficiency of tasks.
+//!
+//! Compares what each task requested with what its recorded history shows it
+//! used: mean CPU against requested cores, and peak memory against requested
+//! memory. Tasks are grouped by name without their numeric suffix so
+//! pipeline owners can right-size the requests in their task definitions.
+
+use std::collections::HashMap;
+use chrono::{Duration, Utc};
+
+use crate::state::{AppState, Bytes, Cores, Percent, TaskState, TaskStatus};
+
+/// Headroom added on top of observed usage when suggesting requests.
+const SUGGESTION_HEADROOM: f64 = 1.2;
+
+/// CPU requests are suggested in steps of this many millicores.
+const CPU_STEP_MILLICORES: u32 = 250;
+
+/// Memory requests are suggested in steps of this many MiB.
+const MEMORY_STEP_MIB: f64 = 256.0;
+
+/// Requested vs. used resources of a single task.
+#[derive(Debug, Clone)]
+pub struct TaskEfficiency {
+    pub task_id: u64,
+    pub name: String,
+    /// Task name without its numeric suffix
+    pub group: String,
+    pub status: TaskStatus,
+    pub requested_cpu: Cores,
+    pub requested_memory: Bytes,
+    pub mean_cpu: Cores,
+    pub peak_memory: Bytes,
+    /// Time the task has been running for, or ran for, excluding queue wait
+    pub runtime: Duration,
+}
+
+impl TaskEfficiency {
+    /// Measure a task against its requests. Tasks without requests, or that
+    /// have not started running, are skipped. Tasks without recorded history
+    /// are measured by their latest reported usage.
+    pub fn measure(task: &TaskState, state: &AppState) -> Option<Self> {
+        let (requested_cpu, requested_memory) = (task.requested_cpu?, task.requested_memory?);
+        if matches!(task.status, TaskStatus::Created | TaskStatus::Queued) {
+            return None;
+        }
+
+        // Average over the task's lifetime, falling back to the latest sample
+        let end = task.finished_at().unwrap_or_else(Utc::now);
+        let runtime = task.run_time().unwrap_or_else(Duration::zero).max(Duration::zero());
+        let history = state.task_history(task.id);
+        let mean_cpu = history
+            .and_then(|h| h.cpu.mean_ending(end, runtime.max(Duration::seconds(1))))
+            .map_or(task.cpu_usage, Cores);
+        let peak_memory = history
+            .and_then(|h| h.memory.peak())
+            .map_or(task.memory_usage, |peak| Bytes(peak as u64))
+            .max(task.memory_usage);
+
+        Some(Self {
+            task_id: task.id,
+            name: task.name.clone(),
+            group: task.base_name().to_string(),
+            status: task.status,
+            requested_cpu,
+            requested_memory,
+            mean_cpu,
+            peak_memory,
+            runtime,
+        })
+    }
+
+    /// Mean CPU as a share of the requested cores.
+    pub fn cpu_efficiency(&self) -> Percent {
+        Percent::of(self.mean_cpu.0 as f64, self.requested_cpu.0 as f64)
+    }
+
+    /// Peak memory as a share of the requested memory.
+    pub fn memory_efficiency(&self) -> Percent {
+        Percent::of(self.peak_memory.0 as f64, self.requested_memory.0 as f64)
+    }
+
+    /// Overall score: the mean of the CPU and memory efficiency.
+    pub fn score(&self) -> Percent {
+        Percent::new((self.cpu_efficiency().0 + self.memory_efficiency().0) / 2.0)
+    }
+
+    /// Requested but unused cores multiplied by the runtime in hours.
+    pub fn wasted_core_hours(&self) -> f64 {
+        let idle = (self.requested_cpu.0 - self.mean_cpu.0).max(0.0) as f64;
+        idle * hours(self.runtime)
+    }
+}
+
+/// Efficiency of all measured tasks sharing a name, ignoring numeric suffixes.
+#[derive(Debug, Clone)]
+pub struct NameEfficiency {
+    pub name: String,
+    pub tasks: Vec<TaskEfficiency>,
+}
+
+impl NameEfficiency {
+    /// Largest CPU request among the tasks.
+    pub fn requested_cpu(&self) -> Cores {
+        self.tasks.iter().map(|t| t.requested_cpu).fold(Cores::default(), max_cores)
+    }
+
+    /// Largest memory request among the tasks.
+    pub fn requested_memory(&self) -> Bytes {
+        self.tasks.iter().map(|t| t.requested_memory).max().unwrap_or_default()
+    }
+
+    /// CPU efficiency weighted by runtime, so long tasks count for more.
+    pub fn cpu_efficiency(&self) -> Percent {
+        self.weighted(TaskEfficiency::cpu_efficiency)
+    }
+
+    /// Memory efficiency weighted by runtime.
+    pub fn memory_efficiency(&self) -> Percent {
+        self.weighted(TaskEfficiency::memory_efficiency)
+    }
+
+    /// Overall score weighted by runtime.
+    pub fn score(&self) -> Percent {
+        self.weighted(TaskEfficiency::score)
+    }
+
+    /// Wasted core-hours summed over the tasks.
+    pub fn wasted_core_hours(&self) -> f64 {
+        self.tasks.iter().map(TaskEfficiency::wasted_core_hours).sum()
+    }
+
+    /// Suggested CPU request: the highest mean usage plus headroom, rounded
+    /// up to a quarter core.
+    pub fn suggested_cpu(&self) -> Cores {
+        let highest = self.tasks.iter().map(|t| t.mean_cpu).fold(Cores::default(), max_cores);
+        let millicores = highest.0 as f64 * SUGGESTION_HEADROOM * 1000.0;
+        let steps = (millicores / CPU_STEP_MILLICORES as f64).ceil().max(1.0) as u32;
+        Cores::from_millicores(steps * CPU_STEP_MILLICORES)
+    }
+
+    /// Suggested memory request: the highest peak plus headroom, rounded up
+    /// to 256 MiB.
+    pub fn suggested_memory(&self) -> Bytes {
+        let highest = self.tasks.iter().map(|t| t.peak_memory).max().unwrap_or_default();
+        let mib = highest.as_mib() * SUGGESTION_HEADROOM;
+        Bytes::from_mib(((mib / MEMORY_STEP_MIB).ceil()).max(1.0) * MEMORY_STEP_MIB)
+    }
+
+    fn weighted(&self, metric: impl Fn(&TaskEfficiency) -> Percent) -> Percent {
+        let total: f64 = self.tasks.iter().map(|t| hours(t.runtime)).sum();
+        if total <= 0.0 {
+            let mean = self.tasks.iter().map(|t| metric(t).0).sum::<f32>() / self.tasks.len().max(1) as f32;
+            return Percent::new(mean);
+        }
+        let sum: f64 = self.tasks.iter().map(|t| metric(t).0 as f64 * hours(t.runtime)).sum();
+        Percent::new((sum / total) as f32)
+    }
+}
+
+/// Efficiency of every task and task name, sorted by wasted core-hours.
+#[derive(Debug, Clone, Default)]
+pub struct EfficiencyReport {
+    pub tasks: Vec<TaskEfficiency>,
+    pub names: Vec<NameEfficiency>,
+}
+
+impl EfficiencyReport {
+    /// Measure all tasks that have requests and have started running.
+    pub fn from_state(state: &AppState) -> Self {
+        let mut tasks: Vec<TaskEfficiency> = state.tasks.values()
+            .filter_map(|task| TaskEfficiency::measure(task, state))
+            .collect();
+        tasks.sort_by(|a, b| {
+            b.wasted_core_hours().total_cmp(&a.wasted_core_hours()).then(a.task_id.cmp(&b.task_id))
+        });
+
+        let mut by_name: HashMap<&str, Vec<TaskEfficiency>> = HashMap::new();
+        for task in &tasks {
+            by_name.entry(task.group.as_str()).or_default().push(task.clone());
+        }
+        let mut names: Vec<NameEfficiency> = by_name.into_iter()
+            .map(|(name, tasks)| NameEfficiency { name: name.to_string(), tasks })
+            .collect();
+        names.sort_by(|a, b| {
+            b.wasted_core_hours().total_cmp(&a.wasted_core_hours()).then(a.name.cmp(&b.name))
+        });
+
+        Self { tasks, names }
+    }
+
+    /// Wasted core-hours over all tasks.
+    pub fn wasted_core_hours(&self) -> f64 {
+        self.tasks.iter().map(TaskEfficiency::wasted_core_hours).sum()
+    }
+}
+
+fn hours(duration: Duration) -> f64 {
+    duration.num_milliseconds().max(0) as f64 / 3_600_000.0
+}
+
+fn max_cores(a: Cores, b: Cores) -> Cores {
+    if b > a { b } else { a }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::state::testing::{self, state_with};
+
+    /// A measurement of one core and 1 GiB requested.
+    fn measured(mean_cpu: f32, peak_mib: f64, runtime_minutes: i64) -> TaskEfficiency {
+        TaskEfficiency {
+            task_id: 1,
+            name: "align-1".into(),
+            group: "align".into(),
+            status: TaskStatus::Completed,
+            requested_cpu: Cores(1.0),
+            requested_memory: Bytes::from_gib(1.0),
+            mean_cpu: Cores(mean_cpu),
+            peak_memory: Bytes::from_mib(peak_mib),
+            runtime: Duration::minutes(runtime_minutes),
+        }
+    }
+
+    fn named(tasks: Vec<TaskEfficiency>) -> NameEfficiency {
+        NameEfficiency { name: "align".into(), tasks }
+    }
+
+    #[rstest]
+    #[case::idle_gets_one_step(0.0, 250)]
+    #[case::below_one_step(0.2, 250)]
+    #[case::rounds_up(0.5, 750)]
+    #[case::exact_step(1.25, 1500)]
+    #[case::past_a_core(1.0, 1250)]
+    fn suggested_cpu_rounds_up_to_quarter_cores(#[case] mean_cpu: f32, #[case] millicores: u32) {
+        let name = named(vec![measured(mean_cpu, 0.0, 10)]);
+        assert_eq!(name.suggested_cpu().millicores(), millicores);
+    }
+
+    #[rstest]
+    #[case::unused_gets_one_step(0.0, 256.0)]
+    #[case::below_one_step(100.0, 256.0)]
+    #[case::rounds_up(1000.0, 1280.0)]
+    #[case::exact_step(640.0, 768.0)]
+    fn suggested_memory_rounds_up_to_256_mib(#[case] peak_mib: f64, #[case] mib: f64) {
+        let name = named(vec![measured(0.0, peak_mib, 10)]);
+        assert_eq!(name.suggested_memory(), Bytes::from_mib(mib));
+    }
+
+    #[test]
+    fn suggestions_follow_the_busiest_task() {
+        let name = named(vec![measured(0.5, 2000.0, 10), measured(1.0, 500.0, 10)]);
+        assert_eq!(name.suggested_cpu(), Cores::from_millicores(1250));
+        assert_eq!(name.suggested_memory(), Bytes::from_mib(2560.0));
+    }
+
+    #[test]
+    fn weights_efficiency_by_runtime() {
+        // Three hours fully used and one hour idle
+        let name = named(vec![measured(1.0, 1024.0, 180), measured(0.0, 0.0, 60)]);
+        assert_eq!(name.cpu_efficiency(), Percent(75.0));
+        assert_eq!(name.memory_efficiency(), Percent(75.0));
+        assert_eq!(name.score(), Percent(75.0));
+    }
+
+    #[test]
+    fn plain_mean_without_runtime() {
+        let name = named(vec![measured(1.0, 1024.0, 0), measured(0.0, 0.0, 0)]);
+        assert_eq!(name.cpu_efficiency(), Percent(50.0));
+        assert_eq!(name.memory_efficiency(), Percent(50.0));
+        assert_eq!(named(Vec::new()).cpu_efficiency(), Percent(0.0));
+    }
+
+    #[test]
+    fn report_orders_by_wasted_core_hours() {
+        let now = Utc::now();
+        let task = |id: u64, name: &str, requested: f32, used: f32, minutes: i64| {
+            let mut task = testing::task(id, name);
+            task.requested_cpu = Some(Cores(requested));
+            task.requested_memory = Some(Bytes::from_gib(1.0));
+            task.cpu_usage = Cores(used);
+            task.transition(TaskStatus::Running, now - Duration::minutes(minutes));
+            task.transition(TaskStatus::Completed, now);
+            task
+        };
+        let mut queued = testing::task(5, "sort");
+        queued.requested_cpu = Some(Cores(8.0));
+        queued.requested_memory = Some(Bytes::from_gib(1.0));
+        queued.transition(TaskStatus::Queued, now);
+        let unrequested = testing::task(6, "sort");
+
+        let report = EfficiencyReport::from_state(&state_with(vec![
+            task(1, "sort", 2.0, 1.9, 120), // 0.2 core-hours
+            task(2, "align", 4.0, 1.0, 60), // 3 core-hours
+            task(3, "align", 1.0, 1.0, 60), // none
+            task(4, "index", 2.0, 0.5, 60), // 1.5 core-hours
+            queued,
+            unrequested,
+        ]));
+
+        let ids: Vec<u64> = report.tasks.iter().map(|t| t.task_id).collect();
+        assert_eq!(ids, vec![2, 4, 1, 3]);
+        let names: Vec<&str> = report.names.iter().map(|n| n.name.as_str()).collect();
+        assert_eq!(names, vec!["align", "index", "sort"]);
+        assert!((report.wasted_core_hours() - 4.7).abs() < 1e-6);
+    }
+}
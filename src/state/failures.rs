@@ -0,0 +1,212 @@
+//! Failure clustering by error signature.
+//!
+//! Failed tasks are grouped by a signature of their error: the failure
+//! reason, or the last log line when none was reported, with paths, IDs and
+//! numbers replaced by placeholders. Hundreds of shards failing the same way
+//! then show up as one cluster.
+
+use std::collections::{BTreeMap, HashMap};
+use chrono::{DateTime, Utc};
+
+use crate::state::{AppState, TaskState, TaskStatus};
+
+/// Signature of failed tasks that reported neither an error nor any logs.
+const NO_REASON: &str = "(no error reported)";
+
+/// Failed tasks sharing an error signature.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailureCluster {
+    /// Normalized error
+    pub signature: String,
+    /// Member task IDs, most recent failure first
+    pub tasks: Vec<u64>,
+    /// Number of members per backend
+    pub backends: BTreeMap<String, usize>,
+    /// Unnormalized error of the most recent member
+    pub example: String,
+    /// When the most recent member failed
+    pub last_failed: Option<DateTime<Utc>>,
+}
+
+/// Failed tasks clustered by error signature.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FailureReport {
+    /// Clusters, largest first
+    pub clusters: Vec<FailureCluster>,
+}
+
+impl FailureReport {
+    /// Cluster the failed tasks in `state`.
+    pub fn from_state(state: &AppState) -> Self {
+        let mut failed: Vec<&TaskState> = state.tasks.values()
+            .filter(|t| t.status == TaskStatus::Failed)
+            .collect();
+        failed.sort_by(|a, b| b.finished_at().cmp(&a.finished_at()).then(a.id.cmp(&b.id)));
+
+        let mut index: HashMap<String, usize> = HashMap::new();
+        let mut clusters: Vec<FailureCluster> = Vec::new();
+        for task in failed {
+            let reason = failure_reason(task);
+            let signature = signature(reason);
+            let cluster = match index.get(&signature) {
+                Some(&i) => &mut clusters[i],
+                None => {
+                    index.insert(signature.clone(), clusters.len());
+                    clusters.push(FailureCluster {
+                        signature,
+                        tasks: Vec::new(),
+                        backends: BTreeMap::new(),
+                        example: reason.to_string(),
+                        last_failed: task.finished_at(),
+                    });
+                    clusters.last_mut().unwrap()
+                }
+            };
+            cluster.tasks.push(task.id);
+            *cluster.backends.entry(task.backend.clone()).or_default() += 1;
+        }
+
+        clusters.sort_by(|a, b| b.tasks.len().cmp(&a.tasks.len()).then(b.last_failed.cmp(&a.last_failed)));
+        Self { clusters }
+    }
+
+    /// Number of failed tasks across all clusters.
+    pub fn failed_tasks(&self) -> usize {
+        self.clusters.iter().map(|c| c.tasks.len()).sum()
+    }
+}
+
+/// The failure reason of a task, falling back to its last log line.
+pub fn failure_reason(task: &TaskState) -> &str {
+    task.error.as_deref()
+        .or_else(|| task.log_tail.back().map(String::as_str))
+        .unwrap_or(NO_REASON)
+}
+
+/// Normalize an error message so that failures differing only in paths,
+/// IDs or numbers share a signature.
+pub fn signature(message: &str) -> String {
+    message.split_whitespace()
+        .map(normalize_word)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Replace a path or ID with a placeholder, and digit runs with `#`.
+fn normalize_word(word: &str) -> String {
+    // Keep surrounding quotes and punctuation such as a trailing colon
+    let core = word.trim_matches(|c: char| !c.is_alphanumeric() && c != '/' && c != '\\' && c != '.' && c != '_' && c != '-');
+    let core = core.trim_end_matches('.');
+    if core.is_empty() {
+        return word.to_string();
+    }
+    let start = word.find(core).unwrap_or(0);
+    let (prefix, suffix) = (&word[..start], &word[start + core.len()..]);
+
+    if core.contains('/') || core.contains('\\') {
+        return format!("{}<path>{}", prefix, suffix);
+    }
+    if is_id(core) {
+        return format!("{}<id>{}", prefix, suffix);
+    }
+
+    let mut normalized = String::with_capacity(word.len());
+    let mut in_digits = false;
+    for c in word.chars() {
+        if c.is_ascii_digit() {
+            if !in_digits {
+                normalized.push('#');
+            }
+            in_digits = true;
+        } else {
+            normalized.push(c);
+            in_digits = false;
+        }
+    }
+    normalized
+}
+
+/// Whether a word looks like a generated identifier: a hex string with
+/// digits, a UUID, or a `0x` address.
+fn is_id(word: &str) -> bool {
+    let hex = word.strip_prefix("0x").unwrap_or(word);
+    let digits = hex.chars().filter(|c| c.is_ascii_digit()).count();
+    let hex_chars = hex.chars().filter(|c| c.is_ascii_hexdigit()).count();
+    let dashes = hex.chars().filter(|c| *c == '-').count();
+    let all_hex = hex_chars + dashes == hex.len();
+    all_hex && digits > 0 && hex_chars >= 6 && (dashes == 0 || dashes == 4)
+        && (hex_chars > digits || word.starts_with("0x") || dashes == 4)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use crate::state::testing::{self, state_with};
+
+    #[rstest]
+    #[case("", "")]
+    #[case("Out of memory", "Out of memory")]
+    #[case("  exit   code 137 ", "exit code #")]
+    #[case("cannot open /data/sample_42.bam: no such file", "cannot open <path>: no such file")]
+    #[case("container 3f2a9c1b exited", "container <id> exited")]
+    #[case("job 'a1b2c3d4-e5f6-7890-abcd-ef1234567890' failed", "job '<id>' failed")]
+    #[case("node-12 timed out after 3600s", "node-# timed out after #s")]
+    #[case("retry 3 of 5.", "retry # of #.")]
+    fn signature_normalizes_variable_parts(#[case] message: &str, #[case] expected: &str) {
+        assert_eq!(signature(message), expected);
+    }
+
+    #[rstest]
+    #[case("3f2a9c1b", true)]
+    #[case("0x7ffd5e3c", true)]
+    #[case("a1b2c3d4-e5f6-7890-abcd-ef1234567890", true)]
+    #[case("12345678", false)]
+    #[case("deadbeef", false)]
+    #[case("0x7ffe", false)]
+    #[case("abc-1234", false)]
+    #[case("", false)]
+    fn is_id_matches_generated_identifiers(#[case] word: &str, #[case] expected: bool) {
+        assert_eq!(is_id(word), expected);
+    }
+
+    fn failed(id: u64, backend: &str, error: Option<&str>, log: Option<&str>, at: DateTime<Utc>) -> TaskState {
+        let mut task = testing::task(id, "align");
+        task.backend = backend.into();
+        task.error = error.map(String::from);
+        task.log_tail.extend(log.map(String::from));
+        task.transition(TaskStatus::Failed, at);
+        task
+    }
+
+    #[test]
+    fn no_failures() {
+        let report = FailureReport::from_state(&state_with([testing::task(1, "align")]));
+        assert_eq!(report, FailureReport::default());
+        assert_eq!(report.failed_tasks(), 0);
+    }
+
+    #[test]
+    fn clusters_by_signature_largest_first() {
+        let now = Utc::now();
+        let state = state_with([
+            failed(1, "docker", Some("exit code 137"), None, now - Duration::minutes(3)),
+            failed(2, "slurm", Some("exit code 1"), None, now - Duration::minutes(1)),
+            failed(3, "docker", None, Some("disk full on /scratch"), now - Duration::minutes(2)),
+            failed(4, "docker", None, None, now),
+        ]);
+
+        let report = FailureReport::from_state(&state);
+        assert_eq!(report.failed_tasks(), 4);
+        let signatures: Vec<&str> = report.clusters.iter().map(|c| c.signature.as_str()).collect();
+        assert_eq!(signatures, vec!["exit code #", NO_REASON, "disk full on <path>"]);
+
+        let exits = &report.clusters[0];
+        assert_eq!(exits.tasks, vec![2, 1]);
+        assert_eq!(exits.example, "exit code 1");
+        assert_eq!(exits.last_failed, Some(now - Duration::minutes(1)));
+        assert_eq!(exits.backends, BTreeMap::from([("docker".to_string(), 1), ("slurm".to_string(), 1)]));
+    }
+}
@@ -0,0 +1,159 @@
+//! Batch completion forecast.
+//!
+//! Estimates when the remaining Created, Queued and Running tasks will have
+//! finished from the recent completion throughput, with a confidence range
+//! that narrows as more completions are observed.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::state::{AppState, TaskStatus};
+
+/// How far back completions count towards the throughput.
+const THROUGHPUT_WINDOW_MINUTES: i64 = 60;
+
+/// z-score of the two-sided 95% interval.
+const CONFIDENCE_Z: f64 = 1.96;
+
+/// Forecast of when the active tasks will have finished.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BatchForecast {
+    /// Created, Queued and Running tasks
+    pub remaining: usize,
+    /// Tasks finished within the throughput window
+    pub completions: usize,
+    /// Completions per hour within the throughput window
+    pub throughput: f64,
+    /// Expected time until the last task finishes
+    pub eta: Duration,
+    /// Optimistic end of the confidence range
+    pub earliest: Duration,
+    /// Pessimistic end of the confidence range, `None` while too few tasks
+    /// have finished to bound it
+    pub latest: Option<Duration>,
+}
+
+impl BatchForecast {
+    /// Forecast the active tasks in `state`, or `None` if nothing is left
+    /// to run or no task has finished recently.
+    pub fn from_state(state: &AppState, now: DateTime<Utc>) -> Option<Self> {
+        let remaining = state.tasks.values()
+            .filter(|t| matches!(t.status, TaskStatus::Created | TaskStatus::Queued | TaskStatus::Running))
+            .count();
+        if remaining == 0 {
+            return None;
+        }
+
+        // Only count the part of the window the session has been observing
+        let first_seen = state.tasks.values()
+            .filter_map(|t| t.transitions.first().map(|tr| tr.at))
+            .min()?;
+        let window_start = first_seen.max(now - Duration::minutes(THROUGHPUT_WINDOW_MINUTES));
+        let window_hours = (now - window_start).num_milliseconds() as f64 / 3_600_000.0;
+        if window_hours <= 0.0 {
+            return None;
+        }
+
+        let completions = state.tasks.values()
+            .filter_map(|t| t.finished_at())
+            .filter(|at| *at >= window_start && *at <= now)
+            .count();
+        if completions == 0 {
+            return None;
+        }
+
+        // The batch cannot finish before its slowest running task
+        let floor = state.tasks.values()
+            .filter_map(|t| t.eta(now))
+            .max()
+            .unwrap_or_else(Duration::zero);
+
+        // Completions are treated as a Poisson process, so the count's
+        // relative error shrinks with the square root of the count
+        let throughput = completions as f64 / window_hours;
+        let margin = CONFIDENCE_Z * (completions as f64).sqrt();
+        let hours_at = |count: f64| remaining as f64 * window_hours / count;
+
+        Some(Self {
+            remaining,
+            completions,
+            throughput,
+            eta: to_duration(hours_at(completions as f64)).max(floor),
+            earliest: to_duration(hours_at(completions as f64 + margin)).max(floor),
+            latest: (completions as f64 > margin)
+                .then(|| to_duration(hours_at(completions as f64 - margin)).max(floor)),
+        })
+    }
+}
+
+fn to_duration(hours: f64) -> Duration {
+    Duration::milliseconds((hours * 3_600_000.0).round() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    use crate::state::testing::{self, state_with};
+    use crate::state::{StatusTransition, TaskState};
+
+    /// A task first seen 30 minutes before `now`, then moved through
+    /// `steps` as (status, minutes before `now`).
+    fn task(id: u64, now: DateTime<Utc>, steps: &[(TaskStatus, i64)]) -> TaskState {
+        let mut task = testing::task(id, "align");
+        task.transitions = vec![StatusTransition { status: TaskStatus::Created, at: now - Duration::minutes(30) }];
+        for &(status, ago) in steps {
+            task.transition(status, now - Duration::minutes(ago));
+        }
+        task
+    }
+
+    #[test]
+    fn nothing_to_forecast() {
+        let now = Utc::now();
+        assert_eq!(BatchForecast::from_state(&AppState::new(), now), None);
+
+        // Remaining tasks but no completions yet
+        let state = state_with([task(1, now, &[(TaskStatus::Queued, 20)])]);
+        assert_eq!(BatchForecast::from_state(&state, now), None);
+    }
+
+    #[test]
+    fn forecasts_from_recent_throughput() {
+        let now = Utc::now();
+        let state = state_with((1..=6).map(|id| match id {
+            1..=4 => task(id, now, &[(TaskStatus::Completed, 10)]),
+            _ => task(id, now, &[(TaskStatus::Queued, 5)]),
+        }));
+
+        // 4 completions in half an hour leave 2 tasks for a quarter hour
+        let forecast = BatchForecast::from_state(&state, now).unwrap();
+        assert_eq!(forecast.remaining, 2);
+        assert_eq!(forecast.completions, 4);
+        assert_eq!(forecast.throughput, 8.0);
+        assert_eq!(forecast.eta, Duration::minutes(15));
+        assert!(forecast.earliest < forecast.eta);
+        assert!(forecast.latest.is_some_and(|latest| latest > forecast.eta));
+    }
+
+    #[test]
+    fn one_completion_leaves_range_open() {
+        let now = Utc::now();
+        let state = state_with([task(1, now, &[(TaskStatus::Failed, 10)]), task(2, now, &[])]);
+
+        let forecast = BatchForecast::from_state(&state, now).unwrap();
+        assert_eq!(forecast.latest, None);
+    }
+
+    #[test]
+    fn never_before_slowest_running_task() {
+        let now = Utc::now();
+        let mut slow = task(5, now, &[(TaskStatus::Running, 20)]);
+        slow.progress = Some(0.1);
+        let state = state_with((1..=4).map(|id| task(id, now, &[(TaskStatus::Completed, 10)])).chain([slow]));
+
+        let forecast = BatchForecast::from_state(&state, now).unwrap();
+        assert_eq!(forecast.eta, Duration::minutes(180));
+        assert_eq!(forecast.earliest, Duration::minutes(180));
+    }
+}
@@ -6,16 +6,48 @@
 mod task;
 mod backend;
 mod resource;
+mod alert;
+mod timeseries;
+mod units;
+mod efficiency;
+mod stats;
+mod forecast;
+mod straggler;
+mod throughput;
+mod failures;
+mod workflow;
+mod dag;
+#[cfg(test)]
+pub(crate) mod testing;
 
-pub use task::{TaskState, TaskStatus};
-pub use backend::{BackendState, HealthStatus, BackendKind};
+pub use task::{StatusTransition, TaskState, TaskStatus};
+pub use backend::{Allocation, BackendCapacity, BackendState, HealthStatus, BackendKind, TaskActivity, UtilizationMetric};
 pub use resource::ResourceState;
+pub use alert::{Alert, AlertSeverity};
+pub use timeseries::{Bucket, ResourceHistory, Resolution, TimeSeries};
+pub use units::{Bytes, Cores, Percent, Unit};
+pub use efficiency::{EfficiencyReport, NameEfficiency, TaskEfficiency};
+pub use stats::{percentile, DurationStats, Percentiles};
+pub use forecast::BatchForecast;
+pub use straggler::{StallPolicy, StallReason, Straggler, DEFAULT_STALL_AFTER};
+pub use throughput::{RateWindow, Rates, Throughput};
+pub use failures::{failure_reason, signature, FailureCluster, FailureReport};
+pub use workflow::{CallGroup, Rollup, WorkflowGroup, NO_WORKFLOW};
+pub use dag::{Dag, WorkflowDescription};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::rc::Rc;
 use std::cell::RefCell;
+use serde::Serialize;
 use string_interner::{StringInterner, backend::SimpleBackend, DefaultSymbol};
 
+/// Ring sizes for per-task resource history: 2 minutes at 1s, 6 hours at
+/// 1m and 7 days at 1h. Short tasks only allocate the buckets they use.
+const TASK_HISTORY_CAPACITY: (usize, usize, usize) = (120, 360, 168);
+
+/// Number of finished tasks whose resource history is kept.
+const FINISHED_TASK_HISTORY: usize = 200;
+
 /// Temporal state of the application.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Temporality {
@@ -36,14 +68,15 @@ pub struct TaskDetails {
     pub task_id: u64,
     /// Task logs
     pub logs: Vec<String>,
-    /// Resource usage history
-    pub resource_history: Vec<ResourceSample>,
     /// Start time of the task
     pub start_time: chrono::DateTime<chrono::Utc>,
     /// Elapsed time since task started
     pub elapsed: std::time::Duration,
 }
 
+/// A dependency graph and the task set and status revisions it was built at.
+type CachedDag = ((u64, u64), Rc<Dag>);
+
 /// Application state.
 pub struct AppState {
     /// Task state container.
@@ -52,6 +85,11 @@ pub struct AppState {
     pub backends: HashMap<String, BackendState>,
     /// Resource utilization state.
     pub resources: ResourceState,
+    /// Resource history of active and recently finished tasks, CPU in
+    /// cores and memory in bytes.
+    pub task_history: HashMap<u64, ResourceHistory>,
+    /// Finished tasks whose history is still kept, oldest first.
+    finished_history: VecDeque<u64>,
     /// Currently selected task details.
     pub current_task_details: Option<Rc<RefCell<TaskDetails>>>,
     /// Monitoring state.
@@ -70,6 +108,20 @@ pub struct AppState {
     pub terminal_height: u16,
     /// Selected backend name (for UI state)
     pub selected_backend: Option<String>,
+    /// Alerts raised since the last call to `take_alerts`.
+    pub pending_alerts: Vec<Alert>,
+    /// Thresholds for flagging stalled and straggling tasks.
+    pub stall_policy: StallPolicy,
+    /// Tasks started, completed and failed across all backends.
+    pub throughput: Throughput,
+    /// Call dependencies of the monitored workflow, if described.
+    pub workflow_description: WorkflowDescription,
+    /// Bumped whenever a task is added.
+    task_set_revision: u64,
+    /// Bumped whenever a task changes status.
+    status_revision: u64,
+    /// Dependency graph with the revisions it was built at.
+    dag_cache: RefCell<Option<CachedDag>>,
 }
 
 impl AppState {
@@ -79,6 +131,8 @@ impl AppState {
             tasks: HashMap::new(),
             backends: HashMap::new(),
             resources: ResourceState::new(),
+            task_history: HashMap::new(),
+            finished_history: VecDeque::new(),
             current_task_details: None,
             temporality: Temporality::Live,
             strings: StringInterner::new(),
@@ -88,28 +142,91 @@ impl AppState {
             terminal_width: 0,
             terminal_height: 0,
             selected_backend: None,
+            pending_alerts: Vec::new(),
+            stall_policy: StallPolicy::default(),
+            throughput: Throughput::new(),
+            workflow_description: WorkflowDescription::default(),
+            task_set_revision: 0,
+            status_revision: 0,
+            dag_cache: RefCell::new(None),
+        }
+    }
+    
+    /// Builder method to set the stalled task thresholds.
+    pub fn with_stall_policy(mut self, policy: StallPolicy) -> Self {
+        self.stall_policy = policy;
+        self
+    }
+    
+    /// Builder method to set the workflow description that task
+    /// dependencies are derived from.
+    pub fn with_workflow_description(mut self, description: WorkflowDescription) -> Self {
+        self.workflow_description = description;
+        self.task_set_revision += 1;
+        self
+    }
+    
+    /// Dependency graph of the tasks. The layout is rebuilt only when
+    /// tasks are added and the critical path only when a task changes
+    /// status, so views can ask for it on every frame.
+    pub fn dag(&self) -> Rc<Dag> {
+        let revisions = (self.task_set_revision, self.status_revision);
+        let mut cache = self.dag_cache.borrow_mut();
+        match cache.as_mut() {
+            Some((built, _)) if *built == revisions => {}
+            Some((built, dag)) if built.0 == revisions.0 => {
+                Rc::make_mut(dag).update_critical_path(self);
+                *built = revisions;
+            }
+            _ => *cache = Some((revisions, Rc::new(Dag::from_state(self)))),
         }
+        cache.as_ref().map(|(_, dag)| dag.clone()).unwrap_or_default()
     }
     
     /// Updates task states with new data.
     pub fn update_tasks(&mut self, updates: Vec<TaskUpdate>) {
         for update in updates {
             match update {
-                TaskUpdate::Created(task) => {
+                TaskUpdate::Created(mut task) => {
                     // Intern strings to reduce memory usage
                     let _name = self.strings.get_or_intern(&task.name);
                     let _backend = self.strings.get_or_intern(&task.backend);
                     
-                    self.tasks.insert(task.id, task);
+                    // Tasks first seen mid-run only have their current status
+                    if task.transitions.is_empty() {
+                        task.transitions.push(StatusTransition { status: task.status, at: task.start_time });
+                    }
+                    
+                    // Progress reported at creation counts as moving now
+                    if task.progress.is_some() && task.progress_changed_at.is_none() {
+                        task.progress_changed_at = Some(chrono::Utc::now());
+                    }
+                    
+                    self.tasks.insert(task.id, *task);
+                    self.task_set_revision += 1;
                 }
                 TaskUpdate::StatusChanged(id, status) => {
                     if let Some(task) = self.tasks.get_mut(&id) {
-                        task.status = status;
+                        self.status_revision += 1;
+                        let finished = status.is_terminal() && !task.status.is_terminal();
+                        let now = chrono::Utc::now();
+                        if task.transition(status, now) && status == TaskStatus::Running {
+                            self.throughput.record_started(now);
+                        }
+                        if finished {
+                            // Failures reported without an error still raise an alert
+                            if status == TaskStatus::Failed {
+                                self.pending_alerts.push(failure_alert(task, failure_reason(task)));
+                            }
+                            let backend = task.backend.clone();
+                            self.record_finished(&backend, status);
+                            self.retire_task_history(id);
+                        }
                     }
                 }
                 TaskUpdate::Progress(id, progress) => {
                     if let Some(task) = self.tasks.get_mut(&id) {
-                        task.progress = Some(progress);
+                        task.set_progress(progress, chrono::Utc::now());
                     }
                 }
                 TaskUpdate::ResourceUsage(id, usage) => {
@@ -117,29 +234,49 @@ impl AppState {
                         task.cpu_usage = usage.cpu;
                         task.memory_usage = usage.memory;
                         
-                        // Update resource history if this is the selected task
-                        if let Some(details) = &self.current_task_details {
-                            if details.borrow().task_id == id {
-                                details.borrow_mut().resource_history.push(ResourceSample {
-                                    timestamp: chrono::Utc::now(),
-                                    cpu: usage.cpu,
-                                    memory: usage.memory,
-                                });
-                            }
-                        }
+                        // Keep a downsampled history for every task, not just the selected one
+                        let (seconds, minutes, hours) = TASK_HISTORY_CAPACITY;
+                        self.task_history
+                            .entry(id)
+                            .or_insert_with(|| ResourceHistory::with_capacity(seconds, minutes, hours))
+                            .record(chrono::Utc::now(), usage.cpu.0, usage.memory.0 as f32);
                     }
                 }
                 TaskUpdate::Completed(id, result) => {
                     if let Some(task) = self.tasks.get_mut(&id) {
-                        task.status = if result.is_ok() {
-                            TaskStatus::Completed
-                        } else {
-                            TaskStatus::Failed
-                        };
-                        task.end_time = Some(chrono::Utc::now());
+                        self.status_revision += 1;
+                        let finished = !task.status.is_terminal();
+                        let now = chrono::Utc::now();
+                        task.transition(if result.is_ok() { TaskStatus::Completed } else { TaskStatus::Failed }, now);
+                        if finished {
+                            task.end_time = Some(now);
+                        }
+                        
+                        // Only failures need their final log lines
+                        if result.is_ok() {
+                            task.log_tail.clear();
+                        }
+                        
+                        if let Err(error) = result {
+                            if finished {
+                                self.pending_alerts.push(failure_alert(task, &error));
+                            }
+                            task.error = Some(error);
+                        }
+                        
+                        if finished {
+                            let (backend, status) = (task.backend.clone(), task.status);
+                            self.record_finished(&backend, status);
+                            self.retire_task_history(id);
+                        }
                     }
                 }
                 TaskUpdate::Logs(id, log) => {
+                    // Keep the last lines of every active task for failure reports
+                    if let Some(task) = self.tasks.get_mut(&id).filter(|t| t.is_active()) {
+                        task.push_log(log.clone());
+                    }
+                    
                     // Add logs to task details if this is the selected task
                     if let Some(details) = &self.current_task_details {
                         if details.borrow().task_id == id {
@@ -165,23 +302,32 @@ impl AppState {
                             kind: BackendKind::Unknown,
                             running_tasks: 0,
                             total_tasks: 0,
-                            cpu_usage: 0.0,
-                            memory_usage: 0.0,
+                            cpu_usage: Percent::default(),
+                            memory_usage: Percent::default(),
                             health: HealthStatus::Unknown,
-                            resource_history: Vec::new(),  // Add this field
+                            capacity: None,
+                            resource_history: ResourceHistory::new(),
+                            task_activity: TaskActivity::new(),
                             last_update: chrono::Utc::now(),  // Add this field
                         }
                     });
                     
+                    // Raise an alert when the backend's health changes for the worse or recovers
+                    if let Some(alert) = health_alert(&entry.name, entry.health, status.health) {
+                        self.pending_alerts.push(alert);
+                    }
+                    
                     // Update backend state
                     entry.health = status.health;
                     entry.running_tasks = status.running_tasks;
                     entry.total_tasks = status.total_tasks;
+                    entry.task_activity.record_running(chrono::Utc::now(), status.running_tasks);
                 }
                 BackendUpdate::ResourceUsage(name, usage) => {
                     if let Some(backend) = self.backends.get_mut(&name) {
                         backend.cpu_usage = usage.cpu;
                         backend.memory_usage = usage.memory;
+                        backend.resource_history.record(chrono::Utc::now(), usage.cpu.0, usage.memory.0);
                     }
                 }
                 BackendUpdate::Kind(name, kind) => {
@@ -189,6 +335,11 @@ impl AppState {
                         backend.kind = kind;
                     }
                 }
+                BackendUpdate::Capacity(name, capacity) => {
+                    if let Some(backend) = self.backends.get_mut(&name) {
+                        backend.capacity = Some(capacity);
+                    }
+                }
             }
         }
     }
@@ -199,7 +350,6 @@ impl AppState {
             self.current_task_details = Some(Rc::new(RefCell::new(TaskDetails {
                 task_id,
                 logs: Vec::new(),
-                resource_history: Vec::new(),
                 start_time: task.start_time,
                 elapsed: std::time::Duration::from_secs(0),
             })));
@@ -303,6 +453,83 @@ impl AppState {
         self.selected_backend = None;
     }
     
+    /// Resources requested and used by the running tasks of a backend.
+    pub fn backend_allocation(&self, backend: &str) -> Allocation {
+        let mut allocation = Allocation::default();
+        for task in self.tasks.values() {
+            if task.backend != backend || task.status != TaskStatus::Running {
+                continue;
+            }
+            allocation.running += 1;
+            allocation.requested_cpu += task.requested_cpu.unwrap_or_default();
+            allocation.requested_memory += task.requested_memory.unwrap_or_default();
+            allocation.used_cpu += task.cpu_usage;
+            allocation.used_memory += task.memory_usage;
+        }
+        allocation
+    }
+    
+    /// Queue wait percentiles of a backend's tasks that have started
+    /// running, in seconds.
+    pub fn queue_wait_percentiles(&self, backend: &str) -> Option<Percentiles> {
+        Percentiles::of(
+            self.tasks.values()
+                .filter(|t| t.backend == backend && t.entered(TaskStatus::Running).is_some())
+                .filter_map(|t| t.queue_wait())
+                .map(|wait| wait.num_milliseconds() as f64 / 1000.0),
+        )
+    }
+    
+    /// Run time percentiles of a backend's finished tasks, in seconds.
+    pub fn run_time_percentiles(&self, backend: &str) -> Option<Percentiles> {
+        Percentiles::of(
+            self.tasks.values()
+                .filter(|t| t.backend == backend && t.status.is_terminal())
+                .filter_map(|t| t.run_time())
+                .map(|run| run.num_milliseconds() as f64 / 1000.0),
+        )
+    }
+    
+    /// Counts a finished task towards the throughput and its backend's
+    /// task activity.
+    fn record_finished(&mut self, backend: &str, status: TaskStatus) {
+        let now = chrono::Utc::now();
+        self.throughput.record_finished(now, status);
+        if let Some(backend) = self.backends.get_mut(backend) {
+            backend.task_activity.record_finished(now, status);
+        }
+    }
+    
+    /// Recomputes the cluster resource totals from backends and running tasks.
+    pub fn refresh_resources(&mut self) {
+        self.resources.aggregate(self.backends.values(), self.tasks.values());
+    }
+    
+    /// Resource history of a task, if it is active or finished recently.
+    pub fn task_history(&self, task_id: u64) -> Option<&ResourceHistory> {
+        self.task_history.get(&task_id)
+    }
+    
+    /// Marks a task's history as finished, dropping the oldest finished
+    /// histories beyond the retention limit.
+    fn retire_task_history(&mut self, task_id: u64) {
+        if !self.task_history.contains_key(&task_id) {
+            return;
+        }
+        
+        self.finished_history.push_back(task_id);
+        while self.finished_history.len() > FINISHED_TASK_HISTORY {
+            if let Some(old) = self.finished_history.pop_front() {
+                self.task_history.remove(&old);
+            }
+        }
+    }
+    
+    /// Takes all alerts raised since the last call.
+    pub fn take_alerts(&mut self) -> Vec<Alert> {
+        std::mem::take(&mut self.pending_alerts)
+    }
+    
     /// Returns the count of currently active tasks
     pub fn active_task_count(&self) -> usize {
         self.tasks
@@ -319,17 +546,59 @@ impl AppState {
     
 }
 
+/// Builds the alert for a task that failed with `error`. Shards failing the
+/// same way on one backend share a source, so a failed scatter raises one
+/// notification.
+fn failure_alert(task: &TaskState, error: &str) -> Alert {
+    Alert::new(
+        AlertSeverity::Warning,
+        format!("task-failure:{}:{}", task.backend, signature(error)),
+        "Task failed",
+        format!("Task '{}' (ID {}) failed on {}: {}", task.name, task.id, task.backend, error),
+    )
+}
+
+/// Builds the alert for a backend health transition, if it warrants one.
+fn health_alert(name: &str, old: HealthStatus, new: HealthStatus) -> Option<Alert> {
+    if old == new {
+        return None;
+    }
+    
+    let source = format!("backend:{}", name);
+    match new {
+        HealthStatus::Degraded => Some(Alert::new(
+            AlertSeverity::Warning,
+            source,
+            "Backend degraded",
+            format!("Backend '{}' changed from {} to {}", name, old, new),
+        )),
+        HealthStatus::Unhealthy => Some(Alert::new(
+            AlertSeverity::Critical,
+            source,
+            "Backend unhealthy",
+            format!("Backend '{}' changed from {} to {}", name, old, new),
+        )),
+        HealthStatus::Healthy if matches!(old, HealthStatus::Degraded | HealthStatus::Unhealthy) => Some(Alert::new(
+            AlertSeverity::Info,
+            source,
+            "Backend recovered",
+            format!("Backend '{}' changed from {} to {}", name, old, new),
+        )),
+        _ => None,
+    }
+}
+
 /// Resource sample for historical tracking.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct ResourceSample {
     pub timestamp: chrono::DateTime<chrono::Utc>,
-    pub cpu: f32,
-    pub memory: f32,
+    pub cpu: Cores,
+    pub memory: Bytes,
 }
 
 /// Task status update.
 pub enum TaskUpdate {
-    Created(TaskState),
+    Created(Box<TaskState>),
     StatusChanged(u64, TaskStatus),
     Progress(u64, f32),
     ResourceUsage(u64, ResourceUsage),
@@ -340,15 +609,23 @@ pub enum TaskUpdate {
 /// Backend status update.
 pub enum BackendUpdate {
     Status(String, BackendStatus),
-    ResourceUsage(String, ResourceUsage),
+    ResourceUsage(String, CapacityUsage),
     Kind(String, BackendKind),
+    Capacity(String, BackendCapacity),
 }
 
-/// Resource usage information.
+/// Resource usage of a task.
 #[derive(Debug, Clone, Copy)]
 pub struct ResourceUsage {
-    pub cpu: f32,
-    pub memory: f32,
+    pub cpu: Cores,
+    pub memory: Bytes,
+}
+
+/// Resource usage of a backend, relative to its capacity.
+#[derive(Debug, Clone, Copy)]
+pub struct CapacityUsage {
+    pub cpu: Percent,
+    pub memory: Percent,
 }
 
 /// Backend status information.
@@ -359,77 +636,167 @@ pub struct BackendStatus {
     pub health: HealthStatus,
 }
 
-/// Conversion from monitor TaskUpdate to state TaskUpdate
-impl From<crate::monitor::task::TaskUpdate> for TaskUpdate {
+/// Conversion from monitor TaskUpdate to state TaskUpdates
+impl From<crate::monitor::task::TaskUpdate> for Vec<TaskUpdate> {
     fn from(update: crate::monitor::task::TaskUpdate) -> Self {
-        // First, check for log updates
+        let mut updates = Vec::new();
+        
+        // Log and resource usage updates carry no task snapshot
         if let Some((id, message)) = update.logs {
-            return TaskUpdate::Logs(id, message);
+            updates.push(TaskUpdate::Logs(id, message));
         }
         
-        // Then check resource usage updates
         if let Some((id, sample)) = update.resource_usage {
-            return TaskUpdate::ResourceUsage(id, ResourceUsage {
+            updates.push(TaskUpdate::ResourceUsage(id, ResourceUsage {
                 cpu: sample.cpu,
                 memory: sample.memory,
-            });
+            }));
         }
         
-        // Check for new tasks
-        if !update.new_tasks.is_empty() {
-            let task_id = update.new_tasks[0];
-            if let Some(task) = update.tasks.get(&task_id) {
-                return TaskUpdate::Created(task.clone());
+        // New tasks
+        for task_id in &update.new_tasks {
+            if let Some(task) = update.tasks.get(task_id) {
+                updates.push(TaskUpdate::Created(Box::new(task.clone())));
             }
         }
         
-        // Check for completed tasks
-        if !update.completed_tasks.is_empty() {
-            let task_id = update.completed_tasks[0];
-            return TaskUpdate::Completed(task_id, Ok(()));
-        }
-        
-        // Finally, check for status updates
-        if !update.updated_tasks.is_empty() {
-            let task_id = update.updated_tasks[0];
-            if let Some(task) = update.tasks.get(&task_id) {
-                return TaskUpdate::StatusChanged(task_id, task.status);
+        // Status and progress changes. Completed tasks take their final
+        // status from `Completed`, which carries the error of a failure.
+        for task_id in &update.updated_tasks {
+            if let Some(task) = update.tasks.get(task_id) {
+                if !update.completed_tasks.contains(task_id) {
+                    updates.push(TaskUpdate::StatusChanged(*task_id, task.status));
+                }
+                if let Some(progress) = task.progress {
+                    updates.push(TaskUpdate::Progress(*task_id, progress));
+                }
             }
         }
         
-        // Default fallback - create a dummy status update for the first task
-        if let Some((&id, _)) = update.tasks.iter().next() {
-            TaskUpdate::StatusChanged(id, TaskStatus::Running)
-        } else {
-            TaskUpdate::StatusChanged(0, TaskStatus::Created) // Empty update
+        // Completed tasks, keeping failures distinct from successes
+        for task_id in &update.completed_tasks {
+            let result = match update.tasks.get(task_id) {
+                Some(task) if task.status == TaskStatus::Failed => {
+                    Err(task.error.clone().unwrap_or_else(|| "task failed".to_string()))
+                }
+                _ => Ok(()),
+            };
+            updates.push(TaskUpdate::Completed(*task_id, result));
         }
+        
+        updates
     }
 }
 
-/// Conversion from monitor BackendUpdate to state BackendUpdate 
-impl From<crate::monitor::backend::BackendUpdate> for BackendUpdate {
+/// Conversion from monitor BackendUpdate to state BackendUpdates
+impl From<crate::monitor::backend::BackendUpdate> for Vec<BackendUpdate> {
     fn from(update: crate::monitor::backend::BackendUpdate) -> Self {
-        // Take the first backend from the update
-        if let Some((backend_name, backend_state)) = update.backends.iter().next() {
-            // Create a status update for this backend
-            return BackendUpdate::Status(
-                backend_name.clone(), 
+        let mut updates = Vec::with_capacity(update.backends.len() * 3);
+        
+        for (backend_name, backend_state) in update.backends {
+            updates.push(BackendUpdate::Status(
+                backend_name.clone(),
                 BackendStatus {
                     running_tasks: backend_state.running_tasks,
                     total_tasks: backend_state.total_tasks,
                     health: backend_state.health,
                 }
-            );
+            ));
+            updates.push(BackendUpdate::Kind(backend_name.clone(), backend_state.kind));
+            if let Some(capacity) = backend_state.capacity {
+                updates.push(BackendUpdate::Capacity(backend_name.clone(), capacity));
+            }
+            updates.push(BackendUpdate::ResourceUsage(
+                backend_name,
+                CapacityUsage {
+                    cpu: backend_state.cpu_usage,
+                    memory: backend_state.memory_usage,
+                }
+            ));
         }
         
-        // Empty update as fallback
-        BackendUpdate::Status(
-            "unknown".to_string(),
-            BackendStatus {
-                running_tasks: 0,
-                total_tasks: 0,
-                health: HealthStatus::Unknown,
-            }
-        )
+        updates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn created(id: u64) -> TaskUpdate {
+        TaskUpdate::Created(Box::new(testing::task(id, "align")))
+    }
+
+    #[test]
+    fn shards_failing_alike_share_an_alert_source() {
+        let mut state = AppState::new();
+        state.update_tasks(vec![
+            created(1),
+            created(2),
+            TaskUpdate::Completed(1, Err("exit code 137 in /work/1/run.sh".into())),
+            TaskUpdate::Completed(2, Err("exit code 137 in /work/2/run.sh".into())),
+        ]);
+
+        let alerts = state.take_alerts();
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].source, alerts[1].source);
+        assert!(alerts[0].source.starts_with("task-failure:docker:"));
+    }
+
+    #[test]
+    fn failure_alert_only_on_transition() {
+        let mut state = AppState::new();
+        state.update_tasks(vec![created(1), TaskUpdate::Completed(1, Err("boom".into()))]);
+        assert_eq!(state.take_alerts().len(), 1);
+
+        state.update_tasks(vec![TaskUpdate::Completed(1, Err("boom".into()))]);
+        assert!(state.take_alerts().is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn monitor_failure_raises_an_alert() {
+        let mut failed = testing::task(1, "align");
+        failed.transition(TaskStatus::Failed, chrono::Utc::now());
+        failed.error = Some("exit code 137".into());
+        let update = crate::monitor::task::TaskUpdate {
+            tasks: HashMap::from([(1, failed)]),
+            timestamp: chrono::Utc::now(),
+            new_tasks: Vec::new(),
+            updated_tasks: vec![1],
+            completed_tasks: vec![1],
+            resource_usage: None,
+            logs: None,
+        };
+
+        let mut state = AppState::new();
+        state.update_tasks(vec![created(1)]);
+        state.update_tasks(Vec::<TaskUpdate>::from(update));
+
+        let alerts = state.take_alerts();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].source, "task-failure:docker:exit code #");
+        let task = &state.tasks[&1];
+        assert_eq!((task.status, task.error.as_deref()), (TaskStatus::Failed, Some("exit code 137")));
+    }
+
+    #[test]
+    fn status_change_to_failed_raises_an_alert() {
+        let mut state = AppState::new();
+        state.update_tasks(vec![created(1), TaskUpdate::StatusChanged(1, TaskStatus::Failed)]);
+        state.update_tasks(vec![TaskUpdate::Completed(1, Err("boom".into()))]);
+
+        assert_eq!(state.take_alerts().len(), 1);
+    }
+
+    #[test]
+    fn repeated_completion_keeps_end_time() {
+        let mut state = AppState::new();
+        state.update_tasks(vec![created(1), TaskUpdate::Completed(1, Ok(()))]);
+        let end_time = state.tasks[&1].end_time;
+        assert!(end_time.is_some());
+
+        state.update_tasks(vec![TaskUpdate::Completed(1, Ok(()))]);
+        assert_eq!(state.tasks[&1].end_time, end_time);
+    }
+}
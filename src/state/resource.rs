@@ -2,78 +2,149 @@
 //!
 //! Tracks resource utilization across tasks and backends.
 
-use std::collections::VecDeque;
 use chrono::{DateTime, Utc};
 
-const HISTORY_SIZE: usize = 100; // Keep 100 samples max
+use crate::state::timeseries::ResourceHistory;
+use crate::state::{BackendState, Bytes, Cores, Percent, TaskState, TaskStatus};
 
 /// Resource utilization state.
 pub struct ResourceState {
-    pub cpu_history: VecDeque<ResourcePoint>,
-    pub memory_history: VecDeque<ResourcePoint>,
-    pub cpu_current: f32,
-    pub memory_current: f32,
-}
-
-/// A single resource utilization data point.
-#[derive(Debug, Clone, Copy)]
-pub struct ResourcePoint {
-    pub timestamp: DateTime<Utc>,
-    pub value: f32,
+    /// Cluster CPU and memory utilization (%), weighted by backend capacity
+    pub history: ResourceHistory,
+    /// Summed CPU (cores) and memory (bytes) of running tasks
+    pub task_history: ResourceHistory,
+    pub cpu_current: Percent,
+    pub memory_current: Percent,
+    pub task_cpu_current: Cores,
+    pub task_memory_current: Bytes,
+    pub running_tasks: usize,
 }
 
 impl ResourceState {
     pub fn new() -> Self {
         Self {
-            cpu_history: VecDeque::with_capacity(HISTORY_SIZE),
-            memory_history: VecDeque::with_capacity(HISTORY_SIZE),
-            cpu_current: 0.0,
-            memory_current: 0.0,
+            history: ResourceHistory::new(),
+            task_history: ResourceHistory::new(),
+            cpu_current: Percent::default(),
+            memory_current: Percent::default(),
+            task_cpu_current: Cores::default(),
+            task_memory_current: Bytes::default(),
+            running_tasks: 0,
         }
     }
-    
-    pub fn update(&mut self, cpu: f32, memory: f32) {
-        let now = Utc::now();
-        
+
+    pub fn update(&mut self, cpu: Percent, memory: Percent) {
+        self.update_at(Utc::now(), cpu, memory);
+    }
+
+    pub fn update_at(&mut self, timestamp: DateTime<Utc>, cpu: Percent, memory: Percent) {
         // Update current values
         self.cpu_current = cpu;
         self.memory_current = memory;
-        
+
         // Add to history
-        self.add_cpu_point(now, cpu);
-        self.add_memory_point(now, memory);
+        self.history.record(timestamp, cpu.0, memory.0);
     }
-    
-    fn add_cpu_point(&mut self, timestamp: DateTime<Utc>, value: f32) {
-        self.cpu_history.push_back(ResourcePoint { timestamp, value });
-        if self.cpu_history.len() > HISTORY_SIZE {
-            self.cpu_history.pop_front();
+
+    /// Recompute the cluster totals from backends and running tasks and
+    /// record them.
+    pub fn aggregate<'a>(
+        &mut self,
+        backends: impl Iterator<Item = &'a BackendState>,
+        tasks: impl Iterator<Item = &'a TaskState>,
+    ) {
+        let now = Utc::now();
+
+        // Backend utilization weighted by capacity, so a busy small backend
+        // does not dominate an idle large one
+        let (mut cpu, mut memory, mut weight) = (0.0, 0.0, 0.0);
+        for backend in backends {
+            let w = backend.capacity_weight();
+            cpu += backend.cpu_usage.0 * w;
+            memory += backend.memory_usage.0 * w;
+            weight += w;
         }
-    }
-    
-    fn add_memory_point(&mut self, timestamp: DateTime<Utc>, value: f32) {
-        self.memory_history.push_back(ResourcePoint { timestamp, value });
-        if self.memory_history.len() > HISTORY_SIZE {
-            self.memory_history.pop_front();
+        if weight > 0.0 {
+            self.update_at(now, Percent::new(cpu / weight), Percent::new(memory / weight));
+        }
+
+        // Usage summed over running tasks
+        let (mut task_cpu, mut task_memory, mut running) = (Cores::default(), Bytes::default(), 0);
+        for task in tasks.filter(|t| t.status == TaskStatus::Running) {
+            task_cpu += task.cpu_usage;
+            task_memory += task.memory_usage;
+            running += 1;
         }
+        self.task_cpu_current = task_cpu;
+        self.task_memory_current = task_memory;
+        self.running_tasks = running;
+        self.task_history.record(now, task_cpu.0, task_memory.0 as f32);
     }
-    
-    pub fn cpu_max(&self) -> f32 {
-        self.cpu_history
-            .iter()
-            .map(|p| p.value)
-            .fold(0.0, f32::max)
+
+    pub fn cpu_max(&self) -> Percent {
+        Percent(self.history.cpu.peak().unwrap_or(0.0))
     }
-    
-    pub fn memory_max(&self) -> f32 {
-        self.memory_history
-            .iter()
-            .map(|p| p.value)
-            .fold(0.0, f32::max)
+
+    pub fn memory_max(&self) -> Percent {
+        Percent(self.history.memory.peak().unwrap_or(0.0))
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    use crate::state::testing;
+    use crate::state::{BackendCapacity, BackendKind};
+
+    fn backend(name: &str, cores: f32, cpu: f32, memory: f32) -> BackendState {
+        let mut backend = BackendState::new(name.into(), BackendKind::Docker)
+            .with_capacity(BackendCapacity { cores: Cores(cores), memory: Bytes::default(), slots: 0 });
+        backend.cpu_usage = Percent(cpu);
+        backend.memory_usage = Percent(memory);
+        backend
+    }
+
+    fn task(id: u64, status: TaskStatus, cpu: f32, memory_mib: f64) -> TaskState {
+        let mut task = testing::task(id, "align");
+        task.transition(status, Utc::now());
+        task.cpu_usage = Cores(cpu);
+        task.memory_usage = Bytes::from_mib(memory_mib);
+        task
+    }
+
+    #[test]
+    fn aggregate_weights_backends_by_capacity() {
+        // Three cores against a backend without capacity, which counts as one
+        let backends = [backend("large", 3.0, 50.0, 25.0), backend("empty", 0.0, 100.0, 100.0)];
+        let tasks = [
+            task(1, TaskStatus::Running, 1.5, 512.0),
+            task(2, TaskStatus::Running, 0.25, 256.0),
+            task(3, TaskStatus::Completed, 4.0, 1024.0),
+        ];
 
+        let mut state = ResourceState::new();
+        state.aggregate(backends.iter(), tasks.iter());
 
+        assert_eq!(state.cpu_current, Percent(62.5));
+        assert_eq!(state.memory_current, Percent(43.75));
+        assert_eq!(state.history.cpu.latest(), Some(62.5));
+        assert_eq!(state.task_cpu_current, Cores(1.75));
+        assert_eq!(state.task_memory_current, Bytes::from_mib(768.0));
+        assert_eq!(state.running_tasks, 2);
+        assert_eq!(state.task_history.cpu.latest(), Some(1.75));
+    }
 
+    #[test]
+    fn aggregate_without_backends_keeps_utilization() {
+        let mut state = ResourceState::new();
+        state.update(Percent(40.0), Percent(20.0));
+        state.aggregate(std::iter::empty(), std::iter::empty());
 
+        assert_eq!(state.cpu_current, Percent(40.0));
+        assert_eq!(state.memory_current, Percent(20.0));
+        assert_eq!(state.task_cpu_current, Cores(0.0));
+        assert_eq!(state.running_tasks, 0);
+    }
+}
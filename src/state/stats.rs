@@ -0,0 +1,165 @@
+//! Summary statistics over samples.
+
+use std::collections::BTreeMap;
+
+use crate::state::{AppState, Percent, TaskStatus};
+
+/// Value at percentile `p` (0-100) of sorted samples, interpolating
+/// linearly between the closest ranks.
+pub fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = (p.clamp(0.0, 100.0) / 100.0) * last as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let fraction = rank - lower as f64;
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * fraction)
+}
+
+/// Median and tail percentiles of a set of samples.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Percentiles {
+    pub count: usize,
+    pub p50: f64,
+    pub p90: f64,
+    pub p99: f64,
+}
+
+impl Percentiles {
+    /// Percentiles of the samples, or `None` if there are none.
+    pub fn of(values: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut sorted: Vec<f64> = values.into_iter().filter(|v| v.is_finite()).collect();
+        sorted.sort_by(f64::total_cmp);
+        Some(Self {
+            count: sorted.len(),
+            p50: percentile(&sorted, 50.0)?,
+            p90: percentile(&sorted, 90.0)?,
+            p99: percentile(&sorted, 99.0)?,
+        })
+    }
+}
+
+/// Duration statistics of finished tasks sharing a base name.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DurationStats {
+    /// Base name of the group (see `TaskState::base_name`)
+    pub name: String,
+    /// Finished tasks in the group
+    pub count: usize,
+    /// Failed tasks in the group
+    pub failed: usize,
+    pub mean: f64,
+    pub p50: f64,
+    pub p95: f64,
+    pub p99: f64,
+    pub max: f64,
+    /// Durations in seconds, sorted
+    pub durations: Vec<f64>,
+}
+
+impl DurationStats {
+    /// Statistics for every base name with finished tasks, sorted by name.
+    ///
+    /// Durations are run times, or time since creation for tasks that never
+    /// started running.
+    pub fn by_name(state: &AppState) -> Vec<Self> {
+        let mut groups: BTreeMap<&str, (Vec<f64>, usize)> = BTreeMap::new();
+        for task in state.tasks.values().filter(|t| t.status.is_terminal()) {
+            let duration = task.run_time().unwrap_or_else(|| task.total_time());
+            let (durations, failed) = groups.entry(task.base_name()).or_default();
+            durations.push(duration.num_milliseconds() as f64 / 1000.0);
+            if task.status == TaskStatus::Failed {
+                *failed += 1;
+            }
+        }
+
+        groups.into_iter()
+            .filter_map(|(name, (mut durations, failed))| {
+                durations.sort_by(f64::total_cmp);
+                let count = durations.len();
+                Some(Self {
+                    name: name.to_string(),
+                    count,
+                    failed,
+                    mean: durations.iter().sum::<f64>() / count as f64,
+                    p50: percentile(&durations, 50.0)?,
+                    p95: percentile(&durations, 95.0)?,
+                    p99: percentile(&durations, 99.0)?,
+                    max: *durations.last()?,
+                    durations,
+                })
+            })
+            .collect()
+    }
+
+    /// Share of the group's finished tasks that failed.
+    pub fn failure_rate(&self) -> Percent {
+        Percent::of(self.failed as f64, self.count as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    use crate::state::testing::{self, state_with};
+
+    #[test]
+    fn percentile_of_nothing() {
+        assert_eq!(percentile(&[], 50.0), None);
+        assert_eq!(Percentiles::of([f64::NAN]), None);
+    }
+
+    #[rstest]
+    #[case(0.0)]
+    #[case(50.0)]
+    #[case(100.0)]
+    fn percentile_of_single_sample(#[case] p: f64) {
+        assert_eq!(percentile(&[7.0], p), Some(7.0));
+    }
+
+    #[rstest]
+    #[case(0.0, 10.0)]
+    #[case(25.0, 20.0)]
+    #[case(50.0, 30.0)]
+    #[case(62.5, 35.0)]
+    #[case(100.0, 50.0)]
+    #[case(150.0, 50.0)]
+    #[case(-10.0, 10.0)]
+    fn percentile_interpolates_between_ranks(#[case] p: f64, #[case] expected: f64) {
+        assert_eq!(percentile(&[10.0, 20.0, 30.0, 40.0, 50.0], p), Some(expected));
+    }
+
+    #[test]
+    fn percentiles_sort_and_skip_non_finite() {
+        let stats = Percentiles::of([3.0, f64::INFINITY, 1.0, 2.0]).unwrap();
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.p50, 2.0);
+    }
+
+    #[test]
+    fn duration_stats_group_by_base_name() {
+        let start = Utc::now() - Duration::hours(1);
+        let finished = |id: u64, secs: i64, status: TaskStatus| {
+            let mut task = testing::task(id, "align");
+            task.transition(TaskStatus::Running, start);
+            task.transition(status, start + Duration::seconds(secs));
+            task
+        };
+        let state = state_with([
+            finished(1, 10, TaskStatus::Completed),
+            finished(2, 30, TaskStatus::Failed),
+            testing::task(3, "align"),
+        ]);
+
+        let stats = DurationStats::by_name(&state);
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].name, "align");
+        assert_eq!(stats[0].count, 2);
+        assert_eq!(stats[0].durations, vec![10.0, 30.0]);
+        assert_eq!(stats[0].mean, 20.0);
+        assert_eq!(stats[0].max, 30.0);
+        assert_eq!(stats[0].failure_rate(), Percent::new(50.0));
+    }
+}
@@ -0,0 +1,248 @@
+//! Stalled and straggler task detection.
+//!
+//! A running task is flagged when its progress has not moved for a while,
+//! when its CPU use has fallen to almost nothing, or when it has been running
+//! far longer than finished tasks with the same base name usually take.
+
+use std::collections::HashMap;
+use chrono::{DateTime, Duration, Utc};
+
+use crate::state::{percentile, AppState, Cores, TaskState, TaskStatus};
+
+/// Default time a running task's progress may stay unchanged.
+pub const DEFAULT_STALL_AFTER: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Thresholds for flagging running tasks.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StallPolicy {
+    /// How long progress may stay unchanged
+    pub stall_after: Duration,
+    /// Mean CPU below which a task counts as idle
+    pub idle_cpu: Cores,
+    /// Window the idle CPU mean is taken over
+    pub idle_window: Duration,
+    /// Multiple of the p95 run time of same-named tasks beyond which a task
+    /// is a straggler
+    pub runtime_factor: f64,
+    /// Finished same-named tasks needed before run times are compared
+    pub min_peers: usize,
+}
+
+impl Default for StallPolicy {
+    fn default() -> Self {
+        Self {
+            stall_after: Duration::from_std(DEFAULT_STALL_AFTER).unwrap_or_else(|_| Duration::minutes(10)),
+            idle_cpu: Cores::from_millicores(20),
+            idle_window: Duration::minutes(2),
+            runtime_factor: 2.0,
+            min_peers: 5,
+        }
+    }
+}
+
+impl StallPolicy {
+    /// Policy with the given progress timeout and default thresholds.
+    pub fn with_stall_after(mut self, stall_after: std::time::Duration) -> Self {
+        if let Ok(stall_after) = Duration::from_std(stall_after) {
+            self.stall_after = stall_after;
+        }
+        self
+    }
+}
+
+/// Why a running task was flagged.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StallReason {
+    /// Progress has not moved for this long
+    NoProgress(Duration),
+    /// Mean CPU over the idle window
+    IdleCpu(Cores),
+    /// Run time far beyond the p95 of finished same-named tasks
+    SlowRuntime { run_time: Duration, p95: Duration },
+}
+
+/// A running task flagged as stalled or straggling.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Straggler {
+    pub task_id: u64,
+    pub name: String,
+    pub backend: String,
+    /// Time spent running so far
+    pub run_time: Duration,
+    pub reasons: Vec<StallReason>,
+}
+
+impl Straggler {
+    /// Flag the running tasks in `state`, longest running first.
+    pub fn find(state: &AppState, now: DateTime<Utc>) -> Vec<Self> {
+        let policy = &state.stall_policy;
+        let baselines = run_time_p95_by_name(state, policy.min_peers);
+
+        let mut stragglers: Vec<Self> = state.tasks.values()
+            .filter(|task| task.status == TaskStatus::Running)
+            .filter_map(|task| {
+                let reasons = stall_reasons(task, state, policy, &baselines, now);
+                (!reasons.is_empty()).then(|| Self {
+                    task_id: task.id,
+                    name: task.name.clone(),
+                    backend: task.backend.clone(),
+                    run_time: task.run_time().unwrap_or_else(Duration::zero),
+                    reasons,
+                })
+            })
+            .collect();
+
+        stragglers.sort_by(|a, b| b.run_time.cmp(&a.run_time).then(a.task_id.cmp(&b.task_id)));
+        stragglers
+    }
+}
+
+/// Check one running task against the policy.
+fn stall_reasons(
+    task: &TaskState,
+    state: &AppState,
+    policy: &StallPolicy,
+    baselines: &HashMap<&str, Duration>,
+    now: DateTime<Utc>,
+) -> Vec<StallReason> {
+    let mut reasons = Vec::new();
+
+    if let Some(idle) = task.progress_idle(now).filter(|idle| *idle >= policy.stall_after) {
+        reasons.push(StallReason::NoProgress(idle));
+    }
+
+    // Only judge CPU once the task has been running for a full window
+    let run_time = task.run_time().unwrap_or_else(Duration::zero);
+    if run_time >= policy.idle_window {
+        let mean = state.task_history.get(&task.id)
+            .and_then(|history| history.cpu.mean_ending(now, policy.idle_window));
+        if let Some(mean) = mean.map(Cores).filter(|mean| *mean < policy.idle_cpu) {
+            reasons.push(StallReason::IdleCpu(mean));
+        }
+    }
+
+    if let Some(p95) = baselines.get(task.base_name()) {
+        let limit = p95.num_milliseconds() as f64 * policy.runtime_factor;
+        if run_time.num_milliseconds() as f64 > limit {
+            reasons.push(StallReason::SlowRuntime { run_time, p95: *p95 });
+        }
+    }
+
+    reasons
+}
+
+/// p95 run time of completed tasks per base name, for names with at least
+/// `min_peers` completions.
+fn run_time_p95_by_name(state: &AppState, min_peers: usize) -> HashMap<&str, Duration> {
+    let mut run_times: HashMap<&str, Vec<f64>> = HashMap::new();
+    for task in state.tasks.values().filter(|t| t.status == TaskStatus::Completed) {
+        if let Some(run_time) = task.run_time() {
+            run_times.entry(task.base_name())
+                .or_default()
+                .push(run_time.num_milliseconds() as f64);
+        }
+    }
+
+    run_times.into_iter()
+        .filter(|(_, samples)| samples.len() >= min_peers.max(1))
+        .filter_map(|(name, mut samples)| {
+            samples.sort_by(f64::total_cmp);
+            percentile(&samples, 95.0).map(|p95| (name, Duration::milliseconds(p95 as i64)))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    use crate::state::testing::{self, state_with};
+    use crate::state::ResourceHistory;
+
+    /// A task with the given name that started running `minutes` ago.
+    fn running(id: u64, name: &str, minutes: i64, now: DateTime<Utc>) -> TaskState {
+        let mut task = testing::task(id, name);
+        task.transition(TaskStatus::Running, now - Duration::minutes(minutes));
+        task
+    }
+
+    #[test]
+    fn nothing_to_flag() {
+        let now = Utc::now();
+        assert!(Straggler::find(&AppState::new(), now).is_empty());
+
+        let mut task = running(1, "align", 5, now);
+        task.set_progress(0.5, now - Duration::minutes(1));
+        assert!(Straggler::find(&state_with(vec![task]), now).is_empty());
+    }
+
+    #[test]
+    fn flags_progress_unchanged_past_timeout() {
+        let now = Utc::now();
+        let mut task = running(1, "align", 30, now);
+        task.set_progress(0.5, now - Duration::minutes(12));
+
+        let stragglers = Straggler::find(&state_with(vec![task]), now);
+        assert_eq!(stragglers.len(), 1);
+        assert_eq!(stragglers[0].reasons, vec![StallReason::NoProgress(Duration::minutes(12))]);
+    }
+
+    #[test]
+    fn no_progress_counts_from_start_of_run() {
+        let now = Utc::now();
+        let task = running(1, "align", 11, now);
+
+        let stragglers = Straggler::find(&state_with(vec![task]), now);
+        assert_eq!(stragglers[0].reasons, vec![StallReason::NoProgress(Duration::minutes(11))]);
+    }
+
+    #[test]
+    fn flags_idle_cpu_after_full_window() {
+        let now = Utc::now();
+        let mut task = running(1, "align", 3, now);
+        task.set_progress(0.5, now);
+        let mut state = state_with(vec![task]);
+        let mut history = ResourceHistory::new();
+        history.record(now - Duration::seconds(30), 0.01, 0.0);
+        state.task_history.insert(1, history);
+
+        let stragglers = Straggler::find(&state, now);
+        assert_eq!(stragglers[0].reasons, vec![StallReason::IdleCpu(Cores(0.01))]);
+    }
+
+    #[test]
+    fn flags_slow_runtime_once_enough_peers_finished() {
+        let now = Utc::now();
+        let finished = |id: u64| {
+            let mut task = running(id, "align", 60, now);
+            task.transition(TaskStatus::Completed, now - Duration::minutes(59));
+            task
+        };
+        let mut slow = running(10, "align", 5, now);
+        slow.set_progress(0.5, now);
+
+        // Four peers are not enough to judge by
+        let mut tasks: Vec<TaskState> = (1..=4).map(finished).collect();
+        tasks.push(slow.clone());
+        assert!(Straggler::find(&state_with(tasks.clone()), now).is_empty());
+
+        tasks.push(finished(5));
+        let stragglers = Straggler::find(&state_with(tasks), now);
+        assert_eq!(stragglers.len(), 1);
+        assert_eq!(stragglers[0].task_id, 10);
+        assert!(matches!(
+            stragglers[0].reasons[..],
+            [StallReason::SlowRuntime { p95, .. }] if p95 == Duration::minutes(1)
+        ));
+    }
+
+    #[test]
+    fn longest_running_first() {
+        let now = Utc::now();
+        let tasks = vec![running(1, "a", 15, now), running(2, "b", 40, now), running(3, "c", 20, now)];
+
+        let ids: Vec<u64> = Straggler::find(&state_with(tasks), now).iter().map(|s| s.task_id).collect();
+        assert_eq!(ids, vec![2, 3, 1]);
+    }
+}
@@ -2,10 +2,16 @@
 //!
 //! Manages the state of tasks running in the Crankshaft engine.
 
+use std::collections::VecDeque;
 use chrono::{DateTime, Utc};
+use serde::Serialize;
+use crate::state::units::{Bytes, Cores};
+
+/// Number of recent log lines kept per task.
+pub const LOG_TAIL_LINES: usize = 5;
 
 /// Task status.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
 pub enum TaskStatus {
     Created,
     Queued,
@@ -16,6 +22,16 @@ pub enum TaskStatus {
 }
 
 impl TaskStatus {
+    /// All task statuses, in display order.
+    pub const ALL: [TaskStatus; 6] = [
+        TaskStatus::Created,
+        TaskStatus::Queued,
+        TaskStatus::Running,
+        TaskStatus::Completed,
+        TaskStatus::Failed,
+        TaskStatus::Cancelled,
+    ];
+    
     pub fn is_terminal(&self) -> bool {
         matches!(
             self,
@@ -35,18 +51,53 @@ impl TaskStatus {
     }
 }
 
+/// A status a task entered, and when.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct StatusTransition {
+    pub status: TaskStatus,
+    pub at: DateTime<Utc>,
+}
+
 /// Task state.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct TaskState {
     pub id: u64,
     pub name: String,
     pub status: TaskStatus,
     pub progress: Option<f32>,
     pub backend: String,
-    pub cpu_usage: f32,
-    pub memory_usage: f32,
+    pub cpu_usage: Cores,
+    pub memory_usage: Bytes,
+    /// CPU requested by the task definition
+    pub requested_cpu: Option<Cores>,
+    /// Memory requested by the task definition
+    pub requested_memory: Option<Bytes>,
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
+    /// Workflow run the task belongs to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub workflow_id: Option<String>,
+    /// Workflow call the task executes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call_name: Option<String>,
+    /// Index of the task among the call's scatter shards
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shard_index: Option<u32>,
+    /// Tasks whose outputs this task consumes
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<u64>,
+    /// Every status the task entered, oldest first
+    pub transitions: Vec<StatusTransition>,
+    /// When `progress` last changed
+    #[serde(skip)]
+    pub progress_changed_at: Option<DateTime<Utc>>,
+    /// Failure reason, if the task failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Most recent log lines, kept after the task fails
+    #[serde(skip)]
+    pub log_tail: VecDeque<String>,
+    #[serde(skip)]
     pub cancellation_token: Option<tokio_util::sync::CancellationToken>,
 }
 
@@ -57,20 +108,146 @@ impl TaskState {
         backend: String,
         cancellation_token: Option<tokio_util::sync::CancellationToken>,
     ) -> Self {
+        let now = Utc::now();
         Self {
             id,
             name,
             status: TaskStatus::Created,
             progress: None,
             backend,
-            cpu_usage: 0.0,
-            memory_usage: 0.0,
-            start_time: Utc::now(),
+            cpu_usage: Cores::default(),
+            memory_usage: Bytes::default(),
+            requested_cpu: None,
+            requested_memory: None,
+            start_time: now,
             end_time: None,
+            workflow_id: None,
+            call_name: None,
+            shard_index: None,
+            depends_on: Vec::new(),
+            transitions: vec![StatusTransition { status: TaskStatus::Created, at: now }],
+            progress_changed_at: None,
+            log_tail: VecDeque::new(),
+            error: None,
             cancellation_token,
         }
     }
     
+    /// Builder method to set the requested CPU and memory.
+    pub fn with_requests(mut self, cpu: Cores, memory: Bytes) -> Self {
+        self.requested_cpu = Some(cpu);
+        self.requested_memory = Some(memory);
+        self
+    }
+    
+    /// Set the workflow run, call and scatter shard the task belongs to.
+    pub fn with_workflow(mut self, workflow_id: impl Into<String>, call_name: impl Into<String>, shard_index: Option<u32>) -> Self {
+        self.workflow_id = Some(workflow_id.into());
+        self.call_name = Some(call_name.into());
+        self.shard_index = shard_index;
+        self
+    }
+    
+    /// Call the task executes, falling back to its base name for tasks
+    /// the engine did not tag with one.
+    pub fn call(&self) -> &str {
+        self.call_name.as_deref().unwrap_or_else(|| self.base_name())
+    }
+    
+    /// Task name without a trailing numeric suffix, so that
+    /// "alignment-12" and "alignment-13" share the base name "alignment".
+    pub fn base_name(&self) -> &str {
+        match self.name.rsplit_once('-') {
+            Some((base, suffix)) if !base.is_empty() && !suffix.is_empty()
+                && suffix.bytes().all(|b| b.is_ascii_digit()) => base,
+            _ => &self.name,
+        }
+    }
+    
+    /// Remember a log line, dropping the oldest beyond `LOG_TAIL_LINES`.
+    pub fn push_log(&mut self, line: String) {
+        if self.log_tail.len() == LOG_TAIL_LINES {
+            self.log_tail.pop_front();
+        }
+        self.log_tail.push_back(line);
+    }
+    
+    /// Set the progress, remembering when it last moved.
+    pub fn set_progress(&mut self, progress: f32, at: DateTime<Utc>) {
+        if self.progress != Some(progress) {
+            self.progress = Some(progress);
+            self.progress_changed_at = Some(at);
+        }
+    }
+    
+    /// How long the progress has not moved while running.
+    pub fn progress_idle(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        if self.status != TaskStatus::Running {
+            return None;
+        }
+        let running = self.entered(TaskStatus::Running).unwrap_or(self.start_time);
+        let since = self.progress_changed_at.map_or(running, |at| at.max(running));
+        Some(now - since)
+    }
+    
+    /// Move to a new status, recording the transition. Returns whether the
+    /// status changed.
+    pub fn transition(&mut self, status: TaskStatus, at: DateTime<Utc>) -> bool {
+        if self.transitions.last().is_some_and(|t| t.status == status) {
+            self.status = status;
+            return false;
+        }
+        self.status = status;
+        self.transitions.push(StatusTransition { status, at });
+        true
+    }
+    
+    /// When the task first entered a status.
+    pub fn entered(&self, status: TaskStatus) -> Option<DateTime<Utc>> {
+        self.transitions.iter().find(|t| t.status == status).map(|t| t.at)
+    }
+    
+    /// When the task reached a terminal status.
+    pub fn finished_at(&self) -> Option<DateTime<Utc>> {
+        self.end_time.or_else(|| {
+            self.transitions.iter().find(|t| t.status.is_terminal()).map(|t| t.at)
+        })
+    }
+    
+    /// Time spent queued before running, so far if still queued.
+    pub fn queue_wait(&self) -> Option<chrono::Duration> {
+        let queued = self.entered(TaskStatus::Queued)?;
+        let until = self.entered(TaskStatus::Running)
+            .or_else(|| self.finished_at())
+            .unwrap_or_else(Utc::now);
+        Some(until - queued)
+    }
+    
+    /// Time spent running, so far if still running.
+    pub fn run_time(&self) -> Option<chrono::Duration> {
+        let running = self.entered(TaskStatus::Running)?;
+        Some(self.finished_at().unwrap_or_else(Utc::now) - running)
+    }
+    
+    /// Estimated time until a running task completes, extrapolated from its
+    /// progress rate since it started running.
+    pub fn eta(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        if self.status != TaskStatus::Running {
+            return None;
+        }
+        let progress = self.progress.filter(|p| *p > 0.0)?.min(1.0) as f64;
+        let running = self.entered(TaskStatus::Running).unwrap_or(self.start_time);
+        let elapsed = (now - running).num_milliseconds().max(0) as f64;
+        let remaining = elapsed * (1.0 - progress) / progress;
+        Some(chrono::Duration::milliseconds(remaining.round() as i64))
+    }
+    
+    /// Time from creation to finishing, so far if still active.
+    pub fn total_time(&self) -> chrono::Duration {
+        let created = self.transitions.first().map_or(self.start_time, |t| t.at);
+        self.finished_at().unwrap_or_else(Utc::now) - created
+    }
+    
     pub fn duration(&self) -> chrono::Duration {
         self.end_time
             .unwrap_or_else(Utc::now)
@@ -93,3 +270,82 @@ impl TaskState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use crate::state::testing;
+
+    #[test]
+    fn transition_records_only_changes() {
+        let mut task = testing::task(1, "align");
+        let at = task.start_time;
+        assert!(task.transition(TaskStatus::Queued, at));
+        assert!(!task.transition(TaskStatus::Queued, at + Duration::seconds(1)));
+        assert_eq!(task.transitions.len(), 2);
+        assert_eq!(task.entered(TaskStatus::Queued), Some(at));
+    }
+
+    #[test]
+    fn queue_wait_until_running() {
+        let mut task = testing::task(1, "align");
+        assert_eq!(task.queue_wait(), None);
+
+        let queued = Utc::now() - Duration::minutes(5);
+        task.transition(TaskStatus::Queued, queued);
+        task.transition(TaskStatus::Running, queued + Duration::seconds(90));
+        assert_eq!(task.queue_wait(), Some(Duration::seconds(90)));
+    }
+
+    #[test]
+    fn queue_wait_of_task_cancelled_while_queued() {
+        let mut task = testing::task(1, "align");
+        let queued = Utc::now() - Duration::minutes(5);
+        task.transition(TaskStatus::Queued, queued);
+        task.transition(TaskStatus::Cancelled, queued + Duration::seconds(20));
+        assert_eq!(task.queue_wait(), Some(Duration::seconds(20)));
+        assert_eq!(task.run_time(), None);
+    }
+
+    #[test]
+    fn eta_extrapolates_progress_rate() {
+        let mut task = testing::task(1, "align");
+        let now = Utc::now();
+        task.transition(TaskStatus::Running, now - Duration::minutes(10));
+        task.progress = Some(0.25);
+        assert_eq!(task.eta(now), Some(Duration::minutes(30)));
+
+        task.progress = Some(1.0);
+        assert_eq!(task.eta(now), Some(Duration::zero()));
+    }
+
+    #[test]
+    fn no_eta_without_progress_or_when_not_running() {
+        let mut task = testing::task(1, "align");
+        let now = Utc::now();
+        task.progress = Some(0.5);
+        assert_eq!(task.eta(now), None);
+
+        task.transition(TaskStatus::Running, now - Duration::minutes(10));
+        task.progress = Some(0.0);
+        assert_eq!(task.eta(now), None);
+        task.progress = None;
+        assert_eq!(task.eta(now), None);
+    }
+
+    #[rstest]
+    #[case("align-12", "align")]
+    #[case("variant-calling-3", "variant-calling")]
+    #[case("align", "align")]
+    #[case("align-", "align-")]
+    #[case("-12", "-12")]
+    #[case("align-v2", "align-v2")]
+    #[case("", "")]
+    fn base_name_strips_numeric_suffix(#[case] name: &str, #[case] base: &str) {
+        let task = TaskState::new(1, name.into(), "docker".into(), None);
+        assert_eq!(task.base_name(), base);
+    }
+}
@@ -0,0 +1,15 @@
+//! Fixtures shared by the state tests.
+
+use crate::state::{AppState, TaskState};
+
+/// A newly created task named `{name}-{id}` on the docker backend.
+pub fn task(id: u64, name: &str) -> TaskState {
+    TaskState::new(id, format!("{}-{}", name, id), "docker".into(), None)
+}
+
+/// An app state holding the given tasks.
+pub fn state_with(tasks: impl IntoIterator<Item = TaskState>) -> AppState {
+    let mut state = AppState::new();
+    state.tasks = tasks.into_iter().map(|t| (t.id, t)).collect();
+    state
+}
@@ -0,0 +1,238 @@
+//! Cluster-wide task throughput.
+//!
+//! Counts tasks starting, completing and failing across all backends, one
+//! sample per transition like `TaskActivity`, and derives per-minute rates
+//! and the failure rate over rolling windows.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::state::{Percent, Resolution, TaskStatus, TimeSeries};
+
+/// Window that rates are taken over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateWindow {
+    FiveMinutes,
+    Hour,
+    /// Since monitoring started
+    Session,
+}
+
+impl RateWindow {
+    /// All windows, shortest first.
+    pub const ALL: [RateWindow; 3] = [RateWindow::FiveMinutes, RateWindow::Hour, RateWindow::Session];
+
+    /// Short label for tables and titles.
+    pub fn label(&self) -> &'static str {
+        match self {
+            RateWindow::FiveMinutes => "5m",
+            RateWindow::Hour => "1h",
+            RateWindow::Session => "session",
+        }
+    }
+}
+
+/// Rates over one window.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rates {
+    /// Tasks that started running, per minute
+    pub started: f64,
+    /// Tasks that completed successfully, per minute
+    pub completed: f64,
+    /// Share of finished tasks that failed, `None` if none finished
+    pub failure_rate: Option<Percent>,
+}
+
+/// Task starts, completions and failures over time.
+#[derive(Debug, Clone)]
+pub struct Throughput {
+    pub started: TimeSeries,
+    pub completed: TimeSeries,
+    pub failed: TimeSeries,
+    /// When monitoring started
+    pub session_start: DateTime<Utc>,
+    /// Session totals of started, completed and failed tasks, kept apart
+    /// from the series so they survive ring eviction
+    totals: (u64, u64, u64),
+}
+
+impl Default for Throughput {
+    fn default() -> Self {
+        Self {
+            started: TimeSeries::new(),
+            completed: TimeSeries::new(),
+            failed: TimeSeries::new(),
+            session_start: Utc::now(),
+            totals: (0, 0, 0),
+        }
+    }
+}
+
+impl Throughput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record a task starting to run.
+    pub fn record_started(&mut self, timestamp: DateTime<Utc>) {
+        self.started.push(timestamp, 1.0);
+        self.totals.0 += 1;
+    }
+
+    /// Record a task reaching a terminal status.
+    pub fn record_finished(&mut self, timestamp: DateTime<Utc>, status: TaskStatus) {
+        match status {
+            TaskStatus::Completed => {
+                self.completed.push(timestamp, 1.0);
+                self.totals.1 += 1;
+            }
+            TaskStatus::Failed => {
+                self.failed.push(timestamp, 1.0);
+                self.totals.2 += 1;
+            }
+            _ => {}
+        }
+    }
+
+    /// Rates over the window ending at `now`. Windows longer than the
+    /// session are shortened to it.
+    pub fn rates(&self, window: RateWindow, now: DateTime<Utc>) -> Rates {
+        let session = now - self.session_start;
+        let (started, completed, failed, span) = match window {
+            RateWindow::Session => {
+                let (started, completed, failed) = self.totals;
+                (started as f64, completed as f64, failed as f64, session)
+            }
+            RateWindow::FiveMinutes | RateWindow::Hour => {
+                let length = if window == RateWindow::FiveMinutes {
+                    Duration::minutes(5)
+                } else {
+                    Duration::hours(1)
+                };
+                let span = length.min(session);
+                (
+                    events_in(&self.started, now, span),
+                    events_in(&self.completed, now, span),
+                    events_in(&self.failed, now, span),
+                    span,
+                )
+            }
+        };
+
+        // Avoid huge rates in the first seconds of a session
+        let minutes = (span.num_milliseconds() as f64 / 60_000.0).max(1.0);
+        let finished = completed + failed;
+        Rates {
+            started: started / minutes,
+            completed: completed / minutes,
+            failure_rate: (finished > 0.0).then(|| Percent::of(failed, finished)),
+        }
+    }
+
+    /// Events per minute for each of the last `minutes` minutes, oldest
+    /// first, with zeros for quiet minutes.
+    pub fn per_minute(series: &TimeSeries, now: DateTime<Utc>, minutes: usize) -> Vec<f64> {
+        let mut counts = vec![0.0; minutes];
+        let current = now.timestamp() / 60;
+        for bucket in series.buckets(Resolution::Minute) {
+            let age = current - bucket.start.timestamp() / 60;
+            if (0..minutes as i64).contains(&age) {
+                counts[minutes - 1 - age as usize] += bucket.sum();
+            }
+        }
+        counts
+    }
+
+    /// Failure rate in percent for each of the last `minutes` minutes,
+    /// carrying the previous value over minutes without finished tasks.
+    pub fn failure_rate_per_minute(&self, now: DateTime<Utc>, minutes: usize) -> Vec<f64> {
+        let completed = Self::per_minute(&self.completed, now, minutes);
+        let failed = Self::per_minute(&self.failed, now, minutes);
+        let mut last = 0.0;
+        completed.iter().zip(&failed).map(|(&ok, &bad)| {
+            if ok + bad > 0.0 {
+                last = Percent::of(bad, ok + bad).0 as f64;
+            }
+            last
+        }).collect()
+    }
+}
+
+/// Number of events recorded in the span ending at `now`.
+fn events_in(series: &TimeSeries, now: DateTime<Utc>, span: Duration) -> f64 {
+    series.window_ending(now, span).1.iter().map(|b| b.sum()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    fn session(length: Duration) -> (Throughput, DateTime<Utc>) {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let throughput = Throughput { session_start: now - length, ..Throughput::new() };
+        (throughput, now)
+    }
+
+    #[test]
+    fn no_events() {
+        let (throughput, now) = session(Duration::hours(2));
+        for window in RateWindow::ALL {
+            assert_eq!(
+                throughput.rates(window, now),
+                Rates { started: 0.0, completed: 0.0, failure_rate: None },
+            );
+        }
+    }
+
+    #[test]
+    fn rates_per_window() {
+        let (mut throughput, now) = session(Duration::hours(2));
+        for (minutes_ago, count) in [(90, 30), (30, 20), (4, 10)] {
+            for _ in 0..count {
+                throughput.record_started(now - Duration::minutes(minutes_ago));
+            }
+        }
+
+        assert_eq!(throughput.rates(RateWindow::FiveMinutes, now).started, 2.0);
+        assert_eq!(throughput.rates(RateWindow::Hour, now).started, 0.5);
+        assert_eq!(throughput.rates(RateWindow::Session, now).started, 0.5);
+    }
+
+    #[test]
+    fn short_session_counts_as_a_minute() {
+        let (mut throughput, now) = session(Duration::seconds(10));
+        for _ in 0..3 {
+            throughput.record_started(now - Duration::seconds(5));
+        }
+
+        for window in RateWindow::ALL {
+            assert_eq!(throughput.rates(window, now).started, 3.0);
+        }
+    }
+
+    #[test]
+    fn failure_rate_of_finished_tasks() {
+        let (mut throughput, now) = session(Duration::minutes(10));
+        let at = now - Duration::minutes(2);
+        for status in [TaskStatus::Completed, TaskStatus::Completed, TaskStatus::Completed, TaskStatus::Failed] {
+            throughput.record_finished(at, status);
+        }
+        throughput.record_finished(at, TaskStatus::Cancelled);
+
+        let rates = throughput.rates(RateWindow::FiveMinutes, now);
+        assert_eq!(rates.completed, 0.6);
+        assert_eq!(rates.failure_rate, Some(Percent(25.0)));
+    }
+
+    #[test]
+    fn failure_rate_carried_over_quiet_minutes() {
+        let (mut throughput, now) = session(Duration::minutes(10));
+        throughput.record_finished(now - Duration::minutes(3), TaskStatus::Failed);
+        throughput.record_finished(now - Duration::minutes(3), TaskStatus::Completed);
+        throughput.record_finished(now - Duration::minutes(1), TaskStatus::Completed);
+
+        assert_eq!(Throughput::per_minute(&throughput.completed, now, 4), vec![1.0, 0.0, 1.0, 0.0]);
+        assert_eq!(throughput.failure_rate_per_minute(now, 5), vec![0.0, 50.0, 50.0, 0.0, 0.0]);
+    }
+}
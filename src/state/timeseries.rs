@@ -0,0 +1,348 @@
+//! Multi-resolution time series.
+//!
+//! Each series keeps three ring buffers of buckets at 1 second, 1 minute and
+//! 1 hour resolution. Every sample is folded into the current bucket of all
+//! three rings, so the fine ring covers the last few minutes in detail while
+//! the coarse rings cover hours or days at a fixed memory cost. Charts ask for
+//! a time window and get the finest resolution that covers it.
+
+use std::collections::VecDeque;
+use chrono::{DateTime, Duration, Utc};
+
+/// Resolution of a ring buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Second,
+    Minute,
+    Hour,
+}
+
+impl Resolution {
+    /// All resolutions, finest first.
+    pub const ALL: [Resolution; 3] = [Resolution::Second, Resolution::Minute, Resolution::Hour];
+
+    /// Length of one bucket in seconds.
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Resolution::Second => 1,
+            Resolution::Minute => 60,
+            Resolution::Hour => 3600,
+        }
+    }
+
+    /// Length of one bucket.
+    pub fn duration(&self) -> Duration {
+        Duration::seconds(self.seconds())
+    }
+
+    /// Short label for chart titles.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Resolution::Second => "1s",
+            Resolution::Minute => "1m",
+            Resolution::Hour => "1h",
+        }
+    }
+}
+
+/// Aggregated samples for one time bucket.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bucket {
+    /// Start of the bucket
+    pub start: DateTime<Utc>,
+    pub min: f32,
+    pub max: f32,
+    /// Sum of all samples, for the mean
+    sum: f64,
+    /// Number of samples folded into the bucket
+    pub count: u32,
+}
+
+impl Bucket {
+    fn new(start: DateTime<Utc>, value: f32) -> Self {
+        Self { start, min: value, max: value, sum: value as f64, count: 1 }
+    }
+
+    fn add(&mut self, value: f32) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value as f64;
+        self.count += 1;
+    }
+
+    /// Sum of the samples in the bucket.
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Mean of the samples in the bucket.
+    pub fn mean(&self) -> f32 {
+        (self.sum / self.count.max(1) as f64) as f32
+    }
+}
+
+/// Ring buffer of buckets at a single resolution.
+#[derive(Debug, Clone)]
+struct Ring {
+    resolution: Resolution,
+    capacity: usize,
+    buckets: VecDeque<Bucket>,
+}
+
+impl Ring {
+    fn new(resolution: Resolution, capacity: usize) -> Self {
+        Self { resolution, capacity, buckets: VecDeque::new() }
+    }
+
+    fn push(&mut self, timestamp: DateTime<Utc>, value: f32) {
+        let start = bucket_start(timestamp, self.resolution);
+
+        // Late samples are folded into their bucket if it is still held
+        match self.buckets.back_mut() {
+            Some(last) if last.start == start => last.add(value),
+            Some(last) if last.start > start => {
+                if let Some(bucket) = self.buckets.iter_mut().rev().find(|b| b.start == start) {
+                    bucket.add(value);
+                }
+            }
+            _ => {
+                self.buckets.push_back(Bucket::new(start, value));
+                if self.buckets.len() > self.capacity {
+                    self.buckets.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Time span the ring can hold when full.
+    fn span(&self) -> Duration {
+        self.resolution.duration() * self.capacity as i32
+    }
+}
+
+/// A metric recorded at 1s, 1m and 1h resolution.
+#[derive(Debug, Clone)]
+pub struct TimeSeries {
+    rings: [Ring; 3],
+    latest: Option<(DateTime<Utc>, f32)>,
+}
+
+impl Default for TimeSeries {
+    fn default() -> Self {
+        // 10 minutes at 1s, 24 hours at 1m, 7 days at 1h
+        Self::with_capacity(600, 1440, 168)
+    }
+}
+
+impl TimeSeries {
+    /// Create a series with the default ring sizes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a series holding the given number of buckets per resolution.
+    pub fn with_capacity(seconds: usize, minutes: usize, hours: usize) -> Self {
+        Self {
+            rings: [
+                Ring::new(Resolution::Second, seconds),
+                Ring::new(Resolution::Minute, minutes),
+                Ring::new(Resolution::Hour, hours),
+            ],
+            latest: None,
+        }
+    }
+
+    /// Record a sample.
+    pub fn push(&mut self, timestamp: DateTime<Utc>, value: f32) {
+        for ring in &mut self.rings {
+            ring.push(timestamp, value);
+        }
+        if !matches!(self.latest, Some((last, _)) if timestamp < last) {
+            self.latest = Some((timestamp, value));
+        }
+    }
+
+    /// Most recent sample.
+    pub fn latest(&self) -> Option<f32> {
+        self.latest.map(|(_, value)| value)
+    }
+
+    /// Whether no samples have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.latest.is_none()
+    }
+
+    /// All buckets held at a resolution, oldest first.
+    pub fn buckets(&self, resolution: Resolution) -> impl Iterator<Item = &Bucket> {
+        self.ring(resolution).buckets.iter()
+    }
+
+    /// Finest resolution whose ring can cover the window.
+    pub fn resolution_for(&self, window: Duration) -> Resolution {
+        self.rings.iter()
+            .find(|ring| ring.span() >= window)
+            .map_or(Resolution::Hour, |ring| ring.resolution)
+    }
+
+    /// Buckets covering the window ending now, at the matching resolution.
+    pub fn window(&self, window: Duration) -> (Resolution, Vec<Bucket>) {
+        self.window_ending(Utc::now(), window)
+    }
+
+    /// Buckets covering the window ending at `end`, at the matching resolution.
+    pub fn window_ending(&self, end: DateTime<Utc>, window: Duration) -> (Resolution, Vec<Bucket>) {
+        let resolution = self.resolution_for(window);
+        let since = end - window;
+        let buckets = self.buckets(resolution)
+            .filter(|b| b.start + resolution.duration() > since && b.start <= end)
+            .copied()
+            .collect();
+        (resolution, buckets)
+    }
+
+    /// Bucket means over the window ending now, for charts.
+    pub fn means(&self, window: Duration) -> Vec<f64> {
+        self.window(window).1.iter().map(|b| b.mean() as f64).collect()
+    }
+
+    /// Mean of all samples in the window ending at `end`, weighting each
+    /// bucket by its sample count.
+    pub fn mean_ending(&self, end: DateTime<Utc>, window: Duration) -> Option<f32> {
+        let (_, buckets) = self.window_ending(end, window);
+        let count: u64 = buckets.iter().map(|b| b.count as u64).sum();
+        (count > 0).then(|| (buckets.iter().map(|b| b.sum).sum::<f64>() / count as f64) as f32)
+    }
+
+    /// Means of the window ending at `end` split into `columns` equal
+    /// parts, `None` where a part holds no samples. The resolution is picked
+    /// for the whole window, so older parts are not read from a ring that
+    /// has already dropped them.
+    pub fn binned(&self, end: DateTime<Utc>, window: Duration, columns: usize) -> Vec<Option<f32>> {
+        let (resolution, buckets) = self.window_ending(end, window);
+        let start = end - window;
+        let width = window.num_milliseconds().max(1) as f64 / columns.max(1) as f64;
+        (0..columns).map(|i| {
+            let from = start + Duration::milliseconds((i as f64 * width) as i64);
+            let until = start + Duration::milliseconds(((i + 1) as f64 * width) as i64);
+            let (sum, count) = buckets.iter()
+                .filter(|b| b.start < until && b.start + resolution.duration() > from)
+                .fold((0.0, 0u64), |(sum, count), b| (sum + b.sum, count + b.count as u64));
+            (count > 0).then(|| (sum / count as f64) as f32)
+        }).collect()
+    }
+
+    /// Highest value recorded in the window ending now.
+    pub fn max_in(&self, window: Duration) -> Option<f32> {
+        self.window(window).1.iter().map(|b| b.max).reduce(f32::max)
+    }
+
+    /// Highest value held at any resolution.
+    pub fn peak(&self) -> Option<f32> {
+        self.buckets(Resolution::Hour).map(|b| b.max).reduce(f32::max)
+    }
+
+    fn ring(&self, resolution: Resolution) -> &Ring {
+        match resolution {
+            Resolution::Second => &self.rings[0],
+            Resolution::Minute => &self.rings[1],
+            Resolution::Hour => &self.rings[2],
+        }
+    }
+}
+
+/// CPU and memory series recorded together.
+#[derive(Debug, Clone, Default)]
+pub struct ResourceHistory {
+    pub cpu: TimeSeries,
+    pub memory: TimeSeries,
+}
+
+impl ResourceHistory {
+    /// Create an empty history with the default ring sizes.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty history with the given ring sizes for both series.
+    pub fn with_capacity(seconds: usize, minutes: usize, hours: usize) -> Self {
+        Self {
+            cpu: TimeSeries::with_capacity(seconds, minutes, hours),
+            memory: TimeSeries::with_capacity(seconds, minutes, hours),
+        }
+    }
+
+    /// Record a CPU and memory sample.
+    pub fn record(&mut self, timestamp: DateTime<Utc>, cpu: f32, memory: f32) {
+        self.cpu.push(timestamp, cpu);
+        self.memory.push(timestamp, memory);
+    }
+
+    /// Whether no samples have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_empty()
+    }
+}
+
+/// Start of the bucket containing a timestamp.
+fn bucket_start(timestamp: DateTime<Utc>, resolution: Resolution) -> DateTime<Utc> {
+    let secs = timestamp.timestamp();
+    let start = secs - secs.rem_euclid(resolution.seconds());
+    DateTime::from_timestamp(start, 0).unwrap_or(timestamp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use pretty_assertions::assert_eq;
+
+    fn noon() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn binned_empty_series() {
+        let series = TimeSeries::new();
+        assert_eq!(series.binned(noon(), Duration::minutes(10), 5), vec![None; 5]);
+        assert_eq!(series.binned(noon(), Duration::minutes(10), 0), Vec::new());
+    }
+
+    #[test]
+    fn binned_single_sample() {
+        let mut series = TimeSeries::new();
+        series.push(noon() - Duration::seconds(5), 0.4);
+
+        // Five columns of two seconds each, the sample in the third
+        assert_eq!(
+            series.binned(noon(), Duration::seconds(10), 5),
+            vec![None, None, Some(0.4), None, None],
+        );
+    }
+
+    #[test]
+    fn binned_means_samples_per_column() {
+        let mut series = TimeSeries::new();
+        for (ago, value) in [(4, 0.2), (3, 0.6), (1, 1.0)] {
+            series.push(noon() - Duration::seconds(ago), value);
+        }
+
+        assert_eq!(series.binned(noon(), Duration::seconds(4), 2), vec![Some(0.4), Some(1.0)]);
+    }
+
+    #[test]
+    fn binned_reads_whole_window_at_one_resolution() {
+        // The second ring only covers the last ten seconds
+        let mut series = TimeSeries::with_capacity(10, 60, 24);
+        series.push(noon() - Duration::minutes(55), 1.0);
+        series.push(noon() - Duration::minutes(5), 0.5);
+        for ago in (1..=5).rev() {
+            series.push(noon() - Duration::seconds(ago), 0.2);
+        }
+
+        assert_eq!(series.resolution_for(Duration::hours(1)), Resolution::Minute);
+        assert_eq!(
+            series.binned(noon(), Duration::hours(1), 6),
+            vec![Some(1.0), None, None, None, None, Some(0.25)],
+        );
+    }
+}
@@ -0,0 +1,240 @@
+//! Resource units.
+//!
+//! Task usage is measured in absolute units, CPU in cores and memory in
+//! bytes, while backend usage is a percentage of the backend's capacity.
+//! Keeping them as distinct types stops a percentage being summed with a
+//! core count, and gives every view the same human-readable formatting.
+
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, AddAssign};
+use serde::Serialize;
+
+/// Amount of memory in bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(transparent)]
+pub struct Bytes(pub u64);
+
+impl Bytes {
+    pub const KIB: u64 = 1024;
+    pub const MIB: u64 = 1024 * 1024;
+    pub const GIB: u64 = 1024 * 1024 * 1024;
+
+    /// Bytes from a size in mebibytes.
+    pub fn from_mib(mib: f64) -> Self {
+        Self((mib.max(0.0) * Self::MIB as f64) as u64)
+    }
+
+    /// Bytes from a size in gibibytes.
+    pub fn from_gib(gib: f64) -> Self {
+        Self((gib.max(0.0) * Self::GIB as f64) as u64)
+    }
+
+    /// Size in mebibytes.
+    pub fn as_mib(&self) -> f64 {
+        self.0 as f64 / Self::MIB as f64
+    }
+
+    /// Size in gibibytes.
+    pub fn as_gib(&self) -> f64 {
+        self.0 as f64 / Self::GIB as f64
+    }
+}
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.0 as f64;
+        if self.0 >= Self::GIB {
+            write!(f, "{:.1} GiB", bytes / Self::GIB as f64)
+        } else if self.0 >= Self::MIB {
+            write!(f, "{:.1} MiB", bytes / Self::MIB as f64)
+        } else if self.0 >= Self::KIB {
+            write!(f, "{:.1} KiB", bytes / Self::KIB as f64)
+        } else {
+            write!(f, "{} B", self.0)
+        }
+    }
+}
+
+impl Add for Bytes {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+}
+
+impl AddAssign for Bytes {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sum for Bytes {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// CPU time in cores, where 1.0 is one fully used core.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct Cores(pub f32);
+
+impl Cores {
+    /// Cores from millicores, as used by Kubernetes and TES requests.
+    pub fn from_millicores(millicores: u32) -> Self {
+        Self(millicores as f32 / 1000.0)
+    }
+
+    /// Usage in millicores.
+    pub fn millicores(&self) -> u32 {
+        (self.0.max(0.0) * 1000.0).round() as u32
+    }
+}
+
+impl fmt::Display for Cores {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Below one core, millicores read better than a small fraction
+        if self.0 < 1.0 {
+            write!(f, "{}m", self.millicores())
+        } else {
+            write!(f, "{:.2} cores", self.0)
+        }
+    }
+}
+
+impl Add for Cores {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl AddAssign for Cores {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Sum for Cores {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
+
+/// Share of a capacity, from 0 to 100.
+#[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct Percent(pub f32);
+
+impl Percent {
+    /// Percentage clamped to 0-100.
+    pub fn new(value: f32) -> Self {
+        Self(value.clamp(0.0, 100.0))
+    }
+
+    /// Percentage of `part` in `whole`, or zero for an empty whole.
+    pub fn of(part: f64, whole: f64) -> Self {
+        if whole > 0.0 {
+            Self::new((part / whole * 100.0) as f32)
+        } else {
+            Self(0.0)
+        }
+    }
+
+    /// Share as a ratio from 0.0 to 1.0.
+    pub fn ratio(&self) -> f32 {
+        self.0 / 100.0
+    }
+}
+
+impl fmt::Display for Percent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.1}%", self.0)
+    }
+}
+
+/// Unit of a plain number, such as a time series sample, for formatting
+/// and chart scaling.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Bytes,
+    Cores,
+    Percent,
+}
+
+impl Unit {
+    /// Format a value in this unit.
+    pub fn format(&self, value: f64) -> String {
+        match self {
+            Unit::Bytes => Bytes(value.max(0.0) as u64).to_string(),
+            Unit::Cores => Cores(value as f32).to_string(),
+            Unit::Percent => Percent(value as f32).to_string(),
+        }
+    }
+
+    /// Fixed upper bound for charts, if the unit has one.
+    pub fn chart_max(&self) -> Option<f64> {
+        match self {
+            Unit::Percent => Some(100.0),
+            Unit::Bytes | Unit::Cores => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(0, "0 B")]
+    #[case(1023, "1023 B")]
+    #[case(Bytes::KIB, "1.0 KiB")]
+    #[case(Bytes::MIB - 1, "1024.0 KiB")]
+    #[case(Bytes::MIB, "1.0 MiB")]
+    #[case(1536 * Bytes::KIB, "1.5 MiB")]
+    #[case(Bytes::GIB - 1, "1024.0 MiB")]
+    #[case(Bytes::GIB, "1.0 GiB")]
+    #[case(20 * Bytes::GIB, "20.0 GiB")]
+    fn bytes_display(#[case] bytes: u64, #[case] expected: &str) {
+        assert_eq!(Bytes(bytes).to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(0.0, "0m")]
+    #[case(0.25, "250m")]
+    #[case(0.9994, "999m")]
+    #[case(1.0, "1.00 cores")]
+    #[case(2.5, "2.50 cores")]
+    fn cores_display(#[case] cores: f32, #[case] expected: &str) {
+        assert_eq!(Cores(cores).to_string(), expected);
+    }
+
+    #[rstest]
+    #[case(0.0, "0.0%")]
+    #[case(12.34, "12.3%")]
+    #[case(100.0, "100.0%")]
+    fn percent_display(#[case] percent: f32, #[case] expected: &str) {
+        assert_eq!(Percent(percent).to_string(), expected);
+    }
+
+    #[rstest]
+    #[case::half(1.0, 2.0, 50.0)]
+    #[case::clamped(3.0, 2.0, 100.0)]
+    #[case::empty_whole(1.0, 0.0, 0.0)]
+    #[case::negative_whole(1.0, -2.0, 0.0)]
+    fn percent_of(#[case] part: f64, #[case] whole: f64, #[case] expected: f32) {
+        assert_eq!(Percent::of(part, whole), Percent(expected));
+    }
+
+    #[test]
+    fn millicores_round_trip() {
+        assert_eq!(Cores::from_millicores(1500), Cores(1.5));
+        assert_eq!(Cores(0.1234).millicores(), 123);
+        assert_eq!(Cores(-1.0).millicores(), 0);
+    }
+}
@@ -0,0 +1,217 @@
+//! Workflow hierarchy of tasks.
+//!
+//! Groups tasks into workflow runs and, within a run, into the calls they
+//! execute, with each call's scatter shards as its members. Every group
+//! carries a rollup of its members' statuses and progress, so a run of
+//! thousands of tasks can be read at a glance.
+
+use std::collections::BTreeMap;
+
+use crate::state::{TaskState, TaskStatus};
+
+/// Group name for tasks that do not belong to a workflow run.
+pub const NO_WORKFLOW: &str = "(no workflow)";
+
+/// Task counts and progress of a group.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Rollup {
+    pub total: usize,
+    /// Created or queued
+    pub waiting: usize,
+    pub running: usize,
+    pub completed: usize,
+    pub failed: usize,
+    pub cancelled: usize,
+    /// Sum of member progress, finished tasks counting as done
+    progress_sum: f64,
+}
+
+impl Rollup {
+    /// Count a task into the rollup.
+    pub fn add(&mut self, task: &TaskState) {
+        self.total += 1;
+        match task.status {
+            TaskStatus::Created | TaskStatus::Queued => self.waiting += 1,
+            TaskStatus::Running => self.running += 1,
+            TaskStatus::Completed => self.completed += 1,
+            TaskStatus::Failed => self.failed += 1,
+            TaskStatus::Cancelled => self.cancelled += 1,
+        }
+        self.progress_sum += if task.status.is_terminal() {
+            1.0
+        } else {
+            task.progress.unwrap_or(0.0) as f64
+        };
+    }
+
+    /// Mean progress of the members, from 0.0 to 1.0.
+    pub fn progress(&self) -> f64 {
+        if self.total == 0 {
+            0.0
+        } else {
+            self.progress_sum / self.total as f64
+        }
+    }
+
+    /// Overall status of the group: running while any member runs, then
+    /// queued while any waits, then failed if any member failed.
+    pub fn status(&self) -> TaskStatus {
+        if self.running > 0 {
+            TaskStatus::Running
+        } else if self.waiting > 0 {
+            TaskStatus::Queued
+        } else if self.failed > 0 {
+            TaskStatus::Failed
+        } else if self.cancelled == self.total {
+            TaskStatus::Cancelled
+        } else {
+            TaskStatus::Completed
+        }
+    }
+
+    /// Add another group's counts to this one.
+    pub fn merge(&mut self, other: &Rollup) {
+        self.total += other.total;
+        self.waiting += other.waiting;
+        self.running += other.running;
+        self.completed += other.completed;
+        self.failed += other.failed;
+        self.cancelled += other.cancelled;
+        self.progress_sum += other.progress_sum;
+    }
+}
+
+/// Shards of one call within a workflow run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallGroup {
+    /// Call name, or the task's base name for untagged tasks
+    pub name: String,
+    pub rollup: Rollup,
+    /// Member task IDs in the order they were given
+    pub tasks: Vec<u64>,
+}
+
+/// Calls of one workflow run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorkflowGroup {
+    /// Workflow run ID, or `NO_WORKFLOW`
+    pub id: String,
+    pub rollup: Rollup,
+    /// Calls ordered by name
+    pub calls: Vec<CallGroup>,
+}
+
+impl WorkflowGroup {
+    /// Group tasks by workflow run and call, keeping their relative order
+    /// within each call. Runs are ordered by ID, with untagged tasks last.
+    pub fn group(tasks: &[&TaskState]) -> Vec<WorkflowGroup> {
+        let mut runs: BTreeMap<(bool, &str), BTreeMap<&str, CallGroup>> = BTreeMap::new();
+        for task in tasks {
+            let workflow = task.workflow_id.as_deref();
+            let call = task.call();
+            let group = runs
+                .entry((workflow.is_none(), workflow.unwrap_or(NO_WORKFLOW)))
+                .or_default()
+                .entry(call)
+                .or_insert_with(|| CallGroup {
+                    name: call.to_string(),
+                    rollup: Rollup::default(),
+                    tasks: Vec::new(),
+                });
+            group.rollup.add(task);
+            group.tasks.push(task.id);
+        }
+
+        runs.into_iter().map(|((_, id), calls)| {
+            let calls: Vec<CallGroup> = calls.into_values().collect();
+            let mut rollup = Rollup::default();
+            for call in &calls {
+                rollup.merge(&call.rollup);
+            }
+            WorkflowGroup { id: id.to_string(), rollup, calls }
+        }).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+    use crate::state::testing;
+
+    fn task(id: u64, name: &str, status: TaskStatus) -> TaskState {
+        let mut task = testing::task(id, name);
+        task.transition(status, Utc::now());
+        task
+    }
+
+    fn rollup(statuses: &[TaskStatus]) -> Rollup {
+        let mut rollup = Rollup::default();
+        for (id, &status) in statuses.iter().enumerate() {
+            rollup.add(&task(id as u64, "align", status));
+        }
+        rollup
+    }
+
+    #[rstest]
+    #[case(&[TaskStatus::Completed, TaskStatus::Running, TaskStatus::Failed], TaskStatus::Running)]
+    #[case(&[TaskStatus::Completed, TaskStatus::Queued, TaskStatus::Failed], TaskStatus::Queued)]
+    #[case(&[TaskStatus::Created], TaskStatus::Queued)]
+    #[case(&[TaskStatus::Completed, TaskStatus::Failed, TaskStatus::Cancelled], TaskStatus::Failed)]
+    #[case(&[TaskStatus::Cancelled, TaskStatus::Cancelled], TaskStatus::Cancelled)]
+    #[case(&[TaskStatus::Completed, TaskStatus::Cancelled], TaskStatus::Completed)]
+    #[case(&[TaskStatus::Completed], TaskStatus::Completed)]
+    fn rollup_status(#[case] statuses: &[TaskStatus], #[case] expected: TaskStatus) {
+        assert_eq!(rollup(statuses).status(), expected);
+    }
+
+    #[test]
+    fn rollup_progress_counts_finished_tasks_as_done() {
+        assert_eq!(Rollup::default().progress(), 0.0);
+
+        let mut running = task(1, "align", TaskStatus::Running);
+        running.set_progress(0.5, Utc::now());
+        let mut rollup = rollup(&[TaskStatus::Failed, TaskStatus::Queued]);
+        rollup.add(&running);
+        assert_eq!(rollup.progress(), 0.5);
+    }
+
+    #[test]
+    fn nothing_to_group() {
+        assert_eq!(WorkflowGroup::group(&[]), Vec::new());
+    }
+
+    #[test]
+    fn groups_by_run_and_call_with_untagged_tasks_last() {
+        let tasks = [
+            task(1, "qc", TaskStatus::Completed),
+            task(2, "align", TaskStatus::Running).with_workflow("run-b", "align", Some(1)),
+            task(3, "align", TaskStatus::Completed).with_workflow("run-b", "align", Some(0)),
+            task(4, "sort", TaskStatus::Queued).with_workflow("run-a", "sort", Some(0)),
+            task(5, "align", TaskStatus::Failed).with_workflow("run-a", "align", Some(0)),
+            task(6, "report", TaskStatus::Created),
+            task(7, "qc", TaskStatus::Running),
+        ];
+        let refs: Vec<&TaskState> = tasks.iter().collect();
+
+        let groups = WorkflowGroup::group(&refs);
+        let calls: Vec<(&str, &str, Vec<u64>)> = groups.iter()
+            .flat_map(|g| g.calls.iter().map(|c| (g.id.as_str(), c.name.as_str(), c.tasks.clone())))
+            .collect();
+        assert_eq!(calls, vec![
+            ("run-a", "align", vec![5]),
+            ("run-a", "sort", vec![4]),
+            ("run-b", "align", vec![2, 3]),
+            (NO_WORKFLOW, "qc", vec![1, 7]),
+            (NO_WORKFLOW, "report", vec![6]),
+        ]);
+
+        let run_a = &groups[0].rollup;
+        assert_eq!((run_a.total, run_a.waiting, run_a.failed), (2, 1, 1));
+        assert_eq!(run_a.status(), TaskStatus::Queued);
+        assert_eq!(groups[1].rollup.status(), TaskStatus::Running);
+        assert_eq!(groups[2].rollup.total, 3);
+    }
+}
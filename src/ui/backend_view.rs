@@ -8,17 +8,26 @@
 
 use ratatui::Frame;
 use ratatui::layout::{Layout, Constraint, Direction, Rect};
-use ratatui::style::{Color, Style, Modifier};
+use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Tabs, Table, Row, Cell, TableState, Gauge, BarChart};
 use crossterm::event::{KeyEvent, KeyCode};
 use eyre::Result;
-use rand::Rng;
 
-use crate::state::{AppState, BackendState, HealthStatus, BackendKind, TaskStatus};
+use crate::state::{AppState, Bucket, HealthStatus, BackendKind, Percent, TimeSeries, Unit, UtilizationMetric};
 use crate::ui::Theme;
+use crate::ui::widgets::heatmap::Heatmap;
 use crate::ui::widgets::sparkline::Sparkline;
 
+/// Longest selectable resource window: the hourly rings hold 7 days.
+const MAX_RESOURCE_WINDOW_MINUTES: u16 = 7 * 24 * 60;
+
+/// Time window of the utilization heatmap in the backend list, in minutes.
+const HEATMAP_WINDOW_MINUTES: i64 = 60;
+
+/// Width of the backend names in the utilization heatmap.
+const HEATMAP_LABEL_WIDTH: u16 = 16;
+
 /// Tab selection for backend detail view.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum BackendTab {
@@ -116,7 +125,9 @@ impl BackendView {
             
             // Resource time window adjustment
             KeyCode::Char('+') if self.current_tab == BackendTab::Resources => {
-                self.resource_time_window = self.resource_time_window.saturating_add(5);
+                self.resource_time_window = self.resource_time_window
+                    .saturating_add(5)
+                    .min(MAX_RESOURCE_WINDOW_MINUTES);
             }
             KeyCode::Char('-') if self.current_tab == BackendTab::Resources => {
                 self.resource_time_window = self.resource_time_window.saturating_sub(5).max(1);
@@ -128,13 +139,24 @@ impl BackendView {
         Ok(())
     }
     
-    /// Render a list of all backends.
+    /// Render a list of all backends above their utilization heatmap.
     pub fn render_list(
         frame: &mut Frame,
         area: Rect,
         app_state: &AppState,
+        metric: UtilizationMetric,
         theme: &Theme,
     ) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(5),
+                Constraint::Length(app_state.backends.len() as u16 + 3),
+            ])
+            .split(area);
+        let area = chunks[0];
+        Self::render_heatmap(frame, chunks[1], app_state, metric, HEATMAP_WINDOW_MINUTES, theme);
+        
         let block = Block::default()
             .title("Backends")
             .borders(Borders::ALL)
@@ -163,8 +185,8 @@ impl BackendView {
                 Cell::from(format!("{:?}", backend.kind)),
                 Cell::from(format!("{}/{}", backend.running_tasks, backend.total_tasks)),
                 Cell::from(backend.health.to_string()).style(status_style),
-                Cell::from(format!("{:.1}%", backend.cpu_usage)),
-                Cell::from(format!("{:.1}%", backend.memory_usage)),
+                Cell::from(backend.cpu_usage.to_string()),
+                Cell::from(backend.memory_usage.to_string()),
             ])
         });
         
@@ -198,6 +220,45 @@ impl BackendView {
         frame.render_stateful_widget(table, area, &mut table_state);
     }
     
+    /// Render a heatmap of every backend's utilization over the last
+    /// `window_minutes`, newest on the right.
+    pub fn render_heatmap(
+        frame: &mut Frame,
+        area: Rect,
+        app_state: &AppState,
+        metric: UtilizationMetric,
+        window_minutes: i64,
+        theme: &Theme,
+    ) {
+        let block = Block::default()
+            .title(format!(
+                "{} utilization, last {}m (m: metric) ░▒▓█ 25/50/75/100%",
+                metric.label(),
+                window_minutes,
+            ))
+            .borders(Borders::ALL)
+            .style(theme.block_style);
+        
+        // One bucket per cell inside the borders
+        let columns = Heatmap::columns(area.width.saturating_sub(2), HEATMAP_LABEL_WIDTH);
+        let now = chrono::Utc::now();
+        let window = chrono::Duration::minutes(window_minutes);
+        let rows: Vec<(String, Vec<Option<f32>>)> = app_state.backends.values()
+            .map(|backend| {
+                (backend.name.clone(), backend.utilization_history(metric, now, window, columns))
+            })
+            .collect();
+        let start = format!("-{}m", window_minutes);
+        
+        let heatmap = Heatmap::new(&rows)
+            .block(block)
+            .label_width(HEATMAP_LABEL_WIDTH)
+            .axis(&start, "now")
+            .styles(theme.healthy_style, theme.warning_style, theme.critical_style)
+            .label_style(theme.help_style);
+        frame.render_widget(heatmap, area);
+    }
+    
     /// Render the backend detail view.
     pub fn render(
         &self,
@@ -249,8 +310,9 @@ impl BackendView {
             let chunks = Layout::default()
                 .direction(Direction::Vertical)
                 .constraints([
-                    Constraint::Length(6),
+                    Constraint::Length(7),
                     Constraint::Length(8),
+                    Constraint::Length(5),
                     Constraint::Min(0),
                 ])
                 .margin(1)
@@ -259,15 +321,15 @@ impl BackendView {
             // Basic backend info
             let info_text = vec![
                 Line::from(vec![
-                    Span::styled("Name: ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled("Name: ", theme.label_style.add_modifier(Modifier::BOLD)),
                     Span::raw(&backend.name),
                 ]),
                 Line::from(vec![
-                    Span::styled("Type: ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled("Type: ", theme.label_style.add_modifier(Modifier::BOLD)),
                     Span::raw(format!("{:?}", backend.kind)),
                 ]),
                 Line::from(vec![
-                    Span::styled("Status: ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled("Status: ", theme.label_style.add_modifier(Modifier::BOLD)),
                     Span::styled(
                         backend.health.to_string(),
                         match backend.health {
@@ -279,9 +341,19 @@ impl BackendView {
                     ),
                 ]),
                 Line::from(vec![
-                    Span::styled("Tasks: ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled("Tasks: ", theme.label_style.add_modifier(Modifier::BOLD)),
                     Span::raw(format!("{} running / {} total", backend.running_tasks, backend.total_tasks)),
                 ]),
+                Line::from(vec![
+                    Span::styled("Capacity: ", theme.label_style.add_modifier(Modifier::BOLD)),
+                    Span::raw(match backend.capacity {
+                        Some(capacity) => format!(
+                            "{}, {}, {} slots",
+                            capacity.cores, capacity.memory, capacity.slots
+                        ),
+                        None => "not reported".to_string(),
+                    }),
+                ]),
             ];
             
             let info_widget = Paragraph::new(info_text)
@@ -307,34 +379,14 @@ impl BackendView {
             // CPU usage gauge
             let cpu_gauge = Gauge::default()
                 .block(Block::default().title("CPU Usage"))
-                .gauge_style(
-                    Style::default().fg(
-                        if backend.cpu_usage > 80.0 {
-                            Color::Red
-                        } else if backend.cpu_usage > 50.0 {
-                            Color::Yellow
-                        } else {
-                            Color::Green
-                        }
-                    )
-                )
-                .percent(backend.cpu_usage as u16);
+                .gauge_style(theme.usage_style(backend.cpu_usage))
+                .percent(backend.cpu_usage.0 as u16);
                 
             // Memory usage gauge
             let memory_gauge = Gauge::default()
                 .block(Block::default().title("Memory Usage"))
-                .gauge_style(
-                    Style::default().fg(
-                        if backend.memory_usage > 80.0 {
-                            Color::Red
-                        } else if backend.memory_usage > 50.0 {
-                            Color::Yellow
-                        } else {
-                            Color::Green
-                        }
-                    )
-                )
-                .percent(backend.memory_usage as u16);
+                .gauge_style(theme.usage_style(backend.memory_usage))
+                .percent(backend.memory_usage.0 as u16);
                 
             // Layout for resource gauges
             let resource_chunks = Layout::default()
@@ -349,35 +401,38 @@ impl BackendView {
             frame.render_widget(cpu_gauge, resource_chunks[0]);
             frame.render_widget(memory_gauge, resource_chunks[1]);
             
+            // Capacity handed out vs. requested vs. used by running tasks
+            self.render_allocation(frame, chunks[2], app_state, theme);
+            
             // Backend-specific configuration info
             let config_text = match backend.kind {
                 BackendKind::Docker => vec![
                     Line::from(vec![
-                        Span::styled("Image Handling: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled("Image Handling: ", theme.label_style.add_modifier(Modifier::BOLD)),
                         Span::raw("Native container image support"),
                     ]),
                     Line::from(vec![
-                        Span::styled("Limits: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled("Limits: ", theme.label_style.add_modifier(Modifier::BOLD)),
                         Span::raw("Enforced through cgroups"),
                     ]),
                 ],
                 BackendKind::TES => vec![
                     Line::from(vec![
-                        Span::styled("Endpoint: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled("Endpoint: ", theme.label_style.add_modifier(Modifier::BOLD)),
                         Span::raw("Cloud task execution service"),
                     ]),
                     Line::from(vec![
-                        Span::styled("Features: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled("Features: ", theme.label_style.add_modifier(Modifier::BOLD)),
                         Span::raw("Batch processing, autoscaling"),
                     ]),
                 ],
                 BackendKind::Generic => vec![
                     Line::from(vec![
-                        Span::styled("Executor: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled("Executor: ", theme.label_style.add_modifier(Modifier::BOLD)),
                         Span::raw("Shell commands"),
                     ]),
                     Line::from(vec![
-                        Span::styled("Transport: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled("Transport: ", theme.label_style.add_modifier(Modifier::BOLD)),
                         Span::raw("Local or SSH"),
                     ]),
                 ],
@@ -386,11 +441,11 @@ impl BackendView {
                 ],
                 BackendKind::Local => vec![  // Add this match arm
                     Line::from(vec![
-                        Span::styled("Runtime: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled("Runtime: ", theme.label_style.add_modifier(Modifier::BOLD)),
                         Span::raw("Local machine execution"),
                     ]),
                     Line::from(vec![
-                        Span::styled("Features: ", Style::default().add_modifier(Modifier::BOLD)),
+                        Span::styled("Features: ", theme.label_style.add_modifier(Modifier::BOLD)),
                         Span::raw("Direct file access, native performance"),
                     ]),
                 ],
@@ -400,7 +455,7 @@ impl BackendView {
                 .block(Block::default().borders(Borders::ALL).title("Configuration"))
                 .style(theme.normal_text);
                 
-            frame.render_widget(config_widget, chunks[2]);
+            frame.render_widget(config_widget, chunks[3]);
         } else {
             // Backend not found
             let text = vec![
@@ -418,6 +473,84 @@ impl BackendView {
         }
     }
     
+    /// Render the capacity, requests and usage of running tasks side by side.
+    fn render_allocation(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        app_state: &AppState,
+        theme: &Theme,
+    ) {
+        let Some(backend) = app_state.backends.get(&self.backend_name) else {
+            return;
+        };
+        let allocation = app_state.backend_allocation(&self.backend_name);
+        
+        // Capacity column, and requests as a share of it when known
+        let (cpu_capacity, memory_capacity) = match backend.capacity {
+            Some(capacity) => (capacity.cores.to_string(), capacity.memory.to_string()),
+            None => ("-".to_string(), "-".to_string()),
+        };
+        let cpu_requested = match backend.capacity {
+            Some(capacity) => format!(
+                "{} ({})",
+                allocation.requested_cpu,
+                Percent::of(allocation.requested_cpu.0 as f64, capacity.cores.0 as f64)
+            ),
+            None => allocation.requested_cpu.to_string(),
+        };
+        let memory_requested = match backend.capacity {
+            Some(capacity) => format!(
+                "{} ({})",
+                allocation.requested_memory,
+                Percent::of(allocation.requested_memory.0 as f64, capacity.memory.0 as f64)
+            ),
+            None => allocation.requested_memory.to_string(),
+        };
+        
+        // Low efficiency means tasks ask for far more than they use
+        let cpu_efficiency = allocation.cpu_efficiency();
+        let memory_efficiency = allocation.memory_efficiency();
+        
+        let header = Row::new(["", "Capacity", "Requested", "Used", "Used / Requested"]
+            .iter()
+            .map(|h| Cell::from(*h).style(theme.header_style)));
+        let rows = [
+            Row::new([
+                Cell::from("CPU").style(theme.label_style),
+                Cell::from(cpu_capacity),
+                Cell::from(cpu_requested),
+                Cell::from(allocation.used_cpu.to_string()),
+                Cell::from(cpu_efficiency.to_string()).style(theme.efficiency_style(cpu_efficiency)),
+            ]),
+            Row::new([
+                Cell::from("Memory").style(theme.label_style),
+                Cell::from(memory_capacity),
+                Cell::from(memory_requested),
+                Cell::from(allocation.used_memory.to_string()),
+                Cell::from(memory_efficiency.to_string()).style(theme.efficiency_style(memory_efficiency)),
+            ]),
+        ];
+        
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(8),
+                Constraint::Percentage(20),
+                Constraint::Percentage(30),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+            ],
+        )
+            .header(header)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Allocation ({} running tasks)", allocation.running)))
+            .style(theme.normal_text);
+            
+        frame.render_widget(table, area);
+    }
+    
     /// Render the Tasks tab showing tasks running on this backend.
     fn render_tasks_tab(
         &self,
@@ -438,7 +571,7 @@ impl BackendView {
                 Line::from(""),
                 Line::from(vec![
                     Span::raw("Run tasks with: "),
-                    Span::styled("crankshaft run task.json", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled("crankshaft run task.json", theme.key_style),
                 ]),
             ];
             
@@ -459,14 +592,7 @@ impl BackendView {
         
         // Create task rows
         let rows = tasks.iter().map(|task| {
-            let status_style = match task.status {
-                TaskStatus::Created => Style::default().fg(Color::Blue),
-                TaskStatus::Queued => Style::default().fg(Color::Yellow),
-                TaskStatus::Running => Style::default().fg(Color::Green),
-                TaskStatus::Completed => Style::default().fg(Color::Cyan),
-                TaskStatus::Failed => Style::default().fg(Color::Red),
-                TaskStatus::Cancelled => Style::default().fg(Color::Gray),
-            };
+            let status_style = theme.task_status_style(task.status);
             
             // Create progress bar
             let progress = task.progress.unwrap_or(0.0);
@@ -495,8 +621,8 @@ impl BackendView {
                 Cell::from(task.name.clone()),
                 Cell::from(task.status.to_string()).style(status_style),
                 Cell::from(progress_bar),
-                Cell::from(format!("{:.1}%", task.cpu_usage)),
-                Cell::from(format!("{:.1}MB", task.memory_usage)),
+                Cell::from(task.cpu_usage.to_string()),
+                Cell::from(task.memory_usage.to_string()),
                 Cell::from(duration),
             ])
         });
@@ -544,28 +670,38 @@ impl BackendView {
                 .margin(1)
                 .split(area);
                 
-            // Generate synthetic resource history for the demo
-            // In a real implementation, this would come from the backend
-            let cpu_history = generate_resource_history(30, backend.cpu_usage);
-            let memory_history = generate_resource_history(30, backend.memory_usage);
-            
-            // Convert to f64 for sparkline
-            let cpu_data: Vec<f64> = cpu_history.iter().map(|x| *x as f64).collect();
-            let memory_data: Vec<f64> = memory_history.iter().map(|x| *x as f64).collect();
+            // Recorded samples within the selected window
+            let window = chrono::Duration::minutes(self.resource_time_window as i64);
+            let (resolution, cpu) = backend.resource_history.cpu.window(window);
+            let (_, memory) = backend.resource_history.memory.window(window);
+            let cpu_data: Vec<f64> = cpu.iter().map(|b| b.mean() as f64).collect();
+            let memory_data: Vec<f64> = memory.iter().map(|b| b.mean() as f64).collect();
             
             // CPU usage sparkline
             let cpu_sparkline = Sparkline::new(&cpu_data)
-                .block(Block::default().borders(Borders::ALL).title("CPU Usage (%)"))
-                .style(Style::default().fg(Color::Green))
-                .max(100.0); // Scale to 100%
+                .block(Block::default().borders(Borders::ALL).title(format!(
+                    "CPU Usage ({} avg, last {}m)",
+                    resolution.label(),
+                    self.resource_time_window,
+                )))
+                .style(theme.cpu_style)
+                .min_style(theme.sparkline_min_style)
+                .max_style(theme.sparkline_max_style)
+                .unit(Unit::Percent);
                 
             frame.render_widget(cpu_sparkline, chunks[0]);
             
             // Memory usage sparkline
             let memory_sparkline = Sparkline::new(&memory_data)
-                .block(Block::default().borders(Borders::ALL).title("Memory Usage (%)"))
-                .style(Style::default().fg(Color::Blue))
-                .max(100.0); // Scale to 100%
+                .block(Block::default().borders(Borders::ALL).title(format!(
+                    "Memory Usage ({} avg, last {}m)",
+                    resolution.label(),
+                    self.resource_time_window,
+                )))
+                .style(theme.memory_style)
+                .min_style(theme.sparkline_min_style)
+                .max_style(theme.sparkline_max_style)
+                .unit(Unit::Percent);
                 
             frame.render_widget(memory_sparkline, chunks[1]);
             
@@ -581,37 +717,26 @@ impl BackendView {
             // Use inner area of the block for the charts
             let chart_area = task_history_block.inner(chart_area);
             
-            // Create synthetic task history data
-            let task_data = generate_task_history(backend);
-            let _labels = ["Running", "Completed", "Failed"];
-            
-            
-            let running_data = [
-                ("5m ago", task_data[0][0]),
-                ("4m ago", task_data[0][1]),
-                ("3m ago", task_data[0][2]),
-                ("2m ago", task_data[0][3]),
-                ("1m ago", task_data[0][4]),
-                ("now", task_data[0][5]),
-            ];
-
-            let completed_data = [
-                ("5m ago", task_data[1][0]),
-                ("4m ago", task_data[1][1]),
-                ("3m ago", task_data[1][2]),
-                ("2m ago", task_data[1][3]),
-                ("1m ago", task_data[1][4]),
-                ("now", task_data[1][5]),
-            ];
-
-            let failed_data = [
-                ("5m ago", task_data[2][0]),
-                ("4m ago", task_data[2][1]),
-                ("3m ago", task_data[2][2]),
-                ("2m ago", task_data[2][3]),
-                ("1m ago", task_data[2][4]),
-                ("now", task_data[2][5]),
-            ];
+            // Split the window into slots: peak running tasks and the number
+            // of tasks that completed or failed in each
+            let activity = &backend.task_activity;
+            let labels = slot_labels(window);
+            let running = slot_totals(&activity.running, window, |b| b.max as f64, f64::max);
+            let completed = slot_totals(&activity.completed, window, Bucket::sum, |a, b| a + b);
+            let failed = slot_totals(&activity.failed, window, Bucket::sum, |a, b| a + b);
+            
+            let running_data: Vec<(&str, u64)> = labels.iter()
+                .map(String::as_str)
+                .zip(running)
+                .collect();
+            let completed_data: Vec<(&str, u64)> = labels.iter()
+                .map(String::as_str)
+                .zip(completed)
+                .collect();
+            let failed_data: Vec<(&str, u64)> = labels.iter()
+                .map(String::as_str)
+                .zip(failed)
+                .collect();
 
             // Create three charts and split the chart area into thirds vertically
             let chart_sub_areas = Layout::default()
@@ -627,27 +752,27 @@ impl BackendView {
             let running_chart = BarChart::default()
                 .data(&running_data)
                 .bar_width(9)
-                .bar_style(Style::default().fg(Color::Green))
-                .value_style(Style::default().add_modifier(Modifier::BOLD))
-                .label_style(Style::default().fg(Color::White))
+                .bar_style(theme.running_style)
+                .value_style(theme.value_style.add_modifier(Modifier::BOLD))
+                .label_style(theme.label_style)
                 .bar_gap(2)
                 .block(Block::default().title("Running Tasks"));
 
             let completed_chart = BarChart::default()
                 .data(&completed_data)
                 .bar_width(9)
-                .bar_style(Style::default().fg(Color::Cyan))
-                .value_style(Style::default().add_modifier(Modifier::BOLD))
-                .label_style(Style::default().fg(Color::White))
+                .bar_style(theme.completed_style)
+                .value_style(theme.value_style.add_modifier(Modifier::BOLD))
+                .label_style(theme.label_style)
                 .bar_gap(2)
                 .block(Block::default().title("Completed Tasks"));
 
             let failed_chart = BarChart::default()
                 .data(&failed_data)
                 .bar_width(9)
-                .bar_style(Style::default().fg(Color::Red))
-                .value_style(Style::default().add_modifier(Modifier::BOLD))
-                .label_style(Style::default().fg(Color::White))
+                .bar_style(theme.failed_style)
+                .value_style(theme.value_style.add_modifier(Modifier::BOLD))
+                .label_style(theme.label_style)
                 .bar_gap(2)
                 .block(Block::default().title("Failed Tasks"));
 
@@ -658,9 +783,9 @@ impl BackendView {
             // Help text for adjusting time window
             let help_text = vec![
                 Line::from(vec![
-                    Span::styled("+ ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled("+ ", theme.key_style),
                     Span::raw("Increase time window  "),
-                    Span::styled("- ", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled("- ", theme.key_style),
                     Span::raw("Decrease time window"),
                 ]),
             ];
@@ -694,59 +819,43 @@ impl BackendView {
     }
 }
 
-// Helper function to generate synthetic resource history for demo
-fn generate_resource_history(points: usize, current_value: f32) -> Vec<f32> {
-    let mut history = Vec::with_capacity(points);
-    let mut value = current_value;
+/// Number of bars in the task history charts.
+const TASK_HISTORY_SLOTS: usize = 6;
+
+/// Fold the buckets of a series within the window ending now into equal
+/// slots, oldest first, combining the bucket values that share a slot.
+fn slot_totals(
+    series: &TimeSeries,
+    window: chrono::Duration,
+    value: impl Fn(&Bucket) -> f64,
+    combine: impl Fn(f64, f64) -> f64,
+) -> Vec<u64> {
+    let end = chrono::Utc::now();
+    let start = end - window;
+    let slot_ms = (window.num_milliseconds() / TASK_HISTORY_SLOTS as i64).max(1);
+    let (_, buckets) = series.window_ending(end, window);
     
-    // Work backwards from current value
-    for _ in 0..points {
-        history.push(value);
-        // Random walk with regression to mean
-        let change = rand::thread_rng().gen::<f32>() * 10.0 - 5.0;
-        value = (value + change).clamp(0.0, 100.0);
+    let mut slots = [0.0; TASK_HISTORY_SLOTS];
+    for bucket in &buckets {
+        let offset = (bucket.start - start).num_milliseconds().max(0) / slot_ms;
+        let slot = &mut slots[(offset as usize).min(TASK_HISTORY_SLOTS - 1)];
+        *slot = combine(*slot, value(bucket));
     }
-    
-    history.reverse();
-    history
+    slots.iter().map(|v| v.round() as u64).collect()
 }
 
-// Helper function to generate synthetic task history for demo
-fn generate_task_history(backend: &BackendState) -> [[u64; 6]; 3] {
-    let running = backend.running_tasks as u64;
-    let total = backend.total_tasks as u64;
-    let completed = (total - running) / 2;
-    let failed = total - running - completed;
-    
-    // Generate 6 time points of data
-    [
-        // Running tasks over time
-        [
-            running.saturating_sub(2),
-            running.saturating_sub(1),
-            running,
-            running,
-            running.saturating_add(1),
-            running,
-        ],
-        // Completed tasks over time
-        [
-            completed.saturating_sub(3),
-            completed.saturating_sub(2),
-            completed.saturating_sub(1),
-            completed,
-            completed,
-            completed.saturating_add(1),
-        ],
-        // Failed tasks over time
-        [
-            failed,
-            failed,
-            failed,
-            failed.saturating_add(1),
-            failed.saturating_add(1),
-            failed.saturating_add(1),
-        ],
-    ]
+/// Labels for the slots of a window, giving how long ago each slot ended.
+fn slot_labels(window: chrono::Duration) -> Vec<String> {
+    let slot_secs = window.num_seconds() / TASK_HISTORY_SLOTS as i64;
+    (1..=TASK_HISTORY_SLOTS as i64)
+        .map(|i| {
+            let ago = slot_secs * (TASK_HISTORY_SLOTS as i64 - i);
+            match ago {
+                0 => "now".to_string(),
+                s if s < 60 => format!("{}s ago", s),
+                s if s < 3600 => format!("{}m ago", s / 60),
+                s => format!("{}h ago", s / 3600),
+            }
+        })
+        .collect()
 }
-
@@ -0,0 +1,416 @@
+//! Workflow DAG view of task dependencies.
+//!
+//! Draws every task with upstream or downstream tasks as a node, one column
+//! per layer of the graph, colored by status, with the critical path in
+//! bold. Gather nodes, standing for every task of a call, are drawn as ◆.
+//! The view follows the selected node and can be panned, zoomed and
+//! narrowed to the selected node's upstream and downstream tasks.
+
+use std::collections::{HashMap, HashSet};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use eyre::Result;
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::state::{AppState, Dag};
+use crate::ui::Theme;
+use crate::ui::task_list::format_duration;
+
+/// Node width in cells at each zoom level.
+const NODE_WIDTHS: [i32; 3] = [1, 14, 26];
+/// Columns between layers at each zoom level, leaving room for edges.
+const LAYER_GAPS: [i32; 3] = [3, 4, 6];
+/// Rows per node at each zoom level.
+const ROW_HEIGHTS: [i32; 3] = [1, 2, 2];
+/// Cells moved per pan step, horizontally and vertically.
+const PAN_STEP: (i32, i32) = (8, 3);
+
+/// Directions a line leaves a cell in.
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+/// Box-drawing symbol for each combination of directions, so that edges
+/// crossing or meeting in a cell join up.
+const BOX_ARMS: [(&str, u8); 11] = [
+    ("─", LEFT | RIGHT),
+    ("│", UP | DOWN),
+    ("┐", LEFT | DOWN),
+    ("┘", LEFT | UP),
+    ("┌", RIGHT | DOWN),
+    ("└", RIGHT | UP),
+    ("┬", LEFT | RIGHT | DOWN),
+    ("┴", LEFT | RIGHT | UP),
+    ("├", UP | DOWN | RIGHT),
+    ("┤", UP | DOWN | LEFT),
+    ("┼", UP | DOWN | LEFT | RIGHT),
+];
+
+/// DAG view state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DagView {
+    /// Selected task, defaulting to the start of the critical path
+    selected: Option<u64>,
+    /// Offset of the view from the selected node, in cells
+    pan: (i32, i32),
+    /// Index into the zoom level tables
+    zoom: usize,
+    /// Dim everything outside the selected task's lineage
+    focus: bool,
+}
+
+impl Default for DagView {
+    fn default() -> Self {
+        Self {
+            selected: None,
+            pan: (0, 0),
+            zoom: 1,
+            focus: false,
+        }
+    }
+}
+
+impl DagView {
+    /// Create a new DAG view.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The selected task, if it is still in the graph.
+    pub fn selected_task(&self, dag: &Dag) -> Option<u64> {
+        self.selected
+            .filter(|id| dag.position(*id).is_some())
+            .or_else(|| dag.critical_path.first().copied())
+            .or_else(|| dag.layers.first().and_then(|layer| layer.first()).copied())
+    }
+
+    /// Handle key events for this view.
+    pub fn handle_key_event(&mut self, key: KeyEvent, app_state: &AppState) -> Result<()> {
+        let dag = app_state.dag();
+        let Some(current) = self.selected_task(&dag) else {
+            return Ok(());
+        };
+
+        if key.modifiers.contains(KeyModifiers::SHIFT) {
+            let (dx, dy) = match key.code {
+                KeyCode::Left => (-PAN_STEP.0, 0),
+                KeyCode::Right => (PAN_STEP.0, 0),
+                KeyCode::Up => (0, -PAN_STEP.1),
+                KeyCode::Down => (0, PAN_STEP.1),
+                _ => (0, 0),
+            };
+            // Panning further than the graph reaches has no effect
+            let reach_x = dag.layers.len() as i32 * (NODE_WIDTHS[self.zoom] + LAYER_GAPS[self.zoom]);
+            let reach_y = dag.layers.iter().map(Vec::len).max().unwrap_or(0) as i32 * ROW_HEIGHTS[self.zoom];
+            self.pan = (
+                (self.pan.0 + dx).clamp(-reach_x, reach_x),
+                (self.pan.1 + dy).clamp(-reach_y, reach_y),
+            );
+            return Ok(());
+        }
+
+        let next = match key.code {
+            KeyCode::Left | KeyCode::Char('h') => linked(&dag, current, &dag.parents, -1),
+            KeyCode::Right | KeyCode::Char('l') => linked(&dag, current, &dag.children, 1),
+            KeyCode::Up | KeyCode::Char('k') => in_layer(&dag, current, -1),
+            KeyCode::Down | KeyCode::Char('j') => in_layer(&dag, current, 1),
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                self.zoom = (self.zoom + 1).min(NODE_WIDTHS.len() - 1);
+                None
+            }
+            KeyCode::Char('-') => {
+                self.zoom = self.zoom.saturating_sub(1);
+                None
+            }
+            KeyCode::Char('f') => {
+                self.focus = !self.focus;
+                None
+            }
+            KeyCode::Char('c') => {
+                self.pan = (0, 0);
+                None
+            }
+            _ => None,
+        };
+
+        if let Some(next) = next {
+            self.selected = Some(next);
+            self.pan = (0, 0);
+        }
+
+        Ok(())
+    }
+
+    /// Render the DAG view.
+    pub fn render(&self, frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let dag = app_state.dag();
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),  // Header
+                Constraint::Min(3),     // Graph
+                Constraint::Length(3),  // Selected task
+            ])
+            .split(area);
+
+        self.render_header(frame, chunks[0], theme);
+
+        let title = format!(
+            "{} tasks, {} dependencies, {} levels | critical path: {} tasks, {}",
+            dag.task_count(),
+            dag.edge_count(),
+            dag.layers.len(),
+            dag.critical_path.iter().filter(|id| !dag.is_gather(**id)).count(),
+            format_duration(&dag.critical_time),
+        );
+        let block = Block::default().borders(Borders::ALL).title(title).style(theme.block_style);
+        let inner = block.inner(chunks[1]);
+        frame.render_widget(block, chunks[1]);
+
+        if dag.is_empty() {
+            let empty = Paragraph::new(vec![
+                Line::from(Span::styled("No task dependencies reported", theme.help_style)),
+                Line::from(Span::styled(
+                    "Dependencies come from the engine or from a workflow description set with `workflow_file`",
+                    theme.help_style,
+                )),
+            ]);
+            frame.render_widget(empty, inner);
+        } else {
+            self.render_graph(frame.buffer_mut(), inner, &dag, app_state, theme);
+        }
+
+        self.render_selected(frame, chunks[2], &dag, app_state, theme);
+    }
+
+    /// Render the header with the view settings and key hints.
+    fn render_header(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let zoom = ["dots", "short names", "full names"][self.zoom];
+        let header_text = Line::from(vec![
+            Span::styled("Workflow DAG", theme.header_style),
+            Span::raw(" | "),
+            Span::styled(
+                format!("Zoom: {} | Focus: {}", zoom, if self.focus { "on" } else { "off" }),
+                theme.label_style,
+            ),
+            Span::raw(" | "),
+            Span::styled(
+                "arrows: select, Shift+arrows: pan, c: center, +/-: zoom, f: focus, Enter: details",
+                theme.help_style,
+            ),
+        ]);
+
+        let header = Paragraph::new(header_text)
+            .style(theme.normal_text)
+            .block(Block::default().borders(Borders::BOTTOM));
+
+        frame.render_widget(header, area);
+    }
+
+    /// Draw edges and nodes, centered on the selected node plus the pan.
+    fn render_graph(&self, buf: &mut Buffer, area: Rect, dag: &Dag, app_state: &AppState, theme: &Theme) {
+        let (width, gap, height) = (NODE_WIDTHS[self.zoom], LAYER_GAPS[self.zoom], ROW_HEIGHTS[self.zoom]);
+        let place = |id: u64| {
+            let (layer, index) = dag.position(id).unwrap_or_default();
+            (layer as i32 * (width + gap), index as i32 * height)
+        };
+
+        // Keep the view inside the graph where it fits
+        let graph_width = dag.layers.len() as i32 * (width + gap) - gap;
+        let graph_height = dag.layers.iter().map(Vec::len).max().unwrap_or(0) as i32 * height;
+        let selected = self.selected_task(dag);
+        let (sx, sy) = selected.map_or((0, 0), place);
+        let clamp = |origin: i32, size: i32, view: u16| origin.min(size - view as i32).max(0);
+        let origin = (
+            clamp(sx + width / 2 + self.pan.0 - area.width as i32 / 2, graph_width, area.width),
+            clamp(sy + self.pan.1 - area.height as i32 / 2, graph_height, area.height),
+        );
+        let mut canvas = Canvas { buf, area, origin };
+
+        let lineage = match (self.focus, selected) {
+            (true, Some(id)) => Some(dag.lineage(id)),
+            _ => None,
+        };
+        let dimmed = |id: &u64| lineage.as_ref().is_some_and(|l| !l.contains(id));
+        let critical: HashSet<(u64, u64)> = dag.critical_path.windows(2).map(|w| (w[0], w[1])).collect();
+
+        // Edges first, critical ones last so they stay on top
+        let mut edges: Vec<(u64, u64)> = dag.children.iter()
+            .flat_map(|(&from, to)| to.iter().map(move |&to| (from, to)))
+            .collect();
+        edges.sort_by_key(|edge| (critical.contains(edge), *edge));
+        for (from, to) in edges {
+            let style = if critical.contains(&(from, to)) {
+                theme.critical_style
+            } else if dimmed(&from) || dimmed(&to) {
+                theme.empty_style
+            } else {
+                theme.help_style
+            };
+            let (fx, fy) = place(from);
+            let (tx, ty) = place(to);
+            canvas.edge((fx + width, fy), (tx, ty), style);
+        }
+
+        for id in dag.layers.iter().flatten() {
+            let (name, status_style) = match (dag.gathers.get(id), app_state.tasks.get(id)) {
+                (Some(call), _) => (call.as_str(), theme.label_style),
+                (None, Some(task)) => (task.name.as_str(), theme.task_status_style(task.status)),
+                (None, None) => continue,
+            };
+            let mut style = if dimmed(id) {
+                theme.empty_style
+            } else {
+                status_style
+            };
+            if dag.critical_path.contains(id) {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            if Some(*id) == selected {
+                style = theme.selected_style;
+            }
+            let label = match (self.zoom, dag.is_gather(*id)) {
+                (0, true) => "◆".to_string(),
+                (0, false) => "●".to_string(),
+                (_, gather) => {
+                    let name: String = name.chars().take(width as usize - 2).collect();
+                    let (open, close) = if gather { ('◆', ' ') } else { ('[', ']') };
+                    format!("{}{:<w$}{}", open, name, close, w = width as usize - 2)
+                }
+            };
+            let (x, y) = place(*id);
+            canvas.text(x, y, &label, style);
+        }
+    }
+
+    /// Render the selected task's status and links.
+    fn render_selected(&self, frame: &mut Frame, area: Rect, dag: &Dag, app_state: &AppState, theme: &Theme) {
+        let task = self.selected_task(dag).and_then(|id| app_state.tasks.get(&id));
+        let line = match task {
+            Some(task) => {
+                let mut spans = vec![
+                    Span::styled(format!("#{} {}", task.id, task.name), theme.value_style),
+                    Span::raw(" | "),
+                    Span::styled(task.status.to_string(), theme.task_status_style(task.status)),
+                    Span::raw(" | "),
+                    Span::raw(format!(
+                        "Run time: {} | Upstream: {} | Downstream: {}",
+                        task.run_time().map_or_else(|| "-".to_string(), |d| format_duration(&d)),
+                        dag.parents.get(&task.id).map_or(0, Vec::len),
+                        dag.children.get(&task.id).map_or(0, Vec::len),
+                    )),
+                ];
+                if dag.critical_path.contains(&task.id) {
+                    spans.push(Span::raw(" | "));
+                    spans.push(Span::styled("On critical path", theme.critical_style));
+                }
+                Line::from(spans)
+            }
+            None => match self.selected_task(dag).and_then(|id| dag.gathers.get(&id).map(|call| (id, call))) {
+                Some((id, call)) => Line::from(vec![
+                    Span::styled(format!("All {} tasks", call), theme.value_style),
+                    Span::raw(" | "),
+                    Span::raw(format!(
+                        "Gathers {} upstream tasks for {} downstream tasks",
+                        dag.parents.get(&id).map_or(0, Vec::len),
+                        dag.children.get(&id).map_or(0, Vec::len),
+                    )),
+                ]),
+                None => Line::from(Span::styled("No task selected", theme.help_style)),
+            },
+        };
+
+        let selected = Paragraph::new(line)
+            .block(Block::default().borders(Borders::ALL).title("Selected Task"))
+            .style(theme.normal_text);
+        frame.render_widget(selected, area);
+    }
+}
+
+/// The upstream or downstream task to move to: the one on the critical
+/// path if any, else the topmost, else the nearest task in the adjacent
+/// layer.
+fn linked(dag: &Dag, current: u64, edges: &HashMap<u64, Vec<u64>>, step: isize) -> Option<u64> {
+    let linked = edges.get(&current).map(Vec::as_slice).unwrap_or_default();
+    if let Some(&id) = linked.iter().find(|id| dag.critical_path.contains(id)) {
+        return Some(id);
+    }
+    if let Some(&id) = linked.iter().min_by_key(|id| dag.position(**id)) {
+        return Some(id);
+    }
+    let (layer, index) = dag.position(current)?;
+    let layer = dag.layers.get(layer.checked_add_signed(step)?)?;
+    layer.get(index.min(layer.len() - 1)).copied()
+}
+
+/// The task above or below `current` in its layer.
+fn in_layer(dag: &Dag, current: u64, step: isize) -> Option<u64> {
+    let (layer, index) = dag.position(current)?;
+    dag.layers[layer].get(index.checked_add_signed(step)?).copied()
+}
+
+/// Buffer area showing the graph from `origin`, in graph cells.
+struct Canvas<'a> {
+    buf: &'a mut Buffer,
+    area: Rect,
+    origin: (i32, i32),
+}
+
+impl Canvas<'_> {
+    /// Set one graph cell, if it is in view.
+    fn put(&mut self, x: i32, y: i32, symbol: &str, style: Style) {
+        let (x, y) = (x - self.origin.0, y - self.origin.1);
+        if (0..self.area.width as i32).contains(&x) && (0..self.area.height as i32).contains(&y) {
+            self.buf
+                .get_mut(self.area.x + x as u16, self.area.y + y as u16)
+                .set_symbol(symbol)
+                .set_style(style);
+        }
+    }
+
+    /// Write text starting at a graph cell.
+    fn text(&mut self, x: i32, y: i32, text: &str, style: Style) {
+        for (i, c) in text.chars().enumerate() {
+            self.put(x + i as i32, y, c.encode_utf8(&mut [0; 4]), style);
+        }
+    }
+
+    /// Draw a line segment, joining it with any line already in the cell.
+    fn line(&mut self, x: i32, y: i32, arms: u8, style: Style) {
+        let (cx, cy) = (x - self.origin.0, y - self.origin.1);
+        if !(0..self.area.width as i32).contains(&cx) || !(0..self.area.height as i32).contains(&cy) {
+            return;
+        }
+        let existing = self.buf.get(self.area.x + cx as u16, self.area.y + cy as u16).symbol();
+        let arms = arms | BOX_ARMS.iter().find(|(s, _)| *s == existing).map_or(0, |(_, a)| *a);
+        if let Some((symbol, _)) = BOX_ARMS.iter().find(|(_, a)| *a == arms) {
+            self.put(x, y, symbol, style);
+        }
+    }
+
+    /// Draw an edge from the right of one node to the left of another:
+    /// across, then up or down just before the target, then into it.
+    fn edge(&mut self, from: (i32, i32), to: (i32, i32), style: Style) {
+        let bend = to.0 - 2;
+        for x in from.0..bend {
+            self.line(x, from.1, LEFT | RIGHT, style);
+        }
+        if from.1 == to.1 {
+            self.line(bend, to.1, LEFT | RIGHT, style);
+        } else {
+            let down = to.1 > from.1;
+            self.line(bend, from.1, LEFT | if down { DOWN } else { UP }, style);
+            for y in from.1.min(to.1) + 1..from.1.max(to.1) {
+                self.line(bend, y, UP | DOWN, style);
+            }
+            self.line(bend, to.1, RIGHT | if down { UP } else { DOWN }, style);
+        }
+        self.put(to.0 - 1, to.1, "▶", style);
+    }
+}
@@ -16,14 +16,25 @@
 use std::collections::HashMap;
 use ratatui::Frame;
 use ratatui::layout::{Layout, Constraint, Direction, Rect};
-use ratatui::style::{Color, Style, Modifier};
+use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Table, Row, Cell, 
                        TableState, BarChart, List, ListItem, Wrap};
 
-use crate::state::{AppState, TaskStatus, HealthStatus, Temporality};
-use crate::ui::Theme;
+use crate::state::{AppState, BatchForecast, UtilizationMetric, RateWindow, Rates, StallReason, Straggler, TaskStatus, HealthStatus, Temporality, Throughput, Unit};
+use crate::ui::{BackendView, Theme};
+use crate::ui::task_list::format_duration;
 use crate::ui::widgets::sparkline::Sparkline as CustomSparkline;
+use crate::ui::widgets::stat_panel::{StatPanel, StatStyles, StatValue, Trend};
+
+/// Time window of the dashboard resource charts, in minutes.
+const RESOURCE_WINDOW_MINUTES: i64 = 10;
+
+/// Minutes of history in the throughput sparklines.
+const THROUGHPUT_MINUTES: usize = 60;
+
+/// Most stalled tasks listed on the dashboard.
+const MAX_STRAGGLER_ROWS: usize = 5;
 
 /// Dashboard view showing an overview of all tasks and backends.
 pub struct DashboardView;
@@ -37,6 +48,7 @@ impl DashboardView {
         frame: &mut Frame,  // Updated: removed <B> generic parameter
         area: Rect,
         app_state: &AppState,
+        metric: UtilizationMetric,
         theme: &Theme,
     ) {
         // Determine the best layout based on terminal size (inspired by tokio-console's adaptive layout)
@@ -56,7 +68,7 @@ impl DashboardView {
         Self::render_task_summary(frame, chunks[0], app_state, theme);
         
         // Right/bottom section: Backend summary and events
-        Self::render_backend_summary(frame, chunks[1], app_state, theme);
+        Self::render_backend_summary(frame, chunks[1], app_state, metric, theme);
     }
     
     /// Render task summary section.
@@ -66,13 +78,18 @@ impl DashboardView {
         app_state: &AppState,
         theme: &Theme,
     ) {
+        let stragglers = Straggler::find(app_state, chrono::Utc::now());
+        let straggler_height = stragglers.len().clamp(1, MAX_STRAGGLER_ROWS) as u16 + 3;
+        
         // Divide the area into sections
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(7),  // Task status summary
-                Constraint::Length(10), // Resource usage
-                Constraint::Min(0),     // Recent tasks
+                Constraint::Length(7),                 // Task status summary
+                Constraint::Length(10),                // Resource usage
+                Constraint::Length(9),                 // Throughput
+                Constraint::Length(straggler_height),  // Stragglers
+                Constraint::Min(0),                    // Recent tasks
             ])
             .split(area);
             
@@ -82,8 +99,14 @@ impl DashboardView {
         // Render the resource usage
         Self::render_resource_usage(frame, chunks[1], app_state, theme);
         
+        // Render the throughput and failure rate
+        Self::render_throughput(frame, chunks[2], app_state, theme);
+        
+        // Render the stalled and straggling tasks
+        Self::render_stragglers(frame, chunks[3], &stragglers, theme);
+        
         // Render the recent tasks
-        Self::render_recent_tasks(frame, chunks[2], app_state, theme);
+        Self::render_recent_tasks(frame, chunks[4], app_state, theme);
     }
     
     /// Render backend summary section.
@@ -91,22 +114,52 @@ impl DashboardView {
         frame: &mut Frame,
         area: Rect,
         app_state: &AppState,
+        metric: UtilizationMetric,
         theme: &Theme,
     ) {
         // Divide the area into sections
+        let queue_height = app_state.backends.len() as u16 + 3;
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Percentage(60), // Backend table
-                Constraint::Percentage(40), // Events
+                Constraint::Percentage(35),         // Backend table
+                Constraint::Length(queue_height),   // Queue wait
+                Constraint::Length(queue_height),   // Utilization heatmap
+                Constraint::Min(0),                 // Events
             ])
             .split(area);
             
         // Render the backend table
         Self::render_backend_table(frame, chunks[0], app_state, theme);
         
+        // Render the queue wait percentiles
+        Self::render_queue_wait(frame, chunks[1], app_state, theme);
+        
+        // Render the backend utilization heatmap
+        BackendView::render_heatmap(frame, chunks[2], app_state, metric, RESOURCE_WINDOW_MINUTES, theme);
+        
         // Render the events
-        Self::render_events(frame, chunks[1], app_state, theme);
+        Self::render_events(frame, chunks[3], app_state, theme);
+    }
+    
+    /// Batch ETA with its confidence range, or a placeholder while there is
+    /// nothing to forecast.
+    fn forecast_line<'a>(app_state: &AppState, theme: &Theme) -> Line<'a> {
+        let label = Span::styled("Batch ETA: ", theme.label_style.add_modifier(Modifier::BOLD));
+        let Some(forecast) = BatchForecast::from_state(app_state, chrono::Utc::now()) else {
+            return Line::from(vec![label, Span::styled("-", theme.help_style)]);
+        };
+
+        let latest = forecast.latest
+            .map_or_else(|| "?".to_string(), |d| format_duration(&d));
+        Line::from(vec![
+            label,
+            Span::styled(format_duration(&forecast.eta), theme.value_style),
+            Span::styled(
+                format!(" ({} - {})", format_duration(&forecast.earliest), latest),
+                theme.help_style,
+            ),
+        ])
     }
     
     /// Render task status summary.
@@ -135,50 +188,50 @@ impl DashboardView {
         // Create status summary text with enhanced colors
         let text = vec![
             Line::from(vec![
-                Span::styled("Total Tasks: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("Total Tasks: ", theme.label_style.add_modifier(Modifier::BOLD)),
                 Span::raw(format!("{}", total_tasks)),
             ]),
-            Line::from(""),
+            Self::forecast_line(app_state, theme),
             Line::from(vec![
-                Span::styled("Created: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("Created: ", theme.label_style.add_modifier(Modifier::BOLD)),
                 Span::styled(
                     format!("{}", status_counts[&TaskStatus::Created]),
-                    Style::default().fg(Color::LightBlue)
+                    theme.task_status_style(TaskStatus::Created)
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Queued: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("Queued: ", theme.label_style.add_modifier(Modifier::BOLD)),
                 Span::styled(
                     format!("{}", status_counts[&TaskStatus::Queued]),
-                    Style::default().fg(Color::LightYellow)
+                    theme.task_status_style(TaskStatus::Queued)
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Running: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("Running: ", theme.label_style.add_modifier(Modifier::BOLD)),
                 Span::styled(
                     format!("{}", status_counts[&TaskStatus::Running]),
-                    Style::default().fg(Color::LightGreen)
+                    theme.task_status_style(TaskStatus::Running)
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Completed: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("Completed: ", theme.label_style.add_modifier(Modifier::BOLD)),
                 Span::styled(
                     format!("{}", status_counts[&TaskStatus::Completed]),
-                    Style::default().fg(Color::LightCyan)
+                    theme.task_status_style(TaskStatus::Completed)
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Failed: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("Failed: ", theme.label_style.add_modifier(Modifier::BOLD)),
                 Span::styled(
                     format!("{}", status_counts[&TaskStatus::Failed]),
-                    Style::default().fg(Color::LightRed)
+                    theme.task_status_style(TaskStatus::Failed)
                 ),
             ]),
             Line::from(vec![
-                Span::styled("Cancelled: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled("Cancelled: ", theme.label_style.add_modifier(Modifier::BOLD)),
                 Span::styled(
                     format!("{}", status_counts[&TaskStatus::Cancelled]),
-                    Style::default().fg(Color::LightMagenta)
+                    theme.task_status_style(TaskStatus::Cancelled)
                 ),
             ]),
         ];
@@ -194,8 +247,8 @@ impl DashboardView {
         let inner_chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
-                Constraint::Percentage(40),
-                Constraint::Percentage(60),
+                Constraint::Percentage(50),
+                Constraint::Percentage(50),
             ])
             .split(area.inner(&ratatui::layout::Margin { 
                 vertical: 1, 
@@ -212,9 +265,9 @@ impl DashboardView {
         let bars = BarChart::default()
             .data(&bars_data)
             .bar_width(7)
-            .bar_style(Style::default().fg(Color::Green))
-            .value_style(Style::default().add_modifier(Modifier::BOLD))
-            .label_style(Style::default().fg(Color::White));
+            .bar_style(theme.sparkline_style)
+            .value_style(theme.value_style.add_modifier(Modifier::BOLD))
+            .label_style(theme.label_style);
             
         frame.render_widget(bars, inner_chunks[1]);
         
@@ -235,16 +288,11 @@ impl DashboardView {
         app_state: &AppState,
         theme: &Theme,
     ) {
-        // Get resource data from state
-        let cpu_data: Vec<f64> = app_state.resources.cpu_history
-            .iter()
-            .map(|p| p.value as f64)
-            .collect();
-            
-        let memory_data: Vec<f64> = app_state.resources.memory_history
-            .iter()
-            .map(|p| p.value as f64)
-            .collect();
+        // Cluster utilization, weighted by backend capacity
+        let resources = &app_state.resources;
+        let window = chrono::Duration::minutes(RESOURCE_WINDOW_MINUTES);
+        let cpu_data = resources.history.cpu.means(window);
+        let memory_data = resources.history.memory.means(window);
             
         // Create inner layout
         let chunks = Layout::default()
@@ -262,20 +310,35 @@ impl DashboardView {
         // Render CPU sparkline
         let cpu_sparkline = CustomSparkline::new(&cpu_data)
             .block(Block::default()
-                .title("CPU Usage (%)")
+                .title(format!(
+                    "Cluster CPU {} (last {}m) · {} running tasks using {}",
+                    resources.cpu_current,
+                    RESOURCE_WINDOW_MINUTES,
+                    resources.running_tasks,
+                    resources.task_cpu_current,
+                ))
                 .borders(Borders::TOP | Borders::LEFT | Borders::RIGHT))
-            .style(Style::default().fg(Color::Green))
-            .max(100.0);
+            .style(theme.cpu_style)
+            .min_style(theme.sparkline_min_style)
+            .max_style(theme.sparkline_max_style)
+            .unit(Unit::Percent);
             
         frame.render_widget(cpu_sparkline, chunks[0]);
         
         // Render Memory sparkline
         let memory_sparkline = CustomSparkline::new(&memory_data)
             .block(Block::default()
-                .title("Memory Usage (%)")
+                .title(format!(
+                    "Cluster Memory {} (last {}m) · tasks using {}",
+                    resources.memory_current,
+                    RESOURCE_WINDOW_MINUTES,
+                    resources.task_memory_current,
+                ))
                 .borders(Borders::BOTTOM | Borders::LEFT | Borders::RIGHT))
-            .style(Style::default().fg(Color::Blue))
-            .max(100.0);
+            .style(theme.memory_style)
+            .min_style(theme.sparkline_min_style)
+            .max_style(theme.sparkline_max_style)
+            .unit(Unit::Percent);
             
         frame.render_widget(memory_sparkline, chunks[1]);
         
@@ -283,12 +346,101 @@ impl DashboardView {
         frame.render_widget(
             Block::default()
                 .borders(Borders::ALL)
-                .title("Resource Usage")
+                .title("Cluster Resources")
                 .style(theme.block_style),
             area,
         );
     }
     
+    /// Render task start, completion and failure rates per window, and
+    /// their last hour as sparklines.
+    fn render_throughput(
+        frame: &mut Frame,
+        area: Rect,
+        app_state: &AppState,
+        theme: &Theme,
+    ) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title("Throughput")
+            .style(theme.block_style);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),  // Rates per window
+                Constraint::Min(0),     // Sparklines
+            ])
+            .split(inner);
+        
+        // One stat column per window, each trending against the next longer one
+        let now = chrono::Utc::now();
+        let throughput = &app_state.throughput;
+        let rates: Vec<Rates> = RateWindow::ALL.iter().map(|w| throughput.rates(*w, now)).collect();
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Ratio(1, 3), Constraint::Ratio(1, 3), Constraint::Ratio(1, 3)])
+            .split(rows[0]);
+        for (i, window) in RateWindow::ALL.iter().enumerate() {
+            let baseline = rates.get(i + 1);
+            let rate = rates[i];
+            let failure = rate.failure_rate.map_or(0.0, |p| p.0 as f64);
+            let panel = StatPanel::new()
+                .block(Block::default().title(window.label()).title_style(theme.header_style))
+                .label_style(theme.label_style)
+                .value_styles(StatStyles {
+                    healthy: theme.healthy_style,
+                    warning: theme.warning_style,
+                    critical: theme.critical_style,
+                    muted: theme.help_style,
+                })
+                .right_align(true)
+                .spacing(1)
+                .stat("Started/min", StatValue::new(format!("{:.1}", rate.started))
+                    .trend(baseline.map_or(Trend::None, |b| trend(rate.started, b.started))))
+                .stat("Done/min", StatValue::new(format!("{:.1}", rate.completed))
+                    .trend(baseline.map_or(Trend::None, |b| trend(rate.completed, b.completed))))
+                .stat("Failed", StatValue::with_unit(failure, Unit::Percent)
+                    .warn_at(10.0)
+                    .critical_at(25.0)
+                    .lower_is_better()
+                    .trend(baseline.map_or(Trend::None, |b| {
+                        trend(failure, b.failure_rate.map_or(0.0, |p| p.0 as f64))
+                    })));
+            frame.render_widget(panel, columns[i]);
+        }
+        
+        // Last hour per minute
+        let started = Throughput::per_minute(&throughput.started, now, THROUGHPUT_MINUTES);
+        let completed = Throughput::per_minute(&throughput.completed, now, THROUGHPUT_MINUTES);
+        let failure = throughput.failure_rate_per_minute(now, THROUGHPUT_MINUTES);
+        let lines = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Length(1), Constraint::Length(1)])
+            .split(rows[1]);
+        for (line, (label, data, style, unit)) in lines.iter().zip([
+            ("Started  ", &started, theme.sparkline_style, None),
+            ("Done     ", &completed, theme.completed_style, None),
+            ("Failed % ", &failure, theme.failed_style, Some(Unit::Percent)),
+        ]) {
+            let cells = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Length(9), Constraint::Min(0)])
+                .split(*line);
+            frame.render_widget(Paragraph::new(Span::styled(label, theme.label_style)), cells[0]);
+            let mut sparkline = CustomSparkline::new(data)
+                .style(style)
+                .min_style(style)
+                .max_style(style);
+            if let Some(unit) = unit {
+                sparkline = sparkline.unit(unit);
+            }
+            frame.render_widget(sparkline, cells[1]);
+        }
+    }
+    
     /// Render recent tasks list.
     fn render_recent_tasks(
         frame: &mut Frame,
@@ -307,19 +459,12 @@ impl DashboardView {
         let items: Vec<ListItem> = recent_tasks
             .map(|task| {
                 // Format the task item with status color
-                let status_style = match task.status {
-                    TaskStatus::Created => Style::default().fg(Color::Blue),
-                    TaskStatus::Queued => Style::default().fg(Color::Yellow),
-                    TaskStatus::Running => Style::default().fg(Color::Green),
-                    TaskStatus::Completed => Style::default().fg(Color::Cyan),
-                    TaskStatus::Failed => Style::default().fg(Color::Red),
-                    TaskStatus::Cancelled => Style::default().fg(Color::Gray),
-                };
+                let status_style = theme.task_status_style(task.status);
                 
                 // Create a formatted line for the task
                 let line = Line::from(vec![
                     Span::styled(format!("[{}] ", task.status.to_string()), status_style),
-                    Span::styled(task.name.clone(), Style::default().add_modifier(Modifier::BOLD)),
+                    Span::styled(task.name.clone(), theme.value_style.add_modifier(Modifier::BOLD)),
                     Span::raw(format!(" (ID: {})", task.id)),
                 ]);
                 
@@ -345,13 +490,18 @@ impl DashboardView {
         theme: &Theme,
     ) {
         // Create header row
-        let header = ["Name", "Type", "Tasks", "Status", "Utilization"]
+        let header = ["Name", "Type", "Tasks", "Status", "Utilization", "CPU", "Memory", "Share"]
             .iter()
             .map(|h| Cell::from(*h).style(theme.header_style));
             
         let header = Row::new(header)
             .style(theme.header_style);
             
+        // Each backend's share of cluster capacity
+        let total_weight: f32 = app_state.backends.values()
+            .map(|b| b.capacity_weight())
+            .sum();
+        
         // Create rows
         let rows = app_state.backends.values().map(|backend| {
             // Style based on health status
@@ -379,6 +529,11 @@ impl DashboardView {
                 Cell::from(format!("{}/{}", backend.running_tasks, backend.total_tasks)),
                 Cell::from(backend.health.to_string()).style(status_style),
                 Cell::from(bar),
+                Cell::from(backend.cpu_usage.to_string())
+                    .style(theme.usage_style(backend.cpu_usage)),
+                Cell::from(backend.memory_usage.to_string())
+                    .style(theme.usage_style(backend.memory_usage)),
+                Cell::from(format!("{:.0}%", backend.capacity_weight() / total_weight.max(1.0) * 100.0)),
             ])
         });
         
@@ -386,11 +541,14 @@ impl DashboardView {
         let table = Table::new(
             rows,
             &[
-                Constraint::Percentage(20),  // Name
-                Constraint::Percentage(15),  // Type
-                Constraint::Percentage(15),  // Tasks
-                Constraint::Percentage(15),  // Status
-                Constraint::Percentage(35),  // Utilization
+                Constraint::Percentage(17),  // Name
+                Constraint::Percentage(11),  // Type
+                Constraint::Percentage(10),  // Tasks
+                Constraint::Percentage(11),  // Status
+                Constraint::Percentage(24),  // Utilization
+                Constraint::Percentage(9),   // CPU
+                Constraint::Percentage(10),  // Memory
+                Constraint::Percentage(8),   // Share
             ]
         )
             .header(header)
@@ -412,6 +570,121 @@ impl DashboardView {
         frame.render_stateful_widget(table, area, &mut state);
     }
     
+    /// Render queue wait percentiles next to run times for each backend, to
+    /// tell scheduler delays from slow work.
+    fn render_queue_wait(
+        frame: &mut Frame,
+        area: Rect,
+        app_state: &AppState,
+        theme: &Theme,
+    ) {
+        let header = Row::new(["Backend", "Waited", "Wait p50", "p90", "p99", "Run p50", "Queued", "Oldest"]
+            .iter()
+            .map(|h| Cell::from(*h).style(theme.header_style)));
+        
+        let seconds = |secs: f64| format_duration(&chrono::Duration::milliseconds((secs * 1000.0) as i64));
+        
+        let mut backends: Vec<_> = app_state.backends.keys().collect();
+        backends.sort();
+        let rows = backends.into_iter().map(|name| {
+            let waits = app_state.queue_wait_percentiles(name);
+            let runs = app_state.run_time_percentiles(name);
+            
+            // Tasks still waiting, and the longest current wait
+            let queued: Vec<chrono::Duration> = app_state.tasks.values()
+                .filter(|t| &t.backend == name && t.status == TaskStatus::Queued)
+                .filter_map(|t| t.queue_wait())
+                .collect();
+            let longest = queued.iter().max().map_or_else(|| "-".to_string(), format_duration);
+            
+            let (count, p50, p90, p99) = match waits {
+                Some(w) => (w.count.to_string(), seconds(w.p50), seconds(w.p90), seconds(w.p99)),
+                None => ("0".to_string(), "-".to_string(), "-".to_string(), "-".to_string()),
+            };
+            
+            Row::new([
+                Cell::from(name.clone()),
+                Cell::from(count),
+                Cell::from(p50),
+                Cell::from(p90),
+                Cell::from(p99),
+                Cell::from(runs.map_or_else(|| "-".to_string(), |r| seconds(r.p50))),
+                Cell::from(queued.len().to_string()),
+                Cell::from(longest),
+            ])
+        });
+        
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Percentage(17),
+                Constraint::Percentage(9),
+                Constraint::Percentage(12),
+                Constraint::Percentage(12),
+                Constraint::Percentage(12),
+                Constraint::Percentage(12),
+                Constraint::Percentage(9),
+                Constraint::Percentage(13),
+            ],
+        )
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title("Queue Wait"))
+            .style(theme.normal_text);
+            
+        frame.render_widget(table, area);
+    }
+    
+    /// Render running tasks flagged as stalled or straggling.
+    fn render_stragglers(
+        frame: &mut Frame,
+        area: Rect,
+        stragglers: &[Straggler],
+        theme: &Theme,
+    ) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Stragglers ({})", stragglers.len()));
+        
+        if stragglers.is_empty() {
+            let paragraph = Paragraph::new(Span::styled("No stalled tasks", theme.help_style))
+                .block(block)
+                .style(theme.normal_text);
+            frame.render_widget(paragraph, area);
+            return;
+        }
+        
+        let header = Row::new(["ID", "Name", "Backend", "Running", "Reason"]
+            .iter()
+            .map(|h| Cell::from(*h).style(theme.header_style)));
+        
+        let rows = stragglers.iter().take(MAX_STRAGGLER_ROWS).map(|straggler| {
+            let reasons: Vec<String> = straggler.reasons.iter().map(describe_stall).collect();
+            Row::new([
+                Cell::from(straggler.task_id.to_string()),
+                Cell::from(straggler.name.clone()),
+                Cell::from(straggler.backend.clone()),
+                Cell::from(format_duration(&straggler.run_time)),
+                Cell::from(reasons.join(", ")).style(theme.warning_style),
+            ])
+        });
+        
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Percentage(22),
+                Constraint::Length(13),
+                Constraint::Length(9),
+                Constraint::Percentage(40),
+            ],
+        )
+            .header(header)
+            .block(block)
+            .style(theme.normal_text);
+            
+        frame.render_widget(table, area);
+    }
+    
     /// Render system events and notifications.
     fn render_events(
         frame: &mut Frame,
@@ -422,21 +695,21 @@ impl DashboardView {
         // Create placeholder events
         // In a real implementation, these would come from the application state
         let events = vec![
-            (chrono::Utc::now(), "Engine connected successfully", Color::Green),
-            (chrono::Utc::now() - chrono::Duration::seconds(30), "Task 'genome-analysis' completed", Color::Cyan),
-            (chrono::Utc::now() - chrono::Duration::seconds(45), "Docker backend reports healthy status", Color::Green),
-            (chrono::Utc::now() - chrono::Duration::minutes(2), "TES backend reports degraded status", Color::Yellow),
-            (chrono::Utc::now() - chrono::Duration::minutes(5), "Task 'data-processing' failed", Color::Red),
+            (chrono::Utc::now(), "Engine connected successfully", theme.healthy_style),
+            (chrono::Utc::now() - chrono::Duration::seconds(30), "Task 'genome-analysis' completed", theme.completed_style),
+            (chrono::Utc::now() - chrono::Duration::seconds(45), "Docker backend reports healthy status", theme.healthy_style),
+            (chrono::Utc::now() - chrono::Duration::minutes(2), "TES backend reports degraded status", theme.warning_style),
+            (chrono::Utc::now() - chrono::Duration::minutes(5), "Task 'data-processing' failed", theme.failed_style),
         ];
         
         // Format events as text
-        let text: Vec<Line> = events.into_iter().map(|(time, message, color)| {
+        let text: Vec<Line> = events.into_iter().map(|(time, message, style)| {
             Line::from(vec![
                 Span::styled(
                     format!("[{}] ", time.format("%H:%M:%S")),
-                    Style::default().add_modifier(Modifier::BOLD)
+                    theme.label_style.add_modifier(Modifier::BOLD)
                 ),
-                Span::styled(message, Style::default().fg(color)),
+                Span::styled(message, style),
             ])
         }).collect();
         
@@ -506,5 +779,22 @@ impl DashboardView {
     }
 }
 
+/// Short description of why a task was flagged.
+fn describe_stall(reason: &StallReason) -> String {
+    match reason {
+        StallReason::NoProgress(idle) => format!("no progress for {}", format_duration(idle)),
+        StallReason::IdleCpu(mean) => format!("CPU idle ({})", mean),
+        StallReason::SlowRuntime { p95, .. } => format!("beyond p95 of {}", format_duration(p95)),
+    }
+}
 
-
+/// Direction of `current` against `baseline`, ignoring changes under 10%.
+fn trend(current: f64, baseline: f64) -> Trend {
+    if current > baseline * 1.1 && current - baseline > 1e-6 {
+        Trend::Up
+    } else if current < baseline * 0.9 {
+        Trend::Down
+    } else {
+        Trend::Neutral
+    }
+}
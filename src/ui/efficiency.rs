@@ -0,0 +1,226 @@
+//! Efficiency view comparing requested and used resources.
+//!
+//! Lists task names, or individual tasks, by wasted core-hours with their
+//! CPU and memory efficiency and suggested right-sized requests.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use eyre::Result;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+
+use crate::state::{AppState, EfficiencyReport};
+use crate::ui::Theme;
+
+/// Grouping of the efficiency table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EfficiencyMode {
+    /// One row per task name
+    ByName,
+    /// One row per task
+    ByTask,
+}
+
+/// Efficiency view state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EfficiencyView {
+    /// Current grouping
+    mode: EfficiencyMode,
+    /// Table state for cursor position
+    table_state: TableState,
+}
+
+impl Default for EfficiencyView {
+    fn default() -> Self {
+        Self {
+            mode: EfficiencyMode::ByName,
+            table_state: TableState::default(),
+        }
+    }
+}
+
+impl EfficiencyView {
+    /// Create a new efficiency view.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle key events for this view.
+    pub fn handle_key_event(&mut self, key: KeyEvent, app_state: &AppState) -> Result<()> {
+        let report = EfficiencyReport::from_state(app_state);
+        let rows = self.row_count(&report);
+
+        match key.code {
+            KeyCode::Tab => {
+                self.mode = match self.mode {
+                    EfficiencyMode::ByName => EfficiencyMode::ByTask,
+                    EfficiencyMode::ByTask => EfficiencyMode::ByName,
+                };
+                self.table_state.select(None);
+            }
+            KeyCode::Down | KeyCode::Char('j') if rows > 0 => {
+                let next = self.table_state.selected().map_or(0, |i| (i + 1) % rows);
+                self.table_state.select(Some(next));
+            }
+            KeyCode::Up | KeyCode::Char('k') if rows > 0 => {
+                let prev = self.table_state.selected().map_or(0, |i| if i == 0 { rows - 1 } else { i - 1 });
+                self.table_state.select(Some(prev));
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// The task under the cursor, when listing individual tasks.
+    pub fn selected_task(&self, app_state: &AppState) -> Option<u64> {
+        if self.mode != EfficiencyMode::ByTask {
+            return None;
+        }
+        let report = EfficiencyReport::from_state(app_state);
+        self.table_state.selected()
+            .and_then(|i| report.tasks.get(i))
+            .map(|task| task.task_id)
+    }
+
+    fn row_count(&self, report: &EfficiencyReport) -> usize {
+        match self.mode {
+            EfficiencyMode::ByName => report.names.len(),
+            EfficiencyMode::ByTask => report.tasks.len(),
+        }
+    }
+
+    /// Render the efficiency view.
+    pub fn render(&self, frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let report = EfficiencyReport::from_state(app_state);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),  // Header
+                Constraint::Min(3),     // Table
+            ])
+            .split(area);
+
+        self.render_header(frame, chunks[0], &report, theme);
+
+        let mut table_state = self.table_state.clone();
+        let table = match self.mode {
+            EfficiencyMode::ByName => Self::names_table(&report, theme),
+            EfficiencyMode::ByTask => Self::tasks_table(&report, theme),
+        };
+        frame.render_stateful_widget(table, chunks[1], &mut table_state);
+    }
+
+    /// Render the header with totals and key hints.
+    fn render_header(&self, frame: &mut Frame, area: Rect, report: &EfficiencyReport, theme: &Theme) {
+        let mode = match self.mode {
+            EfficiencyMode::ByName => "by task name",
+            EfficiencyMode::ByTask => "by task",
+        };
+        let header_text = Line::from(vec![
+            Span::styled(format!("Efficiency ({})", mode), theme.header_style),
+            Span::raw(" | "),
+            Span::styled(
+                format!(
+                    "{} tasks measured, {:.2} core-hours wasted",
+                    report.tasks.len(),
+                    report.wasted_core_hours()
+                ),
+                theme.label_style,
+            ),
+            Span::raw(" | "),
+            Span::styled("Tab: group by name/task, Enter: task details", theme.help_style),
+        ]);
+
+        let header = Paragraph::new(header_text)
+            .style(theme.normal_text)
+            .block(Block::default().borders(Borders::BOTTOM));
+
+        frame.render_widget(header, area);
+    }
+
+    /// Table with one row per task name and suggested requests.
+    fn names_table<'a>(report: &EfficiencyReport, theme: &Theme) -> Table<'a> {
+        let header = Row::new(
+            ["Name", "Tasks", "CPU eff", "Mem eff", "Score", "Wasted core-h", "Requested", "Suggested"]
+                .iter()
+                .map(|h| Cell::from(*h).style(theme.header_style)),
+        );
+
+        let rows: Vec<Row> = report.names.iter().map(|group| {
+            Row::new([
+                Cell::from(group.name.clone()),
+                Cell::from(group.tasks.len().to_string()),
+                Cell::from(group.cpu_efficiency().to_string())
+                    .style(theme.efficiency_style(group.cpu_efficiency())),
+                Cell::from(group.memory_efficiency().to_string())
+                    .style(theme.efficiency_style(group.memory_efficiency())),
+                Cell::from(group.score().to_string())
+                    .style(theme.efficiency_style(group.score())),
+                Cell::from(format!("{:.2}", group.wasted_core_hours())),
+                Cell::from(format!("{} / {}", group.requested_cpu(), group.requested_memory())),
+                Cell::from(format!("{} / {}", group.suggested_cpu(), group.suggested_memory()))
+                    .style(theme.value_style),
+            ])
+        }).collect();
+
+        Table::new(
+            rows,
+            [
+                Constraint::Percentage(20),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(7),
+                Constraint::Length(14),
+                Constraint::Percentage(20),
+                Constraint::Percentage(20),
+            ],
+        )
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).style(theme.block_style))
+            .highlight_style(theme.selected_style)
+    }
+
+    /// Table with one row per task.
+    fn tasks_table<'a>(report: &EfficiencyReport, theme: &Theme) -> Table<'a> {
+        let header = Row::new(
+            ["ID", "Name", "Status", "Mean CPU / Req", "Peak Mem / Req", "Score", "Wasted core-h"]
+                .iter()
+                .map(|h| Cell::from(*h).style(theme.header_style)),
+        );
+
+        let rows: Vec<Row> = report.tasks.iter().map(|task| {
+            Row::new([
+                Cell::from(task.task_id.to_string()),
+                Cell::from(task.name.clone()),
+                Cell::from(task.status.to_string()).style(theme.task_status_style(task.status)),
+                Cell::from(format!("{} / {}", task.mean_cpu, task.requested_cpu))
+                    .style(theme.efficiency_style(task.cpu_efficiency())),
+                Cell::from(format!("{} / {}", task.peak_memory, task.requested_memory))
+                    .style(theme.efficiency_style(task.memory_efficiency())),
+                Cell::from(task.score().to_string())
+                    .style(theme.efficiency_style(task.score())),
+                Cell::from(format!("{:.2}", task.wasted_core_hours())),
+            ])
+        }).collect();
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Percentage(20),
+                Constraint::Length(10),
+                Constraint::Percentage(20),
+                Constraint::Percentage(22),
+                Constraint::Length(7),
+                Constraint::Length(14),
+            ],
+        )
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).style(theme.block_style))
+            .highlight_style(theme.selected_style)
+    }
+}
@@ -0,0 +1,256 @@
+//! Failures view clustering failed tasks by error signature.
+//!
+//! Lists one row per distinct failure with its count, affected backends and
+//! example tasks. Enter drills into a cluster's members, showing each
+//! task's error and final log lines.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use eyre::Result;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap};
+
+use crate::state::{failure_reason, AppState, FailureCluster, FailureReport};
+use crate::ui::Theme;
+
+/// Example task names shown per cluster.
+const EXAMPLE_TASKS: usize = 3;
+
+/// Failures view state.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FailuresView {
+    /// Cursor in the cluster list
+    cluster_state: TableState,
+    /// Signature of the cluster being drilled into
+    open: Option<String>,
+    /// Cursor in the member list
+    member_state: TableState,
+}
+
+impl FailuresView {
+    /// Create a new failures view with the largest cluster selected.
+    pub fn new() -> Self {
+        let mut view = Self::default();
+        view.cluster_state.select(Some(0));
+        view
+    }
+
+    /// Whether a cluster's members are shown.
+    pub fn is_drilled_in(&self) -> bool {
+        self.open.is_some()
+    }
+
+    /// Handle key events for this view.
+    pub fn handle_key_event(&mut self, key: KeyEvent, app_state: &AppState) -> Result<()> {
+        let report = FailureReport::from_state(app_state);
+
+        match (&self.open, key.code) {
+            (None, KeyCode::Enter) => {
+                if let Some(cluster) = self.cluster_state.selected().and_then(|i| report.clusters.get(i)) {
+                    self.open = Some(cluster.signature.clone());
+                    self.member_state.select(Some(0));
+                }
+            }
+            (Some(_), KeyCode::Esc) => {
+                self.open = None;
+                self.member_state.select(None);
+            }
+            (None, code) => move_cursor(&mut self.cluster_state, report.clusters.len(), code),
+            (Some(_), code) => {
+                let members = self.open_cluster(&report).map_or(0, |c| c.tasks.len());
+                move_cursor(&mut self.member_state, members, code);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The member task under the cursor, when drilled into a cluster.
+    pub fn selected_task(&self, app_state: &AppState) -> Option<u64> {
+        let report = FailureReport::from_state(app_state);
+        let cluster = self.open_cluster(&report)?;
+        self.member_state.selected().and_then(|i| cluster.tasks.get(i)).copied()
+    }
+
+    fn open_cluster<'a>(&self, report: &'a FailureReport) -> Option<&'a FailureCluster> {
+        let signature = self.open.as_ref()?;
+        report.clusters.iter().find(|c| &c.signature == signature)
+    }
+
+    /// Render the failures view.
+    pub fn render(&self, frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let report = FailureReport::from_state(app_state);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),  // Header
+                Constraint::Min(3),     // Clusters or members
+            ])
+            .split(area);
+
+        self.render_header(frame, chunks[0], &report, theme);
+
+        match self.open_cluster(&report) {
+            Some(cluster) => self.render_members(frame, chunks[1], cluster, app_state, theme),
+            None => {
+                let mut cluster_state = self.cluster_state.clone();
+                frame.render_stateful_widget(Self::clusters_table(&report, app_state, theme), chunks[1], &mut cluster_state);
+            }
+        }
+    }
+
+    /// Render the header with totals and key hints.
+    fn render_header(&self, frame: &mut Frame, area: Rect, report: &FailureReport, theme: &Theme) {
+        let hint = if self.open.is_some() {
+            "Enter: task details, Esc: back to clusters"
+        } else {
+            "Enter: show members"
+        };
+        let header_text = Line::from(vec![
+            Span::styled("Failures", theme.header_style),
+            Span::raw(" | "),
+            Span::styled(
+                format!(
+                    "{} failed tasks, {} distinct reasons",
+                    report.failed_tasks(),
+                    report.clusters.len()
+                ),
+                theme.label_style,
+            ),
+            Span::raw(" | "),
+            Span::styled(hint, theme.help_style),
+        ]);
+
+        let header = Paragraph::new(header_text)
+            .style(theme.normal_text)
+            .block(Block::default().borders(Borders::BOTTOM));
+
+        frame.render_widget(header, area);
+    }
+
+    /// Table with one row per failure cluster.
+    fn clusters_table<'a>(report: &FailureReport, app_state: &AppState, theme: &Theme) -> Table<'a> {
+        let header = Row::new(
+            ["Count", "Signature", "Backends", "Example tasks"]
+                .iter()
+                .map(|h| Cell::from(*h).style(theme.header_style)),
+        );
+
+        let rows: Vec<Row> = report.clusters.iter().map(|cluster| {
+            let backends: Vec<String> = cluster.backends.iter()
+                .map(|(name, count)| format!("{} ({})", name, count))
+                .collect();
+            let examples: Vec<&str> = cluster.tasks.iter()
+                .take(EXAMPLE_TASKS)
+                .filter_map(|id| app_state.tasks.get(id))
+                .map(|task| task.name.as_str())
+                .collect();
+            Row::new([
+                Cell::from(cluster.tasks.len().to_string()).style(theme.failed_style),
+                Cell::from(cluster.signature.clone()),
+                Cell::from(backends.join(", ")),
+                Cell::from(examples.join(", ")),
+            ])
+        }).collect();
+
+        Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Percentage(45),
+                Constraint::Percentage(25),
+                Constraint::Percentage(30),
+            ],
+        )
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title("Clusters").style(theme.block_style))
+            .highlight_style(theme.selected_style)
+    }
+
+    /// Render the members of a cluster, and the final log lines of the
+    /// selected one.
+    fn render_members(&self, frame: &mut Frame, area: Rect, cluster: &FailureCluster, app_state: &AppState, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(3),     // Members
+                Constraint::Length(8),  // Final log lines
+            ])
+            .split(area);
+
+        let header = Row::new(
+            ["ID", "Name", "Backend", "Failed at", "Error"]
+                .iter()
+                .map(|h| Cell::from(*h).style(theme.header_style)),
+        );
+
+        let rows: Vec<Row> = cluster.tasks.iter()
+            .filter_map(|id| app_state.tasks.get(id))
+            .map(|task| {
+                let failed_at = task.finished_at()
+                    .map_or_else(|| "-".to_string(), |at| at.format("%H:%M:%S").to_string());
+                Row::new([
+                    Cell::from(task.id.to_string()),
+                    Cell::from(task.name.clone()),
+                    Cell::from(task.backend.clone()),
+                    Cell::from(failed_at),
+                    Cell::from(failure_reason(task).to_string()).style(theme.failed_style),
+                ])
+            })
+            .collect();
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Percentage(20),
+                Constraint::Length(14),
+                Constraint::Length(10),
+                Constraint::Percentage(55),
+            ],
+        )
+            .header(header)
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} ({} tasks)", cluster.signature, cluster.tasks.len()))
+                .style(theme.block_style))
+            .highlight_style(theme.selected_style);
+
+        let mut member_state = self.member_state.clone();
+        frame.render_stateful_widget(table, chunks[0], &mut member_state);
+
+        // Final log lines of the member under the cursor
+        let task = self.member_state.selected()
+            .and_then(|i| cluster.tasks.get(i))
+            .and_then(|id| app_state.tasks.get(id));
+        let lines: Vec<Line> = match task {
+            Some(task) if !task.log_tail.is_empty() => {
+                task.log_tail.iter().map(|line| Line::from(line.clone())).collect()
+            }
+            _ => vec![Line::from(Span::styled("No log lines captured", theme.help_style))],
+        };
+        let logs = Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("Final Log Lines"))
+            .style(theme.normal_text)
+            .wrap(Wrap { trim: false });
+        frame.render_widget(logs, chunks[1]);
+    }
+}
+
+/// Move a table cursor with j/k or the arrow keys, wrapping around.
+fn move_cursor(state: &mut TableState, rows: usize, code: KeyCode) {
+    if rows == 0 {
+        return;
+    }
+    match code {
+        KeyCode::Down | KeyCode::Char('j') => {
+            state.select(Some(state.selected().map_or(0, |i| (i + 1) % rows)));
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.select(Some(state.selected().map_or(0, |i| if i == 0 { rows - 1 } else { i - 1 })));
+        }
+        _ => {}
+    }
+}
@@ -3,7 +3,6 @@
 use ratatui::Frame;
 use ratatui::layout::{Rect, Alignment};
 use ratatui::widgets::{Block, Borders, Paragraph, Clear};
-use ratatui::style::{Style, Modifier};
 use ratatui::text::{Line, Span};
 
 use crate::state::AppState;
@@ -19,7 +18,7 @@ impl HelpView {
         area: Rect,
         _app_state: &AppState,
         theme: &Theme,
-        _current_view: &ViewState,
+        current_view: &ViewState,
     ) {
         // Create a centered popup area that's 80% of the screen
         let popup_area = Self::centered_rect(60, 70, area);
@@ -34,9 +33,9 @@ impl HelpView {
             .style(theme.block_style);
             
         // Prepare help text - both global shortcuts and context-specific ones
-        let help_text = vec![
+        let mut help_text = vec![
             Line::from(vec![
-                Span::styled("Global Shortcuts", Style::default().add_modifier(Modifier::BOLD))
+                Span::styled("Global Shortcuts", theme.header_style)
             ]),
             Line::from(""),
             Line::from(vec![
@@ -59,12 +58,194 @@ impl HelpView {
                 Span::styled("b", theme.key_style),
                 Span::raw(" - Backends list view"),
             ]),
+            Line::from(vec![
+                Span::styled("w", theme.key_style),
+                Span::raw(" - Resource efficiency view"),
+            ]),
+            Line::from(vec![
+                Span::styled("s", theme.key_style),
+                Span::raw(" - Duration statistics view"),
+            ]),
+            Line::from(vec![
+                Span::styled("F", theme.key_style),
+                Span::raw(" - Failures by error signature"),
+            ]),
+            Line::from(vec![
+                Span::styled("D", theme.key_style),
+                Span::raw(" - Workflow DAG view"),
+            ]),
+            Line::from(vec![
+                Span::styled("L", theme.key_style),
+                Span::raw(" - Timeline of tasks per backend or group"),
+            ]),
             Line::from(vec![
                 Span::styled("p", theme.key_style),
                 Span::raw(" - Toggle pause"),
             ]),
+            Line::from(vec![
+                Span::styled("T", theme.key_style),
+                Span::raw(" - Cycle color theme"),
+            ]),
         ];
         
+        if let ViewState::TasksList = current_view {
+            help_text.extend([
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Task List", theme.header_style)
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("1-8", theme.key_style),
+                    Span::raw(" - Sort by column (again to reverse)"),
+                ]),
+                Line::from(vec![
+                    Span::styled("f", theme.key_style),
+                    Span::raw(" - Cycle status filter"),
+                ]),
+                Line::from(vec![
+                    Span::styled("v", theme.key_style),
+                    Span::raw(" - Toggle workflow tree"),
+                ]),
+                Line::from(vec![
+                    Span::styled("Enter / ← / →", theme.key_style),
+                    Span::raw(" - Expand or collapse a tree group"),
+                ]),
+                Line::from(vec![
+                    Span::styled("e / E", theme.key_style),
+                    Span::raw(" - Export shown tasks to CSV / JSON"),
+                ]),
+                Line::from(vec![
+                    Span::styled("R", theme.key_style),
+                    Span::raw(" - Write HTML run report"),
+                ]),
+            ]);
+        }
+        
+        if let ViewState::Dashboard | ViewState::BackendsList = current_view {
+            help_text.extend([
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Backend Utilization", theme.header_style)
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("m", theme.key_style),
+                    Span::raw(" - Shade the heatmap by CPU, memory or slots"),
+                ]),
+            ]);
+        }
+        
+        if let ViewState::Efficiency(_) = current_view {
+            help_text.extend([
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Efficiency", theme.header_style)
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Tab", theme.key_style),
+                    Span::raw(" - Group by task name or list tasks"),
+                ]),
+                Line::from(vec![
+                    Span::styled("Enter", theme.key_style),
+                    Span::raw(" - Open the selected task"),
+                ]),
+            ]);
+        }
+        
+        if let ViewState::Timeline(_) = current_view {
+            help_text.extend([
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Timeline", theme.header_style)
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("v", theme.key_style),
+                    Span::raw(" - Lanes per backend or per task group"),
+                ]),
+                Line::from(vec![
+                    Span::styled("+ / -", theme.key_style),
+                    Span::raw(" - Zoom in / out, a to fit all tasks"),
+                ]),
+                Line::from(vec![
+                    Span::styled("← / →", theme.key_style),
+                    Span::raw(" - Scroll back / forward in time, End for now"),
+                ]),
+                Line::from(vec![
+                    Span::styled("j / k", theme.key_style),
+                    Span::raw(" - Scroll rows"),
+                ]),
+            ]);
+        }
+        
+        if let ViewState::Statistics(_) = current_view {
+            help_text.extend([
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Statistics", theme.header_style)
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("j / k", theme.key_style),
+                    Span::raw(" - Pick the task group to plot"),
+                ]),
+            ]);
+        }
+        
+        if let ViewState::Failures(_) = current_view {
+            help_text.extend([
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Failures", theme.header_style)
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Enter", theme.key_style),
+                    Span::raw(" - Show a cluster's tasks, then open a task"),
+                ]),
+                Line::from(vec![
+                    Span::styled("Esc", theme.key_style),
+                    Span::raw(" - Back to the clusters"),
+                ]),
+            ]);
+        }
+        
+        if let ViewState::Dag(_) = current_view {
+            help_text.extend([
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("Workflow DAG", theme.header_style)
+                ]),
+                Line::from(""),
+                Line::from(vec![
+                    Span::styled("← / →", theme.key_style),
+                    Span::raw(" - Select an upstream / downstream task"),
+                ]),
+                Line::from(vec![
+                    Span::styled("↑ / ↓", theme.key_style),
+                    Span::raw(" - Select within a level"),
+                ]),
+                Line::from(vec![
+                    Span::styled("Shift+arrows", theme.key_style),
+                    Span::raw(" - Pan, c to center on the selection"),
+                ]),
+                Line::from(vec![
+                    Span::styled("+ / -", theme.key_style),
+                    Span::raw(" - Zoom in / out"),
+                ]),
+                Line::from(vec![
+                    Span::styled("f", theme.key_style),
+                    Span::raw(" - Focus on the selected task's lineage"),
+                ]),
+                Line::from(vec![
+                    Span::styled("Enter", theme.key_style),
+                    Span::raw(" - Open the selected task"),
+                ]),
+            ]);
+        }
+        
         // Create paragraph with help text
         let help_widget = Paragraph::new(help_text)
             .block(help_block)
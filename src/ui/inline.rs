@@ -0,0 +1,139 @@
+//! Compact progress summary for the inline viewport.
+//!
+//! Drawn a few lines tall below the shell prompt rather than on the
+//! alternate screen: counts by status, a stacked bar of the whole batch and
+//! the three running tasks closest to completion.
+
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+
+use crate::state::{AppState, TaskState, TaskStatus};
+use crate::ui::Theme;
+use crate::ui::task_list::format_duration;
+use crate::ui::widgets::{ProgressBar, StackedBar};
+
+/// Number of terminal lines used by the inline summary.
+pub const INLINE_HEIGHT: u16 = 5;
+
+/// Number of running tasks listed below the bar.
+const TOP_RUNNING: usize = 3;
+
+/// Statuses in the order they are stacked in the bar, finished work first.
+const BAR_ORDER: [TaskStatus; 6] = [
+    TaskStatus::Completed,
+    TaskStatus::Failed,
+    TaskStatus::Cancelled,
+    TaskStatus::Running,
+    TaskStatus::Queued,
+    TaskStatus::Created,
+];
+
+/// Statuses in the order they are counted in the summary line.
+const COUNT_ORDER: [TaskStatus; 6] = [
+    TaskStatus::Created,
+    TaskStatus::Queued,
+    TaskStatus::Running,
+    TaskStatus::Completed,
+    TaskStatus::Failed,
+    TaskStatus::Cancelled,
+];
+
+/// Compact inline progress view.
+pub struct InlineView;
+
+impl InlineView {
+    /// Render the inline summary.
+    pub fn render(frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),                      // Status counts
+                Constraint::Length(1),                      // Stacked bar
+                Constraint::Length(TOP_RUNNING as u16),     // Running tasks
+            ])
+            .split(area);
+
+        Self::render_counts(frame, chunks[0], app_state, theme);
+        Self::render_bar(frame, chunks[1], app_state, theme);
+        Self::render_running(frame, chunks[2], app_state, theme);
+    }
+
+    /// Render the task counts by status.
+    fn render_counts(frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let finished = app_state.tasks.values().filter(|t| t.status.is_terminal()).count();
+        let mut spans = vec![
+            Span::styled("Crankshaft ", theme.header_style),
+            Span::styled(format!("{}/{} done", finished, app_state.tasks.len()), theme.value_style),
+        ];
+
+        for status in COUNT_ORDER {
+            let count = count_status(app_state, status);
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(format!("{} {}", status.to_string(), count), theme.task_status_style(status)));
+        }
+
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Render the stacked progress bar.
+    fn render_bar(frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let segments = BAR_ORDER.iter()
+            .map(|status| (count_status(app_state, *status) as u64, theme.task_status_style(*status)))
+            .collect();
+
+        frame.render_widget(StackedBar::new(segments).empty_style(theme.empty_style), area);
+    }
+
+    /// Render the running tasks closest to completion.
+    fn render_running(frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let mut running: Vec<&TaskState> = app_state.tasks.values()
+            .filter(|t| t.status == TaskStatus::Running)
+            .collect();
+        running.sort_by(|a, b| {
+            b.progress.unwrap_or(0.0).total_cmp(&a.progress.unwrap_or(0.0)).then(a.id.cmp(&b.id))
+        });
+
+        if running.is_empty() {
+            frame.render_widget(Paragraph::new(Span::styled("No running tasks", theme.help_style)), area);
+            return;
+        }
+
+        for (row, task) in running.into_iter().take(TOP_RUNNING).enumerate() {
+            let line_area = Rect::new(area.x, area.y + row as u16, area.width, 1);
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([
+                    Constraint::Min(20),        // Name and backend
+                    Constraint::Length(22),     // Progress bar
+                    Constraint::Length(9),      // Elapsed
+                ])
+                .split(line_area);
+
+            let label = Line::from(vec![
+                Span::styled(format!("#{} ", task.id), theme.label_style),
+                Span::styled(task.name.clone(), theme.value_style.add_modifier(Modifier::BOLD)),
+                Span::styled(format!(" on {}", task.backend), theme.label_style),
+            ]);
+            frame.render_widget(Paragraph::new(label), columns[0]);
+
+            let progress = ProgressBar::new(task.progress.unwrap_or(0.0) as f64)
+                .style(theme.running_style)
+                .empty_style(theme.empty_style)
+                .text_style(theme.value_style)
+                .show_percentage(true);
+            frame.render_widget(progress, columns[1]);
+
+            let elapsed = Paragraph::new(format!(" {}", format_duration(&task.elapsed())))
+                .style(theme.label_style);
+            frame.render_widget(elapsed, columns[2]);
+        }
+    }
+}
+
+/// Count the tasks with the given status.
+fn count_status(app_state: &AppState, status: TaskStatus) -> usize {
+    app_state.tasks.values().filter(|t| t.status == status).count()
+}
@@ -0,0 +1,191 @@
+//! Configurable key bindings for global shortcuts.
+//!
+//! A keymap file is a TOML table mapping actions to one key or a list of
+//! keys, for example:
+//!
+//! ```toml
+//! quit = "q"
+//! help = ["?", "F1"]
+//! pause = "Space"
+//! theme = "T"
+//! ```
+//!
+//! Actions missing from the file keep their default bindings.
+
+use std::path::Path;
+use crossterm::event::{KeyCode, KeyEvent};
+use eyre::{bail, Result, WrapErr};
+use serde::Deserialize;
+
+/// Global actions that can be bound to keys.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    Help,
+    Dashboard,
+    Tasks,
+    Backends,
+    Efficiency,
+    Statistics,
+    Failures,
+    Dag,
+    Timeline,
+    Pause,
+    Theme,
+}
+
+/// Key bindings for the global actions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keymap {
+    pub quit: Vec<KeyCode>,
+    pub help: Vec<KeyCode>,
+    pub dashboard: Vec<KeyCode>,
+    pub tasks: Vec<KeyCode>,
+    pub backends: Vec<KeyCode>,
+    pub efficiency: Vec<KeyCode>,
+    pub statistics: Vec<KeyCode>,
+    pub failures: Vec<KeyCode>,
+    pub dag: Vec<KeyCode>,
+    pub timeline: Vec<KeyCode>,
+    pub pause: Vec<KeyCode>,
+    pub theme: Vec<KeyCode>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self {
+            quit: vec![KeyCode::Char('q')],
+            help: vec![KeyCode::Char('?'), KeyCode::F(1)],
+            dashboard: vec![KeyCode::Char('d')],
+            tasks: vec![KeyCode::Char('t')],
+            backends: vec![KeyCode::Char('b')],
+            efficiency: vec![KeyCode::Char('w')],
+            statistics: vec![KeyCode::Char('s')],
+            failures: vec![KeyCode::Char('F')],
+            dag: vec![KeyCode::Char('D')],
+            timeline: vec![KeyCode::Char('L')],
+            pause: vec![KeyCode::Char('p')],
+            theme: vec![KeyCode::Char('T')],
+        }
+    }
+}
+
+/// A single key or a list of keys, as written in the keymap file.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum KeyList {
+    One(String),
+    Many(Vec<String>),
+}
+
+/// Keymap file contents.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct KeymapFile {
+    quit: Option<KeyList>,
+    help: Option<KeyList>,
+    dashboard: Option<KeyList>,
+    tasks: Option<KeyList>,
+    backends: Option<KeyList>,
+    efficiency: Option<KeyList>,
+    statistics: Option<KeyList>,
+    failures: Option<KeyList>,
+    dag: Option<KeyList>,
+    timeline: Option<KeyList>,
+    pause: Option<KeyList>,
+    theme: Option<KeyList>,
+}
+
+impl Keymap {
+    /// Load a keymap file, keeping the defaults for actions it does not mention.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read keymap {}", path.display()))?;
+        Self::parse(&contents).wrap_err_with(|| format!("invalid keymap {}", path.display()))
+    }
+
+    /// Parse keymap TOML, keeping the defaults for actions it does not mention.
+    pub fn parse(contents: &str) -> Result<Self> {
+        let file: KeymapFile = toml::from_str(contents)?;
+        let mut keymap = Self::default();
+
+        for (binding, keys) in [
+            (&mut keymap.quit, file.quit),
+            (&mut keymap.help, file.help),
+            (&mut keymap.dashboard, file.dashboard),
+            (&mut keymap.tasks, file.tasks),
+            (&mut keymap.backends, file.backends),
+            (&mut keymap.efficiency, file.efficiency),
+            (&mut keymap.statistics, file.statistics),
+            (&mut keymap.failures, file.failures),
+            (&mut keymap.dag, file.dag),
+            (&mut keymap.timeline, file.timeline),
+            (&mut keymap.pause, file.pause),
+            (&mut keymap.theme, file.theme),
+        ] {
+            if let Some(keys) = keys {
+                let keys = match keys {
+                    KeyList::One(key) => vec![key],
+                    KeyList::Many(keys) => keys,
+                };
+                *binding = keys.iter().map(|k| parse_key(k)).collect::<Result<_>>()?;
+            }
+        }
+
+        Ok(keymap)
+    }
+
+    /// The action bound to a key event, if any.
+    pub fn action(&self, key: &KeyEvent) -> Option<Action> {
+        [
+            (Action::Quit, &self.quit),
+            (Action::Help, &self.help),
+            (Action::Dashboard, &self.dashboard),
+            (Action::Tasks, &self.tasks),
+            (Action::Backends, &self.backends),
+            (Action::Efficiency, &self.efficiency),
+            (Action::Statistics, &self.statistics),
+            (Action::Failures, &self.failures),
+            (Action::Dag, &self.dag),
+            (Action::Timeline, &self.timeline),
+            (Action::Pause, &self.pause),
+            (Action::Theme, &self.theme),
+        ]
+        .into_iter()
+        .find(|(_, keys)| keys.contains(&key.code))
+        .map(|(action, _)| action)
+    }
+}
+
+/// Parse a key name such as `q`, `Esc`, `Space` or `F1`.
+pub fn parse_key(name: &str) -> Result<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Ok(KeyCode::Char(c));
+    }
+
+    let code = match name.to_ascii_lowercase().as_str() {
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "tab" => KeyCode::Tab,
+        "backtab" => KeyCode::BackTab,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "space" => KeyCode::Char(' '),
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        other => match other.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n @ 1..=12) => KeyCode::F(n),
+            _ => bail!("unknown key '{}'", name),
+        },
+    };
+
+    Ok(code)
+}
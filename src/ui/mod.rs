@@ -8,25 +8,41 @@ mod dashboard;
 pub mod task_list;
 pub mod task_detail;
 pub mod backend_view;
+pub mod efficiency;
+pub mod statistics;
+pub mod failures;
+pub mod dag;
+pub mod timeline;
 pub mod log_view;
 pub mod theme;
 pub mod help;
+pub mod inline;
+pub mod keymap;
 pub mod widgets;
 
 pub use dashboard::DashboardView;
 pub use task_list::TaskListView;
 pub use task_detail::TaskDetailView;
 pub use backend_view::BackendView;
+pub use efficiency::EfficiencyView;
+pub use statistics::StatisticsView;
+pub use failures::FailuresView;
+pub use dag::DagView;
+pub use timeline::TimelineView;
 pub use log_view::LogView;
 pub use theme::Theme;
 pub use help::HelpView;
+pub use inline::InlineView;
+pub use keymap::Keymap;
 
 use crossterm::event::KeyEvent;
 use eyre::Result;
 use ratatui::Frame;
 use ratatui::layout::Rect;
 
-use crate::state::{AppState, Temporality};
+use crate::export::{self, ExportFormat};
+use crate::ui::keymap::Action;
+use crate::state::{AppState, Temporality, UtilizationMetric};
 
 /// The result of updating the UI in response to user input.
 pub enum UpdateKind {
@@ -61,6 +77,16 @@ pub enum ViewState {
     TaskInstance(TaskDetailView),
     /// Detailed view of a specific backend
     BackendInstance(BackendView),
+    /// Requested vs. used resources by task name or task
+    Efficiency(EfficiencyView),
+    /// Duration statistics per task group
+    Statistics(StatisticsView),
+    /// Failed tasks clustered by error signature
+    Failures(FailuresView),
+    /// Task dependency graph
+    Dag(DagView),
+    /// Tasks as bars over time, per backend or task group
+    Timeline(TimelineView),
 }
 
 /// Main UI controller.
@@ -69,8 +95,16 @@ pub struct Ui {
     state: ViewState,
     /// Whether to show help overlay
     show_help: bool,
+    /// Task list view state (sort order and filter)
+    task_list: TaskListView,
+    /// Metric shaded in the backend utilization heatmaps
+    heatmap_metric: UtilizationMetric,
+    /// One-off message shown in the status line until the next key press
+    notice: Option<String>,
     /// UI theme
     theme: Theme,
+    /// Key bindings for global shortcuts
+    keymap: Keymap,
     /// Terminal width
     terminal_width: u16,
     /// Terminal height
@@ -85,7 +119,11 @@ impl Ui {
         Self {
             state: ViewState::Dashboard,
             show_help: false,
+            task_list: TaskListView::new(),
+            heatmap_metric: UtilizationMetric::default(),
+            notice: None,
             theme: Theme::default(),
+            keymap: Keymap::default(),
             terminal_width: 80,  
             terminal_height: 24,
             animation_frame: 0,
@@ -107,28 +145,67 @@ impl Ui {
         self.theme = theme;
     }
     
+    /// Switch to the next built-in theme, unless `NO_COLOR` is set.
+    pub fn cycle_theme(&mut self) {
+        if theme::no_color_requested() {
+            self.notice = Some("Colors are disabled by NO_COLOR".to_string());
+            return;
+        }
+        self.theme = self.theme.next_builtin();
+        self.notice = Some(format!("Theme: {}", self.theme.name));
+    }
+    
+    /// Set the key bindings for global shortcuts.
+    pub fn set_keymap(&mut self, keymap: Keymap) {
+        self.keymap = keymap;
+    }
+    
     /// Handle keyboard input.
     pub fn handle_key_event(&mut self, key: KeyEvent, app_state: &mut AppState) -> Result<UpdateKind> {
-        use crossterm::event::KeyCode;
+        self.notice = None;
         
         // Global shortcuts first
-        match key.code {
-            KeyCode::F(1) | KeyCode::Char('?') => return Ok(UpdateKind::ToggleHelp),
-            KeyCode::Char('q') => return Ok(UpdateKind::Quit),
-            KeyCode::Char('d') => {
+        match self.keymap.action(&key) {
+            Some(Action::Help) => return Ok(UpdateKind::ToggleHelp),
+            Some(Action::Quit) => return Ok(UpdateKind::Quit),
+            Some(Action::Dashboard) => {
                 self.state = ViewState::Dashboard;
                 return Ok(UpdateKind::Other);
             },
-            KeyCode::Char('t') => {
+            Some(Action::Tasks) => {
                 self.state = ViewState::TasksList;
                 return Ok(UpdateKind::Other);
             },
-            KeyCode::Char('b') => {
+            Some(Action::Backends) => {
                 self.state = ViewState::BackendsList;
                 return Ok(UpdateKind::Other);
             },
-            KeyCode::Char('p') => return Ok(UpdateKind::TogglePause),
-            _ => {} 
+            Some(Action::Efficiency) => {
+                self.state = ViewState::Efficiency(EfficiencyView::new());
+                return Ok(UpdateKind::Other);
+            },
+            Some(Action::Statistics) => {
+                self.state = ViewState::Statistics(StatisticsView::new());
+                return Ok(UpdateKind::Other);
+            },
+            Some(Action::Failures) => {
+                self.state = ViewState::Failures(FailuresView::new());
+                return Ok(UpdateKind::Other);
+            },
+            Some(Action::Dag) => {
+                self.state = ViewState::Dag(DagView::new());
+                return Ok(UpdateKind::Other);
+            },
+            Some(Action::Timeline) => {
+                self.state = ViewState::Timeline(TimelineView::new());
+                return Ok(UpdateKind::Other);
+            },
+            Some(Action::Pause) => return Ok(UpdateKind::TogglePause),
+            Some(Action::Theme) => {
+                self.cycle_theme();
+                return Ok(UpdateKind::Other);
+            },
+            None => {}
         }
         
         // Delegate to view-specific handlers
@@ -162,6 +239,46 @@ impl Ui {
                 }
                 result
             },
+            ViewState::Efficiency(view) => {
+                let mut view_clone = view.clone();
+                let result = self.handle_efficiency_input(&mut view_clone, key, app_state);
+                if let ViewState::Efficiency(ref mut v) = self.state {
+                    *v = view_clone;
+                }
+                result
+            },
+            ViewState::Statistics(view) => {
+                let mut view_clone = view.clone();
+                let result = view_clone.handle_key_event(key, app_state);
+                if let ViewState::Statistics(ref mut v) = self.state {
+                    *v = view_clone;
+                }
+                result.map(|_| UpdateKind::Other)
+            },
+            ViewState::Failures(view) => {
+                let mut view_clone = view.clone();
+                let result = self.handle_failures_input(&mut view_clone, key, app_state);
+                if let ViewState::Failures(ref mut v) = self.state {
+                    *v = view_clone;
+                }
+                result
+            },
+            ViewState::Dag(view) => {
+                let mut view_clone = view.clone();
+                let result = self.handle_dag_input(&mut view_clone, key, app_state);
+                if let ViewState::Dag(ref mut v) = self.state {
+                    *v = view_clone;
+                }
+                result
+            },
+            ViewState::Timeline(view) => {
+                let mut view_clone = view.clone();
+                let result = view_clone.handle_key_event(key, app_state);
+                if let ViewState::Timeline(ref mut v) = self.state {
+                    *v = view_clone;
+                }
+                result.map(|_| UpdateKind::Other)
+            },
         }
     }
     
@@ -176,6 +293,11 @@ impl Ui {
             ViewState::BackendsList => self.render_backends_list(frame, area, app_state),
             ViewState::TaskInstance(view) => self.render_task_detail(view, frame, area, app_state),
             ViewState::BackendInstance(view) => self.render_backend_detail(view, frame, area, app_state),
+            ViewState::Efficiency(view) => view.render(frame, area, app_state, &self.theme),
+            ViewState::Statistics(view) => view.render(frame, area, app_state, &self.theme),
+            ViewState::Failures(view) => view.render(frame, area, app_state, &self.theme),
+            ViewState::Dag(view) => view.render(frame, area, app_state, &self.theme),
+            ViewState::Timeline(view) => view.render(frame, area, app_state, &self.theme),
         }
         
         // Render help overlay if active (always on top)
@@ -190,11 +312,16 @@ impl Ui {
     /// Render the UI in a specific area
     pub fn render_in_area(&self, frame: &mut Frame, app_state: &AppState, area: Rect) {
         match &self.state {
-            ViewState::Dashboard => DashboardView::render(frame, area, app_state, &self.theme),
-            ViewState::TasksList => TaskListView::render(frame, area, app_state, &self.theme),
-            ViewState::BackendsList => BackendView::render_list(frame, area, app_state, &self.theme),
+            ViewState::Dashboard => DashboardView::render(frame, area, app_state, self.heatmap_metric, &self.theme),
+            ViewState::TasksList => self.task_list.render(frame, area, app_state, &self.theme),
+            ViewState::BackendsList => BackendView::render_list(frame, area, app_state, self.heatmap_metric, &self.theme),
             ViewState::TaskInstance(view) => view.render(frame, area, app_state, &self.theme),
             ViewState::BackendInstance(view) => view.render(frame, area, app_state, &self.theme),
+            ViewState::Efficiency(view) => view.render(frame, area, app_state, &self.theme),
+            ViewState::Statistics(view) => view.render(frame, area, app_state, &self.theme),
+            ViewState::Failures(view) => view.render(frame, area, app_state, &self.theme),
+            ViewState::Dag(view) => view.render(frame, area, app_state, &self.theme),
+            ViewState::Timeline(view) => view.render(frame, area, app_state, &self.theme),
         }
         
         // Render help if active
@@ -223,8 +350,12 @@ impl Ui {
     
     // Private methods for input handling
     
-    fn handle_dashboard_input(&mut self, _key: KeyEvent, _app_state: &mut AppState) -> Result<UpdateKind> {
-        // Dashboard-specific input handling
+    fn handle_dashboard_input(&mut self, key: KeyEvent, _app_state: &mut AppState) -> Result<UpdateKind> {
+        use crossterm::event::KeyCode;
+        
+        if key.code == KeyCode::Char('m') {
+            self.heatmap_metric = self.heatmap_metric.next();
+        }
         Ok(UpdateKind::Other)
     }
     
@@ -233,26 +364,42 @@ impl Ui {
         
         match key.code {
             KeyCode::Enter => {
-                // Find selected task and switch to detail view
-                if let Some(task_id) = app_state.selected_task_id() {
-                    // Create a copy of the task_id before moving it into the new view
-                    let task_id_value = *task_id;
-                    self.state = ViewState::TaskInstance(TaskDetailView::new(task_id_value));
-                    return Ok(UpdateKind::SelectTask(task_id_value));
+                // Switch to the selected task's detail view, or fold a tree group
+                if let Some(task_id) = self.task_list.activate(app_state) {
+                    self.state = ViewState::TaskInstance(TaskDetailView::new(task_id));
+                    return Ok(UpdateKind::SelectTask(task_id));
                 }
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                app_state.select_next_task();
-            }
-            KeyCode::Up | KeyCode::Char('k') => {
-                app_state.select_prev_task();
+            KeyCode::Char('e') => self.export_tasks(ExportFormat::Csv, app_state),
+            KeyCode::Char('E') => self.export_tasks(ExportFormat::Json, app_state),
+            KeyCode::Char('R') => self.export_report(app_state),
+            _ => {
+                // Navigation, sorting and filtering
+                self.task_list.handle_key_event(key, app_state)?;
             }
-            _ => {}
         }
         
         Ok(UpdateKind::Other)
     }
     
+    /// Export the rows and columns currently shown in the task list.
+    fn export_tasks(&mut self, format: ExportFormat, app_state: &AppState) {
+        let table = self.task_list.export_table(app_state);
+        self.notice = Some(match export::export_table(&table, format) {
+            Ok(path) => format!("Exported {} rows to {}", table.rows.len(), path.display()),
+            Err(err) => format!("Export failed: {}", err),
+        });
+    }
+    
+    /// Write an HTML run report for all tasks.
+    fn export_report(&mut self, app_state: &AppState) {
+        let path = export::timestamped_path("crankshaft-report", "html");
+        self.notice = Some(match export::report::write_report_file(&path, app_state) {
+            Ok(()) => format!("Wrote run report to {}", path.display()),
+            Err(err) => format!("Report failed: {}", err),
+        });
+    }
+    
     fn handle_backends_list_input(&mut self, key: KeyEvent, app_state: &mut AppState) -> Result<UpdateKind> {
         use crossterm::event::KeyCode;
         
@@ -270,6 +417,9 @@ impl Ui {
             KeyCode::Up | KeyCode::Char('k') => {
                 app_state.select_prev_backend();
             }
+            KeyCode::Char('m') => {
+                self.heatmap_metric = self.heatmap_metric.next();
+            }
             _ => {}
         }
         
@@ -310,18 +460,73 @@ impl Ui {
         Ok(UpdateKind::Other)
     }
     
+    fn handle_efficiency_input(&mut self, view: &mut EfficiencyView, key: KeyEvent, app_state: &mut AppState) -> Result<UpdateKind> {
+        use crossterm::event::KeyCode;
+        
+        match key.code {
+            KeyCode::Enter => {
+                // Open the task under the cursor, when listing tasks
+                if let Some(task_id) = view.selected_task(app_state) {
+                    app_state.selected_task_id = Some(task_id);
+                    self.state = ViewState::TaskInstance(TaskDetailView::new(task_id));
+                    return Ok(UpdateKind::SelectTask(task_id));
+                }
+            }
+            _ => view.handle_key_event(key, app_state)?,
+        }
+        
+        Ok(UpdateKind::Other)
+    }
+    
+    fn handle_failures_input(&mut self, view: &mut FailuresView, key: KeyEvent, app_state: &mut AppState) -> Result<UpdateKind> {
+        use crossterm::event::KeyCode;
+        
+        match key.code {
+            KeyCode::Enter if view.is_drilled_in() => {
+                // Open the member under the cursor
+                if let Some(task_id) = view.selected_task(app_state) {
+                    app_state.selected_task_id = Some(task_id);
+                    self.state = ViewState::TaskInstance(TaskDetailView::new(task_id));
+                    return Ok(UpdateKind::SelectTask(task_id));
+                }
+            }
+            _ => view.handle_key_event(key, app_state)?,
+        }
+        
+        Ok(UpdateKind::Other)
+    }
+    
+    fn handle_dag_input(&mut self, view: &mut DagView, key: KeyEvent, app_state: &mut AppState) -> Result<UpdateKind> {
+        use crossterm::event::KeyCode;
+        
+        match key.code {
+            KeyCode::Enter => {
+                // Open the selected node, unless it is a gather node
+                let dag = app_state.dag();
+                if let Some(task_id) = view.selected_task(&dag).filter(|id| !dag.is_gather(*id)) {
+                    app_state.selected_task_id = Some(task_id);
+                    self.state = ViewState::TaskInstance(TaskDetailView::new(task_id));
+                    return Ok(UpdateKind::SelectTask(task_id));
+                }
+            }
+            _ => view.handle_key_event(key, app_state)?,
+        }
+        
+        Ok(UpdateKind::Other)
+    }
+    
     // Private methods for rendering
     
     fn render_dashboard(&self, frame: &mut Frame, area: Rect, app_state: &AppState) {
-        DashboardView::render(frame, area, app_state, &self.theme);
+        DashboardView::render(frame, area, app_state, self.heatmap_metric, &self.theme);
     }
     
     fn render_tasks_list(&self, frame: &mut Frame, area: Rect, app_state: &AppState) {
-        TaskListView::render(frame, area, app_state, &self.theme);
+        self.task_list.render(frame, area, app_state, &self.theme);
     }
     
     fn render_backends_list(&self, frame: &mut Frame, area: Rect, app_state: &AppState) {
-        BackendView::render_list(frame, area, app_state, &self.theme);
+        BackendView::render_list(frame, area, app_state, self.heatmap_metric, &self.theme);
     }
     
     fn render_task_detail(&self, view: &TaskDetailView, frame: &mut Frame, area: Rect, app_state: &AppState) {
@@ -353,10 +558,11 @@ impl Ui {
         let total_tasks = app_state.tasks.len();
         let backends = app_state.backends.len();
         
-        // Format the status line
+        // Format the status line, with any notice taking the place of the help hint
         let status_text = format!(
-            "{} | Tasks: {}/{} | Backends: {} | Press ? for help", 
-            status, active_tasks, total_tasks, backends
+            "{} | Tasks: {}/{} | Backends: {} | {}", 
+            status, active_tasks, total_tasks, backends,
+            self.notice.as_deref().unwrap_or("Press ? for help")
         );
         
         let status_style = match app_state.temporality {
@@ -0,0 +1,168 @@
+//! Statistics view with duration statistics per task group.
+//!
+//! Groups finished tasks by base name, lists each group's duration
+//! percentiles and failure rate, and shows the selected group's duration
+//! distribution as a histogram.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use eyre::Result;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+
+use crate::state::{AppState, DurationStats};
+use crate::ui::Theme;
+use crate::ui::task_list::format_duration;
+use crate::ui::widgets::Histogram;
+
+/// Statistics view state.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StatisticsView {
+    /// Table state for the selected group
+    table_state: TableState,
+}
+
+impl StatisticsView {
+    /// Create a new statistics view.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle key events for this view.
+    pub fn handle_key_event(&mut self, key: KeyEvent, app_state: &AppState) -> Result<()> {
+        let groups = DurationStats::by_name(app_state).len();
+        if groups == 0 {
+            return Ok(());
+        }
+
+        let selected = self.selected(groups);
+        match key.code {
+            KeyCode::Down | KeyCode::Char('j') => self.table_state.select(Some((selected + 1) % groups)),
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.table_state.select(Some(if selected == 0 { groups - 1 } else { selected - 1 }));
+            }
+            KeyCode::Home | KeyCode::Char('g') => self.table_state.select(Some(0)),
+            KeyCode::End | KeyCode::Char('G') => self.table_state.select(Some(groups - 1)),
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Selected group index, defaulting to the first group.
+    fn selected(&self, groups: usize) -> usize {
+        self.table_state.selected().unwrap_or(0).min(groups.saturating_sub(1))
+    }
+
+    /// Render the statistics view.
+    pub fn render(&self, frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let groups = DurationStats::by_name(app_state);
+        let selected = self.selected(groups.len());
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),       // Header
+                Constraint::Percentage(50),  // Group table
+                Constraint::Min(6),          // Histogram
+            ])
+            .split(area);
+
+        Self::render_header(frame, chunks[0], &groups, theme);
+
+        let mut table_state = self.table_state.clone();
+        table_state.select((!groups.is_empty()).then_some(selected));
+        frame.render_stateful_widget(Self::groups_table(&groups, theme), chunks[1], &mut table_state);
+
+        Self::render_histogram(frame, chunks[2], groups.get(selected), theme);
+    }
+
+    /// Render the header with totals and key hints.
+    fn render_header(frame: &mut Frame, area: Rect, groups: &[DurationStats], theme: &Theme) {
+        let finished: usize = groups.iter().map(|g| g.count).sum();
+        let header_text = Line::from(vec![
+            Span::styled("Statistics", theme.header_style),
+            Span::raw(" | "),
+            Span::styled(
+                format!("{} groups, {} finished tasks", groups.len(), finished),
+                theme.label_style,
+            ),
+            Span::raw(" | "),
+            Span::styled("j/k: pick group", theme.help_style),
+        ]);
+
+        let header = Paragraph::new(header_text)
+            .style(theme.normal_text)
+            .block(Block::default().borders(Borders::BOTTOM));
+
+        frame.render_widget(header, area);
+    }
+
+    /// Table with one row per task group.
+    fn groups_table<'a>(groups: &[DurationStats], theme: &Theme) -> Table<'a> {
+        let header = Row::new(
+            ["Group", "Count", "Failed", "Mean", "p50", "p95", "p99", "Max"]
+                .iter()
+                .map(|h| Cell::from(*h).style(theme.header_style)),
+        );
+
+        let rows: Vec<Row> = groups.iter().map(|group| {
+            let failure_style = if group.failed > 0 { theme.failed_style } else { theme.normal_text };
+            Row::new([
+                Cell::from(group.name.clone()),
+                Cell::from(group.count.to_string()),
+                Cell::from(group.failure_rate().to_string()).style(failure_style),
+                Cell::from(format_seconds(group.mean)),
+                Cell::from(format_seconds(group.p50)),
+                Cell::from(format_seconds(group.p95)),
+                Cell::from(format_seconds(group.p99)),
+                Cell::from(format_seconds(group.max)),
+            ])
+        }).collect();
+
+        Table::new(
+            rows,
+            [
+                Constraint::Percentage(25),
+                Constraint::Length(7),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+                Constraint::Length(10),
+            ],
+        )
+            .header(header)
+            .block(Block::default().borders(Borders::ALL).title("Groups").style(theme.block_style))
+            .highlight_style(theme.selected_style)
+    }
+
+    /// Render the duration distribution of the selected group.
+    fn render_histogram(frame: &mut Frame, area: Rect, group: Option<&DurationStats>, theme: &Theme) {
+        let Some(group) = group else {
+            let empty = Paragraph::new(Span::styled("No finished tasks yet", theme.help_style))
+                .block(Block::default().borders(Borders::ALL).title("Duration Distribution"));
+            frame.render_widget(empty, area);
+            return;
+        };
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Duration Distribution: {} ({} tasks)", group.name, group.count))
+            .style(theme.block_style);
+        let histogram = Histogram::new(&group.durations)
+            .block(block)
+            .style(theme.sparkline_style)
+            .label_style(theme.label_style)
+            .format(format_seconds);
+
+        frame.render_widget(histogram, area);
+    }
+}
+
+/// Format a duration given in seconds.
+fn format_seconds(seconds: f64) -> String {
+    format_duration(&chrono::Duration::milliseconds((seconds * 1000.0) as i64))
+}
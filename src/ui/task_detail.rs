@@ -10,8 +10,9 @@ use crossterm::event::KeyEvent;
 
 
 use crate::ui::widgets::sparkline::Sparkline as CustomSparkline;
-use crate::state::{AppState, TaskState, TaskStatus, ResourceSample};
+use crate::state::{AppState, Bucket, ResourceHistory, Resolution, TaskState, TaskStatus, Unit};
 use crate::ui::Theme;
+use crate::ui::task_list::format_eta;
 
 /// Tab selection for task detail view.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -128,6 +129,9 @@ impl TaskDetailView {
                 Span::raw(" | "),
                 Span::styled("Status: ", theme.label_style),
                 Span::styled(task.status.to_string(), status_style),
+                Span::raw(" | "),
+                Span::styled("ETA: ", theme.label_style),
+                Span::styled(format_eta(task), theme.value_style),
             ]),
             Line::from(vec![
                 Span::styled("Name: ", theme.label_style),
@@ -196,6 +200,21 @@ impl TaskDetailView {
             "N/A".to_string()
         };
         
+        // Where the time went: waiting for the scheduler vs. doing the work
+        let timing = |duration: Option<chrono::Duration>| {
+            duration.map_or_else(|| "-".to_string(), |d| format_duration(&d))
+        };
+        let history: Vec<Span> = task.transitions.iter().enumerate()
+            .flat_map(|(i, transition)| {
+                let separator = if i == 0 { "" } else { " → " };
+                [
+                    Span::raw(separator),
+                    Span::styled(transition.status.to_string(), theme.task_status_style(transition.status)),
+                    Span::styled(format!(" {}", transition.at.format("%H:%M:%S")), theme.value_style),
+                ]
+            })
+            .collect();
+        
         let info_text = vec![
             Line::from(vec![
                 Span::styled("Backend: ", theme.label_style),
@@ -216,6 +235,19 @@ impl TaskDetailView {
                 Span::styled("Duration: ", theme.label_style),
                 Span::styled(duration, theme.value_style),
             ]),
+            Line::from(vec![
+                Span::styled("Queue Wait: ", theme.label_style),
+                Span::styled(timing(task.queue_wait()), theme.value_style),
+                Span::styled("  Run Time: ", theme.label_style),
+                Span::styled(timing(task.run_time()), theme.value_style),
+                Span::styled("  Total: ", theme.label_style),
+                Span::styled(format_duration(&task.total_time()), theme.value_style),
+            ]),
+            Line::from(
+                std::iter::once(Span::styled("History: ", theme.label_style))
+                    .chain(history)
+                    .collect::<Vec<_>>()
+            ),
             Line::from(vec![
                 Span::styled("Progress: ", theme.label_style),
                 Span::styled(progress, theme.value_style),
@@ -226,11 +258,13 @@ impl TaskDetailView {
             ]),
             Line::from(vec![
                 Span::styled("  CPU: ", theme.label_style),
-                Span::styled(format!("{:.1}%", task.cpu_usage), theme.value_style),
+                Span::styled(task.cpu_usage.to_string(), theme.value_style),
+                Span::styled(requested_label(task.requested_cpu), theme.label_style),
             ]),
             Line::from(vec![
                 Span::styled("  Memory: ", theme.label_style),
-                Span::styled(format!("{:.1} MB", task.memory_usage), theme.value_style),
+                Span::styled(task.memory_usage.to_string(), theme.value_style),
+                Span::styled(requested_label(task.requested_memory), theme.label_style),
             ]),
         ];
         
@@ -297,18 +331,15 @@ impl TaskDetailView {
             ])
             .split(area);
             
-        // Get resource history if available
-        let resource_samples = if let Some(details) = &app_state.current_task_details {
-            details.borrow().resource_history.clone()
-        } else {
-            Vec::new()
-        };
+        // Get resource history if available, covering the task's whole lifetime
+        let history = app_state.task_history(task.id);
+        let (resolution, cpu, memory) = lifetime_buckets(task, history);
         
         // Render graphs
-        self.render_resource_graphs(frame, chunks[0], task, resource_samples.as_slice(), theme);
+        self.render_resource_graphs(frame, chunks[0], task, history, (resolution, &cpu, &memory), theme);
         
         // Render resource details
-        self.render_resource_details(frame, chunks[1], task, resource_samples.as_slice(), theme);
+        self.render_resource_details(frame, chunks[1], &cpu, &memory, theme);
     }
     
     /// Render resource utilization graphs.
@@ -317,7 +348,8 @@ impl TaskDetailView {
         frame: &mut Frame,
         area: Rect,
         task: &TaskState,
-        samples: &[ResourceSample],
+        history: Option<&ResourceHistory>,
+        (resolution, cpu, memory): (Resolution, &[Bucket], &[Bucket]),
         theme: &Theme,
     ) {
         // Split area for CPU and memory graphs
@@ -329,23 +361,29 @@ impl TaskDetailView {
             ])
             .split(area);
             
-        // Create CPU data
-        let cpu_data: Vec<f64> = if samples.is_empty() {
-            vec![task.cpu_usage as f64]
+        // Chart the bucket means, or the current usage before any samples arrive
+        let cpu_data: Vec<f64> = if cpu.is_empty() {
+            vec![task.cpu_usage.0 as f64]
         } else {
-            samples.iter().map(|s| s.cpu as f64).collect()
+            cpu.iter().map(|b| b.mean() as f64).collect()
         };
-        
-        // Create memory data
-        let memory_data: Vec<f64> = if samples.is_empty() {
-            vec![task.memory_usage as f64]
+        let memory_data: Vec<f64> = if memory.is_empty() {
+            vec![task.memory_usage.0 as f64]
         } else {
-            samples.iter().map(|s| s.memory as f64).collect()
+            memory.iter().map(|b| b.mean() as f64).collect()
         };
+        let peaks = history.map(|h| (h.cpu.peak().unwrap_or(0.0), h.memory.peak().unwrap_or(0.0)));
         
         // Create CPU sparkline
         let cpu_block = Block::default()
-            .title("CPU Usage")
+            .title(match peaks {
+                Some((cpu_peak, _)) => format!(
+                    "CPU Usage ({} avg, peak {})",
+                    resolution.label(),
+                    Unit::Cores.format(cpu_peak as f64),
+                ),
+                None => "CPU Usage".to_string(),
+            })
             .borders(Borders::ALL)
             .style(theme.block_style);
         
@@ -353,17 +391,25 @@ impl TaskDetailView {
         let cpu_sparkline = CustomSparkline::new(&cpu_data)
             .block(cpu_block)
             .style(theme.sparkline_style)
-            .max(100.0);
+            .unit(Unit::Cores);
             
         // Create memory sparkline
         let memory_block = Block::default()
-            .title("Memory Usage")
+            .title(match peaks {
+                Some((_, memory_peak)) => format!(
+                    "Memory Usage ({} avg, peak {})",
+                    resolution.label(),
+                    Unit::Bytes.format(memory_peak as f64),
+                ),
+                None => "Memory Usage".to_string(),
+            })
             .borders(Borders::ALL)
             .style(theme.block_style);
           
         let memory_sparkline = CustomSparkline::new(&memory_data)
             .block(memory_block)
             .style(theme.sparkline_style)
+            .unit(Unit::Bytes)
             .max(*memory_data.iter().max_by(|a, b| a.partial_cmp(b).unwrap()).unwrap_or(&100.0) * 1.1);
             
         frame.render_widget(cpu_sparkline, chunks[0]);
@@ -375,8 +421,8 @@ impl TaskDetailView {
         &self,
         frame: &mut Frame,
         area: Rect,
-        _task: &TaskState,
-        samples: &[ResourceSample],
+        cpu: &[Bucket],
+        memory: &[Bucket],
         theme: &Theme,
     ) {
         // Create a block for the content
@@ -386,7 +432,7 @@ impl TaskDetailView {
             .style(theme.block_style);
             
         // If no samples available, show message
-        if samples.is_empty() {
+        if cpu.is_empty() {
             let message = Paragraph::new("No resource samples available")
                 .style(theme.normal_text)
                 .block(block);
@@ -396,13 +442,27 @@ impl TaskDetailView {
         }
         
         // Format sample data as list items
-        let items: Vec<ListItem> = samples.iter()
-            .map(|sample| {
-                let timestamp = sample.timestamp.to_rfc3339();
+        let items: Vec<ListItem> = cpu.iter().zip(memory)
+            .map(|(cpu, memory)| {
+                let timestamp = cpu.start.format("%H:%M:%S");
                 let content = Line::from(vec![
                     Span::styled(format!("{}: ", timestamp), theme.label_style),
-                    Span::styled(format!("CPU {:.1}%, ", sample.cpu), theme.value_style),
-                    Span::styled(format!("Mem {:.1}MB", sample.memory), theme.value_style),
+                    Span::styled(
+                        format!(
+                            "CPU {} (max {}), ",
+                            Unit::Cores.format(cpu.mean() as f64),
+                            Unit::Cores.format(cpu.max as f64),
+                        ),
+                        theme.value_style,
+                    ),
+                    Span::styled(
+                        format!(
+                            "Mem {} (max {})",
+                            Unit::Bytes.format(memory.mean() as f64),
+                            Unit::Bytes.format(memory.max as f64),
+                        ),
+                        theme.value_style,
+                    ),
                 ]);
                 
                 ListItem::new(content)
@@ -519,6 +579,11 @@ impl TaskDetailView {
     }
 }
 
+/// Suffix naming the requested amount of a resource, if any.
+fn requested_label(requested: Option<impl std::fmt::Display>) -> String {
+    requested.map(|r| format!(" of {} requested", r)).unwrap_or_default()
+}
+
 /// Format a duration as a human-readable string.
 fn format_duration(duration: &chrono::Duration) -> String {
     let seconds = duration.num_seconds();
@@ -529,4 +594,17 @@ fn format_duration(duration: &chrono::Duration) -> String {
     } else {
         format!("{}h {}m", seconds / 3600, (seconds % 3600) / 60)
     }
-}
\ No newline at end of file
+}
+
+/// CPU and memory buckets covering a task's lifetime, at the resolution that fits it.
+fn lifetime_buckets(task: &TaskState, history: Option<&ResourceHistory>) -> (Resolution, Vec<Bucket>, Vec<Bucket>) {
+    let Some(history) = history else {
+        return (Resolution::Second, Vec::new(), Vec::new());
+    };
+    
+    let end = task.end_time.unwrap_or_else(chrono::Utc::now);
+    let lifetime = task.elapsed().max(chrono::Duration::seconds(60));
+    let (resolution, cpu) = history.cpu.window_ending(end, lifetime);
+    let (_, memory) = history.memory.window_ending(end, lifetime);
+    (resolution, cpu, memory)
+}
@@ -1,14 +1,31 @@
 //! Task list view showing all tasks with filtering and sorting.
 
+use std::collections::HashSet;
 use ratatui::Frame;
 use ratatui::layout::{Layout, Constraint, Direction, Rect};
+use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, TableState, Table, Row, Cell, Paragraph};
-use ratatui::style::{Style, Color};
+use serde_json::{json, Value};
 
-use crate::state::{AppState, TaskState, TaskStatus};
+use crate::export::{rounded, ExportTable};
+use crate::state::{AppState, Rollup, Straggler, TaskState, TaskStatus, WorkflowGroup, NO_WORKFLOW};
 use crate::ui::Theme;
 
+/// Columns of the flat task list, as header and export name.
+const LIST_COLUMNS: [(&str, &str); 9] = [
+    ("ID", "id"), ("Name", "name"), ("Status", "status"), ("Progress", "progress"),
+    ("Duration", "duration_secs"), ("ETA", "eta_secs"), ("Backend", "backend"),
+    ("CPU", "cpu_cores"), ("Memory", "memory_bytes"),
+];
+
+/// Columns of the workflow tree, as header and export name.
+const TREE_COLUMNS: [(&str, &str); 9] = [
+    ("Name", "name"), ("Status", "status"), ("Progress", "progress"), ("Tasks", "tasks"),
+    ("Waiting", "waiting"), ("Running", "running"), ("Done", "completed"), ("Failed", "failed"),
+    ("Backend", "backend"),
+];
+
 /// Sort fields for the task list.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortField {
@@ -28,8 +45,27 @@ pub struct TaskListView {
     sort_field: SortField,
     /// Sort in ascending order
     sort_ascending: bool,
+    /// Only show tasks with this status
+    status_filter: Option<TaskStatus>,
     /// Table state for cursor position
     table_state: TableState,
+    /// Group tasks into a workflow → call → shard tree
+    tree: bool,
+    /// Keys of the expanded tree groups
+    expanded: HashSet<String>,
+    /// Cursor in the tree
+    tree_state: TableState,
+}
+
+/// A row of the task tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TreeRow {
+    /// Workflow run, by index
+    Workflow(usize),
+    /// Call, by workflow and call index
+    Call(usize, usize),
+    /// Shard of an expanded call
+    Task(u64),
 }
 
 impl Default for TaskListView {
@@ -37,7 +73,11 @@ impl Default for TaskListView {
         Self {
             sort_field: SortField::Id,
             sort_ascending: true,
+            status_filter: None,
             table_state: TableState::default(),
+            tree: false,
+            expanded: HashSet::new(),
+            tree_state: TableState::default(),
         }
     }
 }
@@ -55,19 +95,203 @@ impl TaskListView {
         self
     }
     
+    /// Set the status filter.
+    pub fn filter_by(mut self, status: Option<TaskStatus>) -> Self {
+        self.status_filter = status;
+        self
+    }
+    
+    /// The tasks currently shown, filtered and in display order.
+    pub fn visible_tasks<'a>(&self, app_state: &'a AppState) -> Vec<&'a TaskState> {
+        let mut tasks: Vec<&TaskState> = app_state.tasks.values()
+            .filter(|task| self.status_filter.is_none() || self.status_filter == Some(task.status))
+            .collect();
+        Self::sort_tasks(&mut tasks, self.sort_field, self.sort_ascending);
+        tasks
+    }
+    
+    /// The rows currently shown under the columns shown, plus the failure
+    /// reason of each task. Tree rows lead with their level, since the
+    /// indentation that shows it on screen does not survive export.
+    pub fn export_table(&self, app_state: &AppState) -> ExportTable {
+        let tasks = self.visible_tasks(app_state);
+        let now = chrono::Utc::now();
+        if !self.tree {
+            let rows = tasks.iter().map(|task| vec![
+                json!(task.id),
+                json!(task.name),
+                json!(task.status.to_string()),
+                task.progress.map_or(Value::Null, |p| rounded(p as f64)),
+                json!(task.elapsed().num_seconds()),
+                json!(task.eta(now).map(|eta| eta.num_seconds())),
+                json!(task.backend),
+                rounded(task.cpu_usage.0 as f64),
+                json!(task.memory_usage.0),
+                json!(task.error),
+            ]).collect();
+            return ExportTable {
+                columns: LIST_COLUMNS.iter().map(|(_, name)| *name).chain(["error"]).collect(),
+                rows,
+            };
+        }
+        
+        let groups = WorkflowGroup::group(&tasks);
+        let group_row = |level: &str, name: &str, rollup: &Rollup| vec![
+            json!(level),
+            json!(name),
+            json!(rollup.status().to_string()),
+            rounded(rollup.progress()),
+            json!(rollup.total),
+            json!(rollup.waiting),
+            json!(rollup.running),
+            json!(rollup.completed),
+            json!(rollup.failed),
+            Value::Null,
+            Value::Null,
+        ];
+        let rows = self.tree_rows(&groups).into_iter().filter_map(|row| match row {
+            TreeRow::Workflow(w) => Some(group_row("workflow", &groups[w].id, &groups[w].rollup)),
+            TreeRow::Call(w, c) => {
+                let call = &groups[w].calls[c];
+                Some(group_row("call", &call.name, &call.rollup))
+            }
+            TreeRow::Task(id) => {
+                let task = app_state.tasks.get(&id)?;
+                let counts = [Value::Null, Value::Null, Value::Null, Value::Null, Value::Null];
+                Some([json!("task"), json!(task.name), json!(task.status.to_string())].into_iter()
+                    .chain([task.progress.map_or(Value::Null, |p| rounded(p as f64))])
+                    .chain(counts)
+                    .chain([json!(task.backend), json!(task.error)])
+                    .collect())
+            }
+        }).collect();
+        ExportTable {
+            columns: ["level"].into_iter().chain(TREE_COLUMNS.iter().map(|(_, name)| *name)).chain(["error"]).collect(),
+            rows,
+        }
+    }
+    
+    /// Whether tasks are shown as a workflow tree.
+    pub fn is_tree(&self) -> bool {
+        self.tree
+    }
+    
+    /// Switch between the flat list and the workflow tree.
+    pub fn toggle_tree(&mut self) {
+        self.tree = !self.tree;
+    }
+    
+    /// Rows of the task tree: every workflow run, the calls of expanded
+    /// runs and the shards of expanded calls.
+    fn tree_rows(&self, groups: &[WorkflowGroup]) -> Vec<TreeRow> {
+        let mut rows = Vec::new();
+        for (w, workflow) in groups.iter().enumerate() {
+            rows.push(TreeRow::Workflow(w));
+            if !self.expanded.contains(&workflow.id) {
+                continue;
+            }
+            for (c, call) in workflow.calls.iter().enumerate() {
+                rows.push(TreeRow::Call(w, c));
+                if self.expanded.contains(&call_key(workflow, c)) {
+                    rows.extend(call.tasks.iter().map(|&id| TreeRow::Task(id)));
+                }
+            }
+        }
+        rows
+    }
+    
+    /// Expansion key of a tree row, `None` for task rows.
+    fn row_key(groups: &[WorkflowGroup], row: TreeRow) -> Option<String> {
+        match row {
+            TreeRow::Workflow(w) => Some(groups[w].id.clone()),
+            TreeRow::Call(w, c) => Some(call_key(&groups[w], c)),
+            TreeRow::Task(_) => None,
+        }
+    }
+    
+    /// Tree cursor, clamped to the rows shown.
+    fn tree_cursor(&self, rows: usize) -> usize {
+        self.tree_state.selected().unwrap_or(0).min(rows.saturating_sub(1))
+    }
+    
+    /// Act on the row under the cursor: in the tree, expand or collapse a
+    /// group, otherwise return the task to open.
+    pub fn activate(&mut self, app_state: &mut AppState) -> Option<u64> {
+        if !self.tree {
+            return app_state.selected_task_id;
+        }
+        
+        let groups = WorkflowGroup::group(&self.visible_tasks(app_state));
+        let rows = self.tree_rows(&groups);
+        match rows.get(self.tree_cursor(rows.len())).copied()? {
+            TreeRow::Task(id) => {
+                app_state.selected_task_id = Some(id);
+                Some(id)
+            }
+            row => {
+                if let Some(key) = Self::row_key(&groups, row) {
+                    if !self.expanded.remove(&key) {
+                        self.expanded.insert(key);
+                    }
+                }
+                None
+            }
+        }
+    }
+    
+    /// Handle tree navigation: move the cursor, expand a group with
+    /// Right and collapse it, or the group around a row, with Left.
+    fn handle_tree_key(&mut self, code: crossterm::event::KeyCode, app_state: &mut AppState) {
+        use crossterm::event::KeyCode;
+        
+        let groups = WorkflowGroup::group(&self.visible_tasks(app_state));
+        let rows = self.tree_rows(&groups);
+        if rows.is_empty() {
+            return;
+        }
+        let cursor = self.tree_cursor(rows.len());
+        let row = rows[cursor];
+        
+        match code {
+            KeyCode::Down | KeyCode::Char('j') => self.tree_state.select(Some((cursor + 1).min(rows.len() - 1))),
+            KeyCode::Up | KeyCode::Char('k') => self.tree_state.select(Some(cursor.saturating_sub(1))),
+            KeyCode::Home | KeyCode::Char('g') => self.tree_state.select(Some(0)),
+            KeyCode::End | KeyCode::Char('G') => self.tree_state.select(Some(rows.len() - 1)),
+            KeyCode::Right | KeyCode::Char('l') => {
+                if let Some(key) = Self::row_key(&groups, row) {
+                    self.expanded.insert(key);
+                }
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                let expanded = Self::row_key(&groups, row).is_some_and(|key| self.expanded.remove(&key));
+                if !expanded {
+                    // Jump to the enclosing group
+                    let parent = rows[..cursor].iter().rposition(|r| matches!(
+                        (r, row),
+                        (TreeRow::Workflow(_), TreeRow::Call(..)) | (TreeRow::Call(..), TreeRow::Task(_))
+                    ));
+                    if let Some(parent) = parent {
+                        self.tree_state.select(Some(parent));
+                    }
+                }
+            }
+            _ => {}
+        }
+        
+        // Keep the task selection in step with the tree cursor
+        if let Some(TreeRow::Task(id)) = rows.get(self.tree_cursor(rows.len())) {
+            app_state.selected_task_id = Some(*id);
+        }
+    }
+    
     /// Render the task list view.
-    pub fn render(frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
-        let mut view = Self::default();
+    pub fn render(&self, frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let mut table_state = self.table_state.clone();
+        let tasks = self.visible_tasks(app_state);
         
         // If there's a selected task ID in the app state, select it in the table
         if let Some(task_id) = app_state.selected_task_id {
-            // Find the index of the task in the sorted list
-            let mut tasks: Vec<&TaskState> = app_state.tasks.values().collect();
-            Self::sort_tasks(&mut tasks, view.sort_field, view.sort_ascending);
-            
-            if let Some(index) = tasks.iter().position(|task| task.id == task_id) {
-                view.table_state.select(Some(index));
-            }
+            table_state.select(tasks.iter().position(|task| task.id == task_id));
         }
         
         // Create layout and render components
@@ -79,8 +303,76 @@ impl TaskListView {
             ])
             .split(area);
             
-        Self::render_header(frame, chunks[0], app_state, theme, &view);
-        Self::render_tasks_table(frame, chunks[1], app_state, theme, &mut view);
+        Self::render_header(frame, chunks[0], app_state, theme, self, tasks.len());
+        
+        // Flag stalled tasks with a badge next to their name
+        let stalled: HashSet<u64> = Straggler::find(app_state, chrono::Utc::now())
+            .into_iter()
+            .map(|straggler| straggler.task_id)
+            .collect();
+        if self.tree {
+            self.render_tree(frame, chunks[1], app_state, &tasks, &stalled, theme);
+        } else {
+            Self::render_tasks_table(frame, chunks[1], tasks, &stalled, theme, &mut table_state);
+        }
+    }
+    
+    /// Render the tasks as a workflow tree with a rollup per group.
+    fn render_tree(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        app_state: &AppState,
+        tasks: &[&TaskState],
+        stalled: &HashSet<u64>,
+        theme: &Theme,
+    ) {
+        let groups = WorkflowGroup::group(tasks);
+        let rows = self.tree_rows(&groups);
+        
+        let header = Row::new(TREE_COLUMNS.iter().map(|(h, _)| Cell::from(*h).style(theme.header_style)));
+        
+        let table_rows: Vec<Row> = rows.iter().filter_map(|&row| match row {
+            TreeRow::Workflow(w) => {
+                let workflow = &groups[w];
+                let open = self.expanded.contains(&workflow.id);
+                Some(rollup_row(format!("{} {}", fold_marker(open), workflow.id), &workflow.rollup, theme))
+            }
+            TreeRow::Call(w, c) => {
+                let call = &groups[w].calls[c];
+                let open = self.expanded.contains(&call_key(&groups[w], c));
+                Some(rollup_row(format!("  {} {}", fold_marker(open), call.name), &call.rollup, theme))
+            }
+            TreeRow::Task(id) => {
+                let task = app_state.tasks.get(&id)?;
+                Some(shard_row(task, stalled.contains(&id), theme))
+            }
+        }).collect();
+        
+        let table = Table::new(
+            table_rows,
+            [
+                Constraint::Percentage(30),
+                Constraint::Length(10),
+                Constraint::Length(27),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Length(7),
+                Constraint::Length(15),
+            ]
+        )
+            .header(header.style(theme.header_style))
+            .block(Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{} workflow runs", groups.iter().filter(|g| g.id != NO_WORKFLOW).count()))
+                .style(theme.block_style))
+            .highlight_style(theme.selected_style);
+        
+        let mut tree_state = self.tree_state.clone();
+        tree_state.select((!rows.is_empty()).then(|| self.tree_cursor(rows.len())));
+        frame.render_stateful_widget(table, area, &mut tree_state);
     }
     
     /// Render the header with filter and search info.
@@ -90,8 +382,19 @@ impl TaskListView {
         app_state: &AppState,
         theme: &Theme,
         view: &TaskListView,
+        visible: usize,
     ) {
-        let title = format!("Tasks ({} total)", app_state.tasks.len());
+        let title = if visible == app_state.tasks.len() {
+            format!("Tasks ({} total)", app_state.tasks.len())
+        } else {
+            format!("Tasks ({} of {})", visible, app_state.tasks.len())
+        };
+        
+        // Show filter information
+        let filter_info = format!(
+            "Filter: {}",
+            view.status_filter.map_or("All", |status| status.to_string())
+        );
         
         // Show sort information
         let sort_info = format!(
@@ -114,7 +417,16 @@ impl TaskListView {
             Span::raw(" | "),
             Span::styled(sort_info, theme.label_style),
             Span::raw(" | "),
-            Span::styled("Press Enter to view details", theme.help_style),
+            Span::styled(filter_info, theme.label_style),
+            Span::raw(" | "),
+            Span::styled(
+                if view.tree {
+                    "Enter: expand/details, ←/→: fold, v: flat list, f: filter, e/E: export CSV/JSON"
+                } else {
+                    "Enter: details, f: filter, v: tree, e/E: export CSV/JSON, R: report"
+                },
+                theme.help_style,
+            ),
         ]);
         
         let header = Paragraph::new(header_text)
@@ -128,9 +440,10 @@ impl TaskListView {
     fn render_tasks_table(
         frame: &mut Frame,
         area: Rect,
-        app_state: &AppState,
+        tasks: Vec<&TaskState>,
+        stalled: &HashSet<u64>,
         theme: &Theme,
-        view: &mut TaskListView,
+        table_state: &mut TableState,
     ) {
         // Create the table block
         let table_block = Block::default()
@@ -138,20 +451,13 @@ impl TaskListView {
             .style(theme.block_style);
         
         // Create the table header
-        let header_cells = ["ID", "Name", "Status", "Progress", "Duration", "Backend", "CPU", "Memory"]
-            .iter()
-            .map(|h| {
-                Cell::from(*h).style(theme.header_style)
-            });
+        let header_cells = LIST_COLUMNS.iter().map(|(h, _)| Cell::from(*h).style(theme.header_style));
         let header = Row::new(header_cells).style(theme.header_style);
         
-        // Sort the tasks based on the current sort field and direction
-        let mut tasks: Vec<&TaskState> = app_state.tasks.values().collect();
-        Self::sort_tasks(&mut tasks, view.sort_field, view.sort_ascending);
-        
         // Format task rows
-        let rows = tasks.into_iter().map(|task| {
-            format_task_row(task, view.table_state.selected() == Some(task.id.try_into().unwrap()))
+        let selected = table_state.selected();
+        let rows = tasks.into_iter().enumerate().map(|(index, task)| {
+            format_task_row(task, selected == Some(index), stalled.contains(&task.id), theme)
         });
         
         // Create the table
@@ -163,6 +469,7 @@ impl TaskListView {
                 Constraint::Length(10),
                 Constraint::Length(10),
                 Constraint::Length(10),
+                Constraint::Length(9),
                 Constraint::Length(15),
                 Constraint::Length(8),
                 Constraint::Length(10),
@@ -173,7 +480,7 @@ impl TaskListView {
             .highlight_style(theme.selected_style);
         
         // Render the table with state
-        frame.render_stateful_widget(table, area, &mut view.table_state);
+        frame.render_stateful_widget(table, area, table_state);
     }
     
     /// Sort tasks by the given field.
@@ -213,15 +520,28 @@ impl TaskListView {
     }
     
     /// Handle keyboard input.
-    pub fn handle_key_event(&mut self, key: crossterm::event::KeyEvent, _app_state: &mut AppState) -> eyre::Result<()> {
+    pub fn handle_key_event(&mut self, key: crossterm::event::KeyEvent, app_state: &mut AppState) -> eyre::Result<()> {
         use crossterm::event::KeyCode;
         
+        if key.code == KeyCode::Char('v') {
+            self.toggle_tree();
+            return Ok(());
+        }
+        if self.tree && matches!(
+            key.code,
+            KeyCode::Down | KeyCode::Up | KeyCode::Home | KeyCode::End | KeyCode::Left | KeyCode::Right
+                | KeyCode::Char('j' | 'k' | 'g' | 'G' | 'h' | 'l')
+        ) {
+            self.handle_tree_key(key.code, app_state);
+            return Ok(());
+        }
+        
         match key.code {
             // Navigation
-            KeyCode::Down | KeyCode::Char('j') => self.next(),
-            KeyCode::Up | KeyCode::Char('k') => self.previous(),
-            KeyCode::Home | KeyCode::Char('g') => self.first(),
-            KeyCode::End | KeyCode::Char('G') => self.last(),
+            KeyCode::Down | KeyCode::Char('j') => self.next(app_state),
+            KeyCode::Up | KeyCode::Char('k') => self.previous(app_state),
+            KeyCode::Home | KeyCode::Char('g') => self.first(app_state),
+            KeyCode::End | KeyCode::Char('G') => self.last(app_state),
             
             // Sorting
             KeyCode::Char('1') => self.toggle_sort(SortField::Id),
@@ -236,49 +556,59 @@ impl TaskListView {
             // Toggle direction
             KeyCode::Char('i') => self.sort_ascending = !self.sort_ascending,
             
+            // Filtering
+            KeyCode::Char('f') => self.cycle_filter(),
+            
             _ => {}
         }
         
         Ok(())
     }
     
-    /// Select the next task.
-    pub fn next(&mut self) {
-        let i = match self.table_state.selected() {
-            Some(i) => {
-                i+1
-            }
+    /// Select the next task in display order.
+    pub fn next(&mut self, app_state: &mut AppState) {
+        let tasks = self.visible_tasks(app_state);
+        let index = match self.selected_index(&tasks, app_state) {
+            Some(i) => (i + 1).min(tasks.len().saturating_sub(1)),
             None => 0,
         };
-        self.table_state.select(Some(i));
+        app_state.selected_task_id = tasks.get(index).map(|task| task.id);
     }
     
-    /// Select the previous task.
-    pub fn previous(&mut self) {
-        let i = match self.table_state.selected() {
-            Some(i) => {
-                if i > 0 {
-                    i - 1
-                } else {
-                    0
-                }
-            }
-            None => 0,
-        };
-        self.table_state.select(Some(i));
+    /// Select the previous task in display order.
+    pub fn previous(&mut self, app_state: &mut AppState) {
+        let tasks = self.visible_tasks(app_state);
+        let index = self.selected_index(&tasks, app_state).map_or(0, |i| i.saturating_sub(1));
+        app_state.selected_task_id = tasks.get(index).map(|task| task.id);
     }
     
     /// Select the first task.
-    pub fn first(&mut self) {
-        self.table_state.select(Some(0));
+    pub fn first(&mut self, app_state: &mut AppState) {
+        app_state.selected_task_id = self.visible_tasks(app_state).first().map(|task| task.id);
     }
     
     /// Select the last task.
-    pub fn last(&mut self) {
-        // This requires knowing the number of tasks
-        // In practice, you'd get this from the AppState
-        // Just set a placeholder here
-        self.table_state.select(Some(0));
+    pub fn last(&mut self, app_state: &mut AppState) {
+        app_state.selected_task_id = self.visible_tasks(app_state).last().map(|task| task.id);
+    }
+    
+    /// Position of the selected task among the given tasks.
+    fn selected_index(&self, tasks: &[&TaskState], app_state: &AppState) -> Option<usize> {
+        let selected = app_state.selected_task_id?;
+        tasks.iter().position(|task| task.id == selected)
+    }
+    
+    /// Cycle the status filter through all statuses and back to showing everything.
+    pub fn cycle_filter(&mut self) {
+        self.status_filter = match self.status_filter {
+            None => Some(TaskStatus::Created),
+            Some(TaskStatus::Created) => Some(TaskStatus::Queued),
+            Some(TaskStatus::Queued) => Some(TaskStatus::Running),
+            Some(TaskStatus::Running) => Some(TaskStatus::Completed),
+            Some(TaskStatus::Completed) => Some(TaskStatus::Failed),
+            Some(TaskStatus::Failed) => Some(TaskStatus::Cancelled),
+            Some(TaskStatus::Cancelled) => None,
+        };
     }
     
     /// Toggle sort by the given field.
@@ -293,7 +623,7 @@ impl TaskListView {
 }
 
 /// Format a duration as a human-readable string.
-fn format_duration(duration: &chrono::Duration) -> String {
+pub fn format_duration(duration: &chrono::Duration) -> String {
     let seconds = duration.num_seconds();
     if seconds < 60 {
         format!("{}s", seconds)
@@ -304,18 +634,84 @@ fn format_duration(duration: &chrono::Duration) -> String {
     }
 }
 
-fn format_task_row(task: &TaskState, _is_selected: bool) -> Row {
+/// Format a running task's estimated time to completion, or "-" if it
+/// cannot be estimated.
+pub fn format_eta(task: &TaskState) -> String {
+    task.eta(chrono::Utc::now())
+        .map(|eta| format_duration(&eta))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Expansion key of a call within its workflow run.
+fn call_key(workflow: &WorkflowGroup, call: usize) -> String {
+    format!("{}/{}", workflow.id, workflow.calls[call].name)
+}
+
+/// Marker in front of a tree group showing whether it is expanded.
+fn fold_marker(open: bool) -> &'static str {
+    if open { "▾" } else { "▸" }
+}
+
+/// Format progress from 0.0 to 1.0 as a bar with a percentage.
+fn progress_bar(progress: f32) -> String {
+    let progress = progress.clamp(0.0, 1.0);
+    let percentage = (progress * 100.0).round() as u8;
+    let bar_width = 20;
+    let filled = (bar_width as f32 * progress) as usize;
+    let empty = bar_width - filled;
+    
+    format!("[{}{}] {}%", 
+        "█".repeat(filled), 
+        "░".repeat(empty), 
+        percentage
+    )
+}
+
+/// Tree row for a workflow run or call with its rollup.
+fn rollup_row(name: String, rollup: &Rollup, theme: &Theme) -> Row<'static> {
+    let status = rollup.status();
+    let failed_style = if rollup.failed > 0 { theme.failed_style } else { theme.normal_text };
+    Row::new(vec![
+        Cell::from(name).style(theme.label_style.add_modifier(Modifier::BOLD)),
+        Cell::from(status.to_string()).style(theme.task_status_style(status)),
+        Cell::from(progress_bar(rollup.progress() as f32)),
+        Cell::from(rollup.total.to_string()),
+        Cell::from(rollup.waiting.to_string()),
+        Cell::from(rollup.running.to_string()),
+        Cell::from(rollup.completed.to_string()),
+        Cell::from(rollup.failed.to_string()).style(failed_style),
+        Cell::from(""),
+    ])
+}
+
+/// Tree row for a shard of an expanded call.
+fn shard_row(task: &TaskState, stalled: bool, theme: &Theme) -> Row<'static> {
+    let shard = task.shard_index.map_or_else(|| format!("#{}", task.id), |index| format!("[{}]", index));
+    let mut name = vec![Span::raw(format!("      {} {}", shard, task.name))];
+    if stalled {
+        name.push(Span::raw(" "));
+        name.push(Span::styled("Stalled", theme.warning_style.add_modifier(Modifier::REVERSED)));
+    }
+    let progress = match task.progress {
+        Some(progress) => progress_bar(progress),
+        None => String::new(),
+    };
+    Row::new(vec![
+        Cell::from(Line::from(name)),
+        Cell::from(task.status.to_string()).style(theme.task_status_style(task.status)),
+        Cell::from(progress),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(""),
+        Cell::from(task.backend.clone()),
+    ])
+}
+
+fn format_task_row(task: &TaskState, _is_selected: bool, stalled: bool, theme: &Theme) -> Row<'static> {
     let progress_display = if let Some(progress) = task.progress {
-        let percentage = (progress * 100.0).round() as u8;
-        let bar_width = 20;
-        let filled = (bar_width as f32 * progress) as usize;
-        let empty = bar_width - filled;
-        
-        format!("[{}{}] {}%", 
-            "█".repeat(filled), 
-            "░".repeat(empty), 
-            percentage
-        )
+        progress_bar(progress)
     } else {
         match task.status {
             TaskStatus::Created => "[    pending    ]".to_string(),
@@ -327,28 +723,78 @@ fn format_task_row(task: &TaskState, _is_selected: bool) -> Row {
         }
     };
     
+    let mut name = vec![Span::raw(task.name.clone())];
+    if stalled {
+        name.push(Span::raw(" "));
+        name.push(Span::styled("Stalled", theme.warning_style.add_modifier(Modifier::REVERSED)));
+    }
+    let name = Line::from(name);
+    
     // Create the row with the progress bar
     Row::new(vec![
-        Cell::from(format!("{}", task.id)).style(Style::default()),
-        Cell::from(task.name.clone()),
-        Cell::from(task.status.to_string()).style(get_status_style(task.status)),
+        Cell::from(format!("{}", task.id)).style(theme.normal_text),
+        Cell::from(name),
+        Cell::from(task.status.to_string()).style(theme.task_status_style(task.status)),
         Cell::from(progress_display),
         Cell::from(format_duration(&task.elapsed())),
+        Cell::from(format_eta(task)),
+        Cell::from(task.backend.clone()),
+        Cell::from(task.cpu_usage.to_string()),
+        Cell::from(task.memory_usage.to_string()),
     ])
 }
 
-fn get_status_style(status: TaskStatus) -> Style {
-    match status {
-        TaskStatus::Created => Style::default().fg(Color::Blue),
-        TaskStatus::Queued => Style::default().fg(Color::Yellow),
-        TaskStatus::Running => Style::default().fg(Color::Green),
-        TaskStatus::Completed => Style::default().fg(Color::Cyan),
-        TaskStatus::Failed => Style::default().fg(Color::Red),
-        TaskStatus::Cancelled => Style::default().fg(Color::Gray),
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use crate::state::testing::{self, state_with};
+
+    fn state() -> AppState {
+        let mut failed = testing::task(2, "align").with_workflow("run-1", "align", Some(1));
+        failed.transition(TaskStatus::Failed, chrono::Utc::now());
+        failed.error = Some("exit code 1".into());
+        state_with([
+            testing::task(1, "align").with_workflow("run-1", "align", Some(0)),
+            failed,
+            testing::task(3, "report"),
+        ])
     }
-}
 
+    fn column<'a>(table: &'a ExportTable, name: &str) -> Vec<&'a Value> {
+        let index = table.columns.iter().position(|c| *c == name).unwrap();
+        table.rows.iter().map(|row| &row[index]).collect()
+    }
 
+    #[test]
+    fn exports_shown_columns_in_display_order() {
+        let view = TaskListView::new().sort_by(SortField::Id, false);
+        let table = view.export_table(&state());
+        assert_eq!(table.columns, [
+            "id", "name", "status", "progress", "duration_secs", "eta_secs", "backend", "cpu_cores", "memory_bytes", "error",
+        ]);
+        assert_eq!(column(&table, "id"), [&json!(3), &json!(2), &json!(1)]);
+        assert!(table.rows.iter().all(|row| row.len() == table.columns.len()));
 
+        let failed = TaskListView::new().filter_by(Some(TaskStatus::Failed)).export_table(&state());
+        assert_eq!(column(&failed, "error"), [&json!("exit code 1")]);
+    }
 
+    #[test]
+    fn exports_tree_rows_as_shown() {
+        let mut view = TaskListView::new();
+        view.toggle_tree();
+        assert_eq!(column(&view.export_table(&state()), "name"), [&json!("run-1"), &json!(NO_WORKFLOW)]);
 
+        view.expanded.extend(["run-1".to_string(), "run-1/align".to_string()]);
+        let table = view.export_table(&state());
+        assert_eq!(table.columns[0], "level");
+        assert_eq!(table.columns.len(), TREE_COLUMNS.len() + 2);
+        assert_eq!(
+            column(&table, "level"),
+            [&json!("workflow"), &json!("call"), &json!("task"), &json!("task"), &json!("workflow")],
+        );
+        assert_eq!(column(&table, "failed")[..2], [&json!(1), &json!(1)]);
+        assert_eq!(column(&table, "error")[3], &json!("exit code 1"));
+    }
+}
@@ -1,10 +1,46 @@
 //! UI theme definition.
+//!
+//! Themes are built from a small palette of semantic colors. Besides the
+//! built-in palettes, a theme can be loaded from a TOML file that picks a
+//! base palette and overrides individual colors or styles:
+//!
+//! ```toml
+//! base = "light"
+//!
+//! [palette]
+//! success = "#1a7f37"
+//!
+//! [styles]
+//! header_style = { fg = "blue", modifiers = ["bold"] }
+//! selected_style = { fg = "white", bg = "blue" }
+//! ```
+//!
+//! Setting `NO_COLOR` replaces any theme with a monochrome one.
 
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use eyre::{bail, eyre, Result, WrapErr};
 use ratatui::style::{Color, Modifier, Style};
+use serde::Deserialize;
+
+use crate::state::{Percent, TaskStatus};
+
+/// Names of the built-in themes, in the order they are cycled through.
+pub const BUILTIN_THEMES: [&str; 4] = ["dark", "light", "high-contrast", "deuteranopia"];
+
+/// Name of the monochrome theme used when `NO_COLOR` is set.
+pub const NO_COLOR_THEME: &str = "no-color";
+
+/// Directory (inside the config directory) searched for named theme files.
+const THEME_DIR: &str = "crankshaft-tui/themes";
 
 /// Theme for the application UI.
 #[derive(Debug, Clone)]
 pub struct Theme {
+    /// Name of the theme
+    pub name: String,
+
     // Basic styles
     pub normal_text: Style,
     pub selected_style: Style,
@@ -12,16 +48,16 @@ pub struct Theme {
     pub header_style: Style,
     pub label_style: Style,
     pub value_style: Style,
-    
+
     // Status styles
     pub error_style: Style,
     pub help_style: Style,
     pub status_live: Style,
     pub status_paused: Style,
-    
+
     // Key styles
     pub key_style: Style,
-    
+
     // Task status styles
     pub created_style: Style,
     pub queued_style: Style,
@@ -29,55 +65,503 @@ pub struct Theme {
     pub completed_style: Style,
     pub failed_style: Style,
     pub cancelled_style: Style,
-    
+
     // Backend status styles
     pub healthy_style: Style,
     pub warning_style: Style,
     pub critical_style: Style,
     pub offline_style: Style,
-    
+
     // Chart and graph styles
     pub sparkline_style: Style,
+    pub sparkline_min_style: Style,
+    pub sparkline_max_style: Style,
+    pub cpu_style: Style,
+    pub memory_style: Style,
+    pub empty_style: Style,
+}
+
+/// Semantic colors a theme is built from.
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    pub text: Color,
+    pub dim: Color,
+    pub muted: Color,
+    pub accent: Color,
+    pub selection_fg: Color,
+    pub selection_bg: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub danger: Color,
+    pub created: Color,
+    pub queued: Color,
+    pub running: Color,
+    pub completed: Color,
+    pub failed: Color,
+    pub cancelled: Color,
+    pub cpu: Color,
+    pub memory: Color,
+    pub low: Color,
+    pub high: Color,
+    /// Modifier added to status styles so they do not rely on color alone
+    pub emphasis: Modifier,
+}
+
+impl Palette {
+    /// The original dark palette using the 16 standard terminal colors.
+    pub fn dark() -> Self {
+        Self {
+            text: Color::White,
+            dim: Color::Gray,
+            muted: Color::DarkGray,
+            accent: Color::Cyan,
+            selection_fg: Color::Black,
+            selection_bg: Color::White,
+            success: Color::Green,
+            warning: Color::Yellow,
+            danger: Color::Red,
+            created: Color::Blue,
+            queued: Color::Cyan,
+            running: Color::Yellow,
+            completed: Color::Green,
+            failed: Color::Red,
+            cancelled: Color::Gray,
+            cpu: Color::Green,
+            memory: Color::Blue,
+            low: Color::Blue,
+            high: Color::Red,
+            emphasis: Modifier::empty(),
+        }
+    }
+
+    /// Dark text on a light terminal background.
+    pub fn light() -> Self {
+        Self {
+            text: Color::Black,
+            dim: Color::Rgb(0x57, 0x60, 0x6a),
+            muted: Color::Rgb(0x8c, 0x95, 0x9f),
+            accent: Color::Rgb(0x09, 0x69, 0xda),
+            selection_fg: Color::White,
+            selection_bg: Color::Rgb(0x09, 0x69, 0xda),
+            success: Color::Rgb(0x1a, 0x7f, 0x37),
+            warning: Color::Rgb(0x9a, 0x67, 0x00),
+            danger: Color::Rgb(0xcf, 0x22, 0x2e),
+            created: Color::Rgb(0x82, 0x50, 0xdf),
+            queued: Color::Rgb(0x09, 0x69, 0xda),
+            running: Color::Rgb(0x9a, 0x67, 0x00),
+            completed: Color::Rgb(0x1a, 0x7f, 0x37),
+            failed: Color::Rgb(0xcf, 0x22, 0x2e),
+            cancelled: Color::Rgb(0x57, 0x60, 0x6a),
+            cpu: Color::Rgb(0x1a, 0x7f, 0x37),
+            memory: Color::Rgb(0x82, 0x50, 0xdf),
+            low: Color::Rgb(0x09, 0x69, 0xda),
+            high: Color::Rgb(0xcf, 0x22, 0x2e),
+            emphasis: Modifier::empty(),
+        }
+    }
+
+    /// Bright colors and bold status text for low-vision use.
+    pub fn high_contrast() -> Self {
+        Self {
+            text: Color::White,
+            dim: Color::White,
+            muted: Color::Gray,
+            accent: Color::LightCyan,
+            selection_fg: Color::Black,
+            selection_bg: Color::LightYellow,
+            success: Color::LightGreen,
+            warning: Color::LightYellow,
+            danger: Color::LightRed,
+            created: Color::LightBlue,
+            queued: Color::LightCyan,
+            running: Color::LightYellow,
+            completed: Color::LightGreen,
+            failed: Color::LightRed,
+            cancelled: Color::White,
+            cpu: Color::LightGreen,
+            memory: Color::LightCyan,
+            low: Color::LightBlue,
+            high: Color::LightMagenta,
+            emphasis: Modifier::BOLD,
+        }
+    }
+
+    /// Okabe-Ito colors that stay distinct with red-green color blindness.
+    pub fn deuteranopia() -> Self {
+        let blue = Color::Rgb(0, 114, 178);
+        let sky_blue = Color::Rgb(86, 180, 233);
+        let orange = Color::Rgb(230, 159, 0);
+        let yellow = Color::Rgb(240, 228, 66);
+        let vermillion = Color::Rgb(213, 94, 0);
+        let purple = Color::Rgb(204, 121, 167);
+
+        Self {
+            text: Color::White,
+            dim: Color::Gray,
+            muted: Color::DarkGray,
+            accent: sky_blue,
+            selection_fg: Color::Black,
+            selection_bg: sky_blue,
+            success: blue,
+            warning: yellow,
+            danger: vermillion,
+            created: purple,
+            queued: sky_blue,
+            running: yellow,
+            completed: blue,
+            failed: vermillion,
+            cancelled: Color::Gray,
+            cpu: sky_blue,
+            memory: orange,
+            low: blue,
+            high: vermillion,
+            emphasis: Modifier::BOLD,
+        }
+    }
+
+    /// Look up a built-in palette by theme name.
+    pub fn builtin(name: &str) -> Option<Self> {
+        match name {
+            "default" | "dark" => Some(Self::dark()),
+            "light" => Some(Self::light()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "deuteranopia" => Some(Self::deuteranopia()),
+            _ => None,
+        }
+    }
+
+    /// Mutable access to a palette color by name, for theme files.
+    fn color_mut(&mut self, name: &str) -> Option<&mut Color> {
+        Some(match name {
+            "text" => &mut self.text,
+            "dim" => &mut self.dim,
+            "muted" => &mut self.muted,
+            "accent" => &mut self.accent,
+            "selection_fg" => &mut self.selection_fg,
+            "selection_bg" => &mut self.selection_bg,
+            "success" => &mut self.success,
+            "warning" => &mut self.warning,
+            "danger" => &mut self.danger,
+            "created" => &mut self.created,
+            "queued" => &mut self.queued,
+            "running" => &mut self.running,
+            "completed" => &mut self.completed,
+            "failed" => &mut self.failed,
+            "cancelled" => &mut self.cancelled,
+            "cpu" => &mut self.cpu,
+            "memory" => &mut self.memory,
+            "low" => &mut self.low,
+            "high" => &mut self.high,
+            _ => return None,
+        })
+    }
 }
 
 impl Default for Theme {
     fn default() -> Self {
+        Self::from_palette("dark", &Palette::dark())
+    }
+}
+
+impl Theme {
+    /// Build a theme from a palette.
+    pub fn from_palette(name: &str, p: &Palette) -> Self {
+        let fg = |color: Color| Style::default().fg(color);
+        let status = |color: Color| Style::default().fg(color).add_modifier(p.emphasis);
+
         Self {
+            name: name.to_string(),
+
             // Basic styles
-            normal_text: Style::default().fg(Color::White),
-            selected_style: Style::default().fg(Color::Black).bg(Color::White),
+            normal_text: fg(p.text),
+            selected_style: Style::default().fg(p.selection_fg).bg(p.selection_bg),
             block_style: Style::default(),
-            header_style: Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-            label_style: Style::default().fg(Color::Gray),
-            value_style: Style::default().fg(Color::White),
-            
+            header_style: fg(p.accent).add_modifier(Modifier::BOLD),
+            label_style: fg(p.dim),
+            value_style: fg(p.text),
+
             // Status styles
-            error_style: Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
-            help_style: Style::default().fg(Color::Gray),
-            status_live: Style::default().fg(Color::Green),
-            status_paused: Style::default().fg(Color::Yellow),
-            
+            error_style: fg(p.danger).add_modifier(Modifier::BOLD),
+            help_style: fg(p.dim),
+            status_live: status(p.success),
+            status_paused: status(p.warning),
+
             // Key styles
-            key_style: Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD),
-            
+            key_style: fg(p.accent).add_modifier(Modifier::BOLD),
+
             // Task status styles
-            created_style: Style::default().fg(Color::Blue),
-            queued_style: Style::default().fg(Color::Cyan),
-            running_style: Style::default().fg(Color::Yellow),
-            completed_style: Style::default().fg(Color::Green),
-            failed_style: Style::default().fg(Color::Red),
-            cancelled_style: Style::default().fg(Color::Gray),
-            
+            created_style: status(p.created),
+            queued_style: status(p.queued),
+            running_style: status(p.running),
+            completed_style: status(p.completed),
+            failed_style: status(p.failed),
+            cancelled_style: status(p.cancelled),
+
             // Backend status styles
-            healthy_style: Style::default().fg(Color::Green),
-            warning_style: Style::default().fg(Color::Yellow),
-            critical_style: Style::default().fg(Color::Red),
-            offline_style: Style::default().fg(Color::DarkGray),
-            
+            healthy_style: status(p.success),
+            warning_style: status(p.warning),
+            critical_style: status(p.danger),
+            offline_style: fg(p.muted),
+
             // Chart and graph styles
-            sparkline_style: Style::default().fg(Color::Green),
+            sparkline_style: fg(p.success),
+            sparkline_min_style: fg(p.low),
+            sparkline_max_style: fg(p.high),
+            cpu_style: fg(p.cpu),
+            memory_style: fg(p.memory),
+            empty_style: fg(p.muted),
+        }
+    }
+
+    /// Monochrome theme that distinguishes states with text attributes only.
+    pub fn no_color() -> Self {
+        let plain = Style::default();
+        let bold = Style::default().add_modifier(Modifier::BOLD);
+        let dim = Style::default().add_modifier(Modifier::DIM);
+
+        Self {
+            name: NO_COLOR_THEME.to_string(),
+            normal_text: plain,
+            selected_style: Style::default().add_modifier(Modifier::REVERSED),
+            block_style: plain,
+            header_style: bold,
+            label_style: plain,
+            value_style: plain,
+            error_style: bold,
+            help_style: dim,
+            status_live: plain,
+            status_paused: bold,
+            key_style: bold,
+            created_style: dim,
+            queued_style: plain,
+            running_style: bold,
+            completed_style: plain,
+            failed_style: bold.add_modifier(Modifier::UNDERLINED),
+            cancelled_style: dim,
+            healthy_style: plain,
+            warning_style: bold,
+            critical_style: bold.add_modifier(Modifier::UNDERLINED),
+            offline_style: dim,
+            sparkline_style: plain,
+            sparkline_min_style: plain,
+            sparkline_max_style: bold,
+            cpu_style: plain,
+            memory_style: plain,
+            empty_style: dim,
         }
     }
+
+    /// Look up a built-in theme by name.
+    pub fn from_name(name: &str) -> Result<Self> {
+        // "default" is kept as an alias for the original dark theme
+        let name = if name == "default" { "dark" } else { name };
+        match Palette::builtin(name) {
+            Some(palette) => Ok(Self::from_palette(name, &palette)),
+            None => bail!("unknown theme '{}' (built-in themes: {})", name, BUILTIN_THEMES.join(", ")),
+        }
+    }
+
+    /// Load a theme by built-in name, file path, or name of a file in the
+    /// themes directory.
+    pub fn load(name: &str) -> Result<Self> {
+        if Palette::builtin(name).is_some() {
+            return Self::from_name(name);
+        }
+
+        let path = Path::new(name);
+        if path.extension().is_some_and(|ext| ext == "toml") || path.exists() {
+            return Self::from_file(path);
+        }
+
+        match theme_path(name) {
+            Some(path) if path.exists() => Self::from_file(&path),
+            _ => Self::from_name(name),
+        }
+    }
+
+    /// Load the configured theme, or the monochrome theme if `NO_COLOR` is set.
+    pub fn resolve(name: &str) -> Result<Self> {
+        if no_color_requested() {
+            return Ok(Self::no_color());
+        }
+        Self::load(name)
+    }
+
+    /// Read a theme file.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .wrap_err_with(|| format!("failed to read theme {}", path.display()))?;
+        let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("custom");
+        Self::parse(name, &contents).wrap_err_with(|| format!("invalid theme {}", path.display()))
+    }
+
+    /// Parse theme TOML.
+    pub fn parse(name: &str, contents: &str) -> Result<Self> {
+        let file: ThemeFile = toml::from_str(contents)?;
+
+        let base = file.base.as_deref().unwrap_or("dark");
+        let mut palette = Palette::builtin(base)
+            .ok_or_else(|| eyre!("unknown base theme '{}'", base))?;
+        for (key, value) in &file.palette {
+            let color = palette.color_mut(key).ok_or_else(|| eyre!("unknown palette color '{}'", key))?;
+            *color = parse_color(value)?;
+        }
+
+        let mut theme = Self::from_palette(name, &palette);
+        for (key, spec) in &file.styles {
+            let style = theme.style_mut(key).ok_or_else(|| eyre!("unknown style '{}'", key))?;
+            *style = spec.to_style()?;
+        }
+
+        Ok(theme)
+    }
+
+    /// The built-in theme after this one, for runtime switching.
+    pub fn next_builtin(&self) -> Self {
+        let next = BUILTIN_THEMES.iter()
+            .position(|name| *name == self.name)
+            .map_or(0, |i| (i + 1) % BUILTIN_THEMES.len());
+        Self::from_name(BUILTIN_THEMES[next]).unwrap_or_default()
+    }
+
+    /// Style for a task status.
+    pub fn task_status_style(&self, status: TaskStatus) -> Style {
+        match status {
+            TaskStatus::Created => self.created_style,
+            TaskStatus::Queued => self.queued_style,
+            TaskStatus::Running => self.running_style,
+            TaskStatus::Completed => self.completed_style,
+            TaskStatus::Failed => self.failed_style,
+            TaskStatus::Cancelled => self.cancelled_style,
+        }
+    }
+
+    /// Style for a utilization percentage: healthy, warning above 50%, critical above 80%.
+    pub fn usage_style(&self, percent: Percent) -> Style {
+        if percent.0 > 80.0 {
+            self.critical_style
+        } else if percent.0 > 50.0 {
+            self.warning_style
+        } else {
+            self.healthy_style
+        }
+    }
+
+    /// Style for the share of requested resources actually used: critical
+    /// below 25%, warning below 50%.
+    pub fn efficiency_style(&self, percent: Percent) -> Style {
+        if percent.0 < 25.0 {
+            self.critical_style
+        } else if percent.0 < 50.0 {
+            self.warning_style
+        } else {
+            self.healthy_style
+        }
+    }
+
+    /// Mutable access to a style by field name, for theme files.
+    fn style_mut(&mut self, name: &str) -> Option<&mut Style> {
+        Some(match name {
+            "normal_text" => &mut self.normal_text,
+            "selected_style" => &mut self.selected_style,
+            "block_style" => &mut self.block_style,
+            "header_style" => &mut self.header_style,
+            "label_style" => &mut self.label_style,
+            "value_style" => &mut self.value_style,
+            "error_style" => &mut self.error_style,
+            "help_style" => &mut self.help_style,
+            "status_live" => &mut self.status_live,
+            "status_paused" => &mut self.status_paused,
+            "key_style" => &mut self.key_style,
+            "created_style" => &mut self.created_style,
+            "queued_style" => &mut self.queued_style,
+            "running_style" => &mut self.running_style,
+            "completed_style" => &mut self.completed_style,
+            "failed_style" => &mut self.failed_style,
+            "cancelled_style" => &mut self.cancelled_style,
+            "healthy_style" => &mut self.healthy_style,
+            "warning_style" => &mut self.warning_style,
+            "critical_style" => &mut self.critical_style,
+            "offline_style" => &mut self.offline_style,
+            "sparkline_style" => &mut self.sparkline_style,
+            "sparkline_min_style" => &mut self.sparkline_min_style,
+            "sparkline_max_style" => &mut self.sparkline_max_style,
+            "cpu_style" => &mut self.cpu_style,
+            "memory_style" => &mut self.memory_style,
+            "empty_style" => &mut self.empty_style,
+            _ => return None,
+        })
+    }
+}
+
+/// Whether the user asked for no color through a non-empty `NO_COLOR`.
+pub fn no_color_requested() -> bool {
+    std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty())
 }
 
+/// Location of a named theme file in the themes directory.
+pub fn theme_path(name: &str) -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(THEME_DIR).join(format!("{}.toml", name)))
+}
+
+/// Theme file contents.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ThemeFile {
+    base: Option<String>,
+    palette: HashMap<String, String>,
+    styles: HashMap<String, StyleSpec>,
+}
+
+/// A style in a theme file: a foreground color or a full style table.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum StyleSpec {
+    Fg(String),
+    Full {
+        fg: Option<String>,
+        bg: Option<String>,
+        #[serde(default)]
+        modifiers: Vec<String>,
+    },
+}
+
+impl StyleSpec {
+    fn to_style(&self) -> Result<Style> {
+        match self {
+            StyleSpec::Fg(color) => Ok(Style::default().fg(parse_color(color)?)),
+            StyleSpec::Full { fg, bg, modifiers } => {
+                let mut style = Style::default();
+                if let Some(fg) = fg {
+                    style = style.fg(parse_color(fg)?);
+                }
+                if let Some(bg) = bg {
+                    style = style.bg(parse_color(bg)?);
+                }
+                for modifier in modifiers {
+                    style = style.add_modifier(parse_modifier(modifier)?);
+                }
+                Ok(style)
+            }
+        }
+    }
+}
 
+/// Parse a color name (`red`, `light-blue`), 256-color index or `#rrggbb`.
+fn parse_color(value: &str) -> Result<Color> {
+    Color::from_str(value).map_err(|_| eyre!("invalid color '{}'", value))
+}
+
+/// Parse a text modifier name.
+fn parse_modifier(value: &str) -> Result<Modifier> {
+    let modifier = match value.to_ascii_lowercase().as_str() {
+        "bold" => Modifier::BOLD,
+        "dim" => Modifier::DIM,
+        "italic" => Modifier::ITALIC,
+        "underlined" | "underline" => Modifier::UNDERLINED,
+        "reversed" | "reverse" => Modifier::REVERSED,
+        "crossed_out" | "strikethrough" => Modifier::CROSSED_OUT,
+        other => bail!("unknown modifier '{}'", other),
+    };
+    Ok(modifier)
+}
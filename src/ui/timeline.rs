@@ -0,0 +1,447 @@
+//! Timeline view plotting tasks as bars over time.
+//!
+//! Draws each task as a horizontal bar from its start to its end, or to now
+//! while it is active, in one lane per backend or per task group. Tasks that
+//! overlap in a lane are stacked on extra rows, which shows idle gaps,
+//! bursts and serialized work at a glance.
+
+use std::collections::BTreeMap;
+use chrono::{DateTime, Duration, Utc};
+use crossterm::event::{KeyCode, KeyEvent};
+use eyre::Result;
+use ratatui::Frame;
+use ratatui::buffer::Buffer;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::state::{AppState, TaskState, TaskStatus};
+use crate::ui::Theme;
+use crate::ui::task_list::format_duration;
+
+/// Window lengths to zoom between, in seconds.
+const SPANS: [i64; 6] = [60, 5 * 60, 15 * 60, 60 * 60, 6 * 60 * 60, 24 * 60 * 60];
+/// Width of the lane labels.
+const LABEL_WIDTH: u16 = 20;
+/// Rows a lane may stack overlapping tasks on; further tasks share the last.
+const MAX_LANE_ROWS: usize = 8;
+/// Columns between time axis labels.
+const TICK_SPACING: u16 = 16;
+
+/// What each lane holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LaneMode {
+    Backend,
+    /// Tasks of one call, or of one base name for untagged tasks
+    Group,
+}
+
+/// Timeline view state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineView {
+    lanes: LaneMode,
+    /// Index into `SPANS`, or `None` to fit every task
+    span: Option<usize>,
+    /// How far the window ends before now
+    offset: Duration,
+    /// First row shown
+    scroll: usize,
+}
+
+impl Default for TimelineView {
+    fn default() -> Self {
+        Self {
+            lanes: LaneMode::Backend,
+            span: None,
+            offset: Duration::zero(),
+            scroll: 0,
+        }
+    }
+}
+
+/// One row of the timeline: its lane label on the first row of a lane,
+/// and the tasks drawn on it.
+struct TimelineRow<'a> {
+    label: Option<String>,
+    tasks: Vec<&'a TaskState>,
+}
+
+impl TimelineView {
+    /// Create a new timeline view.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Handle key events for this view.
+    pub fn handle_key_event(&mut self, key: KeyEvent, app_state: &AppState) -> Result<()> {
+        let now = Utc::now();
+        let span = self.span_length(app_state, now);
+
+        match key.code {
+            KeyCode::Char('+') | KeyCode::Char('=') => {
+                // Largest fixed span shorter than the current one
+                self.span = Some(SPANS.iter().rposition(|&s| s < span.num_seconds()).unwrap_or(0));
+            }
+            KeyCode::Char('-') => {
+                let next = SPANS.iter().position(|&s| s > span.num_seconds());
+                self.span = Some(next.unwrap_or(SPANS.len() - 1));
+            }
+            KeyCode::Char('a') => {
+                self.span = None;
+                self.offset = Duration::zero();
+            }
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.fix_span(span);
+                self.offset += span / 4;
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.fix_span(span);
+                self.offset = (self.offset - span / 4).max(Duration::zero());
+            }
+            KeyCode::End => self.offset = Duration::zero(),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll += 1,
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Home | KeyCode::Char('g') => self.scroll = 0,
+            KeyCode::Char('v') => {
+                self.lanes = match self.lanes {
+                    LaneMode::Backend => LaneMode::Group,
+                    LaneMode::Group => LaneMode::Backend,
+                };
+                self.scroll = 0;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Switch from fitting every task to the nearest fixed span, so that
+    /// scrolling keeps the window length.
+    fn fix_span(&mut self, span: Duration) {
+        if self.span.is_none() {
+            self.span = Some(SPANS.iter().position(|&s| s >= span.num_seconds()).unwrap_or(SPANS.len() - 1));
+        }
+    }
+
+    /// Length of the window shown.
+    fn span_length(&self, app_state: &AppState, now: DateTime<Utc>) -> Duration {
+        match self.span {
+            Some(index) => Duration::seconds(SPANS[index]),
+            None => {
+                let earliest = app_state.tasks.values().map(|t| t.start_time).min().unwrap_or(now);
+                (now - earliest).max(Duration::seconds(SPANS[0]))
+            }
+        }
+    }
+
+    /// Render the timeline view.
+    pub fn render(&self, frame: &mut Frame, area: Rect, app_state: &AppState, theme: &Theme) {
+        let now = Utc::now();
+        let end = now - self.offset;
+        let start = end - self.span_length(app_state, now);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(2),  // Header
+                Constraint::Min(4),     // Lanes
+            ])
+            .split(area);
+
+        self.render_header(frame, chunks[0], start, end, theme);
+
+        let rows = self.rows(app_state, start, end, now);
+        let lanes = rows.iter().filter(|row| row.label.is_some()).count();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .title(format!(
+                "{} lanes | █ running  ░ queued | {} to {}",
+                lanes,
+                start.format("%H:%M:%S"),
+                end.format("%H:%M:%S"),
+            ))
+            .style(theme.block_style);
+        let inner = block.inner(chunks[1]);
+        frame.render_widget(block, chunks[1]);
+
+        if rows.is_empty() {
+            let empty = Paragraph::new(Span::styled("No tasks in this window", theme.help_style));
+            frame.render_widget(empty, inner);
+            return;
+        }
+        if inner.height < 2 || inner.width <= LABEL_WIDTH {
+            return;
+        }
+
+        let plot = Rect {
+            x: inner.x + LABEL_WIDTH,
+            width: inner.width - LABEL_WIDTH,
+            ..inner
+        };
+        let buf = frame.buffer_mut();
+        render_axis(buf, Rect { height: 1, ..plot }, start, end, theme);
+
+        let scroll = self.scroll.min(rows.len().saturating_sub(1));
+        for (i, row) in rows.iter().skip(scroll).take((inner.height - 1) as usize).enumerate() {
+            let y = inner.y + 1 + i as u16;
+            if let Some(label) = &row.label {
+                buf.set_stringn(inner.x, y, label, (LABEL_WIDTH - 1) as usize, theme.label_style);
+            }
+            for task in &row.tasks {
+                draw_bar(buf, Rect { y, height: 1, ..plot }, task, start, end, now, theme);
+            }
+        }
+
+        // Mark now when it is in view
+        if self.offset == Duration::zero() {
+            let x = plot.x + plot.width - 1;
+            for y in inner.y + 1..inner.y + inner.height {
+                let cell = buf.get_mut(x, y);
+                if cell.symbol() == " " {
+                    cell.set_symbol("┊").set_style(theme.help_style);
+                }
+            }
+        }
+    }
+
+    /// Render the header with the lane mode, window and key hints.
+    fn render_header(&self, frame: &mut Frame, area: Rect, start: DateTime<Utc>, end: DateTime<Utc>, theme: &Theme) {
+        let lanes = match self.lanes {
+            LaneMode::Backend => "backend",
+            LaneMode::Group => "task group",
+        };
+        let window = match self.span {
+            None => format!("all tasks ({})", format_duration(&(end - start))),
+            Some(index) => format_duration(&Duration::seconds(SPANS[index])),
+        };
+        let position = if self.offset == Duration::zero() {
+            "live".to_string()
+        } else {
+            format!("{} ago", format_duration(&self.offset))
+        };
+        let header_text = Line::from(vec![
+            Span::styled("Timeline", theme.header_style),
+            Span::raw(" | "),
+            Span::styled(
+                format!("Lanes: {} | Window: {}, {}", lanes, window, position),
+                theme.label_style,
+            ),
+            Span::raw(" | "),
+            Span::styled(
+                "v: lanes, +/-: zoom, a: fit all, ←/→: scroll, End: now, j/k: rows",
+                theme.help_style,
+            ),
+        ]);
+
+        let header = Paragraph::new(header_text)
+            .style(theme.normal_text)
+            .block(Block::default().borders(Borders::BOTTOM));
+
+        frame.render_widget(header, area);
+    }
+
+    /// Rows of every lane with tasks in the window, overlapping tasks
+    /// stacked onto the first row they fit on.
+    fn rows<'a>(
+        &self,
+        app_state: &'a AppState,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Vec<TimelineRow<'a>> {
+        let mut lanes: BTreeMap<&str, Vec<&TaskState>> = BTreeMap::new();
+        for task in app_state.tasks.values() {
+            if task.start_time <= end && task.finished_at().unwrap_or(now) >= start {
+                let lane = match self.lanes {
+                    LaneMode::Backend => task.backend.as_str(),
+                    LaneMode::Group => task.call(),
+                };
+                lanes.entry(lane).or_default().push(task);
+            }
+        }
+
+        let mut rows = Vec::new();
+        for (lane, mut tasks) in lanes {
+            tasks.sort_by_key(|t| (t.start_time, t.id));
+            let mut packed: Vec<(DateTime<Utc>, Vec<&TaskState>)> = Vec::new();
+            for task in tasks {
+                let finish = task.finished_at().unwrap_or(now);
+                let row = packed.iter().position(|(free_at, _)| *free_at < task.start_time);
+                match row {
+                    Some(row) => packed[row].0 = finish,
+                    None if packed.len() < MAX_LANE_ROWS => packed.push((finish, Vec::new())),
+                    None => {}
+                }
+                let row = row.unwrap_or(packed.len() - 1);
+                packed[row].1.push(task);
+            }
+            rows.extend(packed.into_iter().enumerate().map(|(i, (_, tasks))| TimelineRow {
+                label: (i == 0).then(|| lane.to_string()),
+                tasks,
+            }));
+        }
+        rows
+    }
+}
+
+/// Column of a time within the plot, clamped to it.
+fn column(area: Rect, start: DateTime<Utc>, end: DateTime<Utc>, at: DateTime<Utc>) -> u16 {
+    let span = (end - start).num_milliseconds().max(1) as f64;
+    let offset = (at - start).num_milliseconds() as f64 / span;
+    let x = (offset * area.width as f64).floor().clamp(0.0, (area.width - 1) as f64);
+    area.x + x as u16
+}
+
+/// Draw the time of every `TICK_SPACING`th column.
+fn render_axis(buf: &mut Buffer, area: Rect, start: DateTime<Utc>, end: DateTime<Utc>, theme: &Theme) {
+    let span = (end - start).num_milliseconds() as f64;
+    let format = if end - start > Duration::days(1) { "%d %H:%M" } else { "%H:%M:%S" };
+    let mut x = 0;
+    while x + 8 <= area.width {
+        let at = start + Duration::milliseconds((span * x as f64 / area.width as f64) as i64);
+        buf.set_string(area.x + x, area.y, format!("├{}", at.format(format)), theme.help_style);
+        x += TICK_SPACING;
+    }
+}
+
+/// Draw a task's bar: shaded while queued, solid while running or done.
+fn draw_bar(
+    buf: &mut Buffer,
+    area: Rect,
+    task: &TaskState,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    now: DateTime<Utc>,
+    theme: &Theme,
+) {
+    let finish = task.finished_at().unwrap_or(now);
+    let running = task.entered(TaskStatus::Running).unwrap_or(finish);
+    let from = column(area, start, end, task.start_time.max(start));
+    let until = column(area, start, end, finish.min(end));
+    let solid_from = column(area, start, end, running.clamp(start, end));
+    let style = theme.task_status_style(task.status);
+    for x in from..=until {
+        let symbol = if x < solid_from { "░" } else { "█" };
+        buf.get_mut(x, area.y).set_symbol(symbol).set_style(style);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use crate::state::testing::{self, state_with};
+
+    /// A task on `backend` that ran from `from` to `until` minutes ago, or
+    /// is still running.
+    fn task(id: u64, backend: &str, from: i64, until: Option<i64>, now: DateTime<Utc>) -> TaskState {
+        let mut task = testing::task(id, "align");
+        task.backend = backend.into();
+        task.start_time = now - Duration::minutes(from);
+        task.transition(TaskStatus::Running, task.start_time);
+        if let Some(until) = until {
+            task.transition(TaskStatus::Completed, now - Duration::minutes(until));
+        }
+        task
+    }
+
+    /// Labels and task IDs of each row.
+    fn layout(rows: &[TimelineRow]) -> Vec<(Option<String>, Vec<u64>)> {
+        rows.iter().map(|r| (r.label.clone(), r.tasks.iter().map(|t| t.id).collect())).collect()
+    }
+
+    #[test]
+    fn packs_overlapping_tasks_onto_rows() {
+        let now = Utc::now();
+        let state = state_with([
+            task(1, "docker", 60, Some(50), now),
+            task(2, "docker", 55, Some(45), now),
+            task(3, "docker", 48, Some(40), now),
+            task(4, "slurm", 30, None, now),
+            task(5, "docker", 120, Some(100), now),
+        ]);
+
+        let rows = TimelineView::new().rows(&state, now - Duration::hours(1), now, now);
+        assert_eq!(layout(&rows), [
+            (Some("docker".to_string()), vec![1, 3]),
+            (None, vec![2]),
+            (Some("slurm".to_string()), vec![4]),
+        ]);
+    }
+
+    #[test]
+    fn cancelled_tasks_end_when_cancelled() {
+        let now = Utc::now();
+        let mut cancelled = task(1, "docker", 30, None, now);
+        cancelled.transition(TaskStatus::Cancelled, now - Duration::minutes(25));
+        let state = state_with([cancelled, task(2, "docker", 20, None, now)]);
+
+        let rows = TimelineView::new().rows(&state, now - Duration::hours(1), now, now);
+        assert_eq!(layout(&rows), [(Some("docker".to_string()), vec![1, 2])]);
+
+        // Out of a window starting after the cancellation
+        let rows = TimelineView::new().rows(&state, now - Duration::minutes(22), now, now);
+        assert_eq!(layout(&rows), [(Some("docker".to_string()), vec![2])]);
+    }
+
+    #[test]
+    fn overflow_shares_the_last_row() {
+        let now = Utc::now();
+        let state = state_with((1..=10).map(|id| task(id, "docker", 30, None, now)));
+
+        let rows = TimelineView::new().rows(&state, now - Duration::hours(1), now, now);
+        assert_eq!(rows.len(), MAX_LANE_ROWS);
+        assert_eq!(layout(&rows)[MAX_LANE_ROWS - 1].1, vec![8, 9, 10]);
+    }
+
+    #[test]
+    fn group_lanes_by_call() {
+        let now = Utc::now();
+        let state = state_with([
+            task(1, "docker", 30, None, now).with_workflow("run-1", "sort", Some(0)),
+            task(2, "slurm", 30, None, now).with_workflow("run-1", "align", Some(0)),
+        ]);
+        let view = TimelineView { lanes: LaneMode::Group, ..TimelineView::new() };
+
+        let labels: Vec<Option<String>> = view.rows(&state, now - Duration::hours(1), now, now)
+            .into_iter()
+            .map(|r| r.label)
+            .collect();
+        assert_eq!(labels, [Some("align".to_string()), Some("sort".to_string())]);
+    }
+
+    #[test]
+    fn column_clamps_to_the_plot() {
+        let area = Rect::new(10, 0, 100, 1);
+        let start = Utc::now();
+        let end = start + Duration::minutes(10);
+
+        assert_eq!(column(area, start, end, start), 10);
+        assert_eq!(column(area, start, end, start + Duration::minutes(5)), 60);
+        assert_eq!(column(area, start, end, end), 109);
+        assert_eq!(column(area, start, end, start - Duration::hours(1)), 10);
+        assert_eq!(column(area, start, end, end + Duration::hours(1)), 109);
+        assert_eq!(column(area, start, start, start), 10);
+    }
+
+    #[test]
+    fn zoom_steps_through_spans() {
+        let state = AppState::new();
+        let mut view = TimelineView::new();
+        let press = |view: &mut TimelineView, c: char| {
+            view.handle_key_event(KeyEvent::from(KeyCode::Char(c)), &state).unwrap();
+            view.span
+        };
+
+        // Fitting an empty state shows the shortest span
+        assert_eq!(press(&mut view, '+'), Some(0));
+        assert_eq!(press(&mut view, '-'), Some(1));
+        assert_eq!(press(&mut view, '-'), Some(2));
+        assert_eq!(press(&mut view, '+'), Some(1));
+        for _ in 0..SPANS.len() {
+            press(&mut view, '-');
+        }
+        assert_eq!(view.span, Some(SPANS.len() - 1));
+        assert_eq!(press(&mut view, 'a'), None);
+    }
+}
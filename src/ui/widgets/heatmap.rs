@@ -0,0 +1,164 @@
+//! Heatmap widget for values over time.
+//!
+//! Draws one row per series and one cell per time bucket, shading each cell
+//! by its value from 0.0 to 1.0 and coloring it by level. A single row of
+//! cells holds as much history as a sparkline several rows high, so many
+//! series fit on a small terminal. The bottom row labels the time axis.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Widget},
+};
+
+/// Shades from an idle to a fully used cell.
+pub const SHADES: [&str; 5] = [" ", "░", "▒", "▓", "█"];
+
+/// Drawn for buckets without samples.
+const NO_DATA: &str = "·";
+
+/// A heatmap widget with one labelled row per series.
+pub struct Heatmap<'a> {
+    /// Block drawn around the heatmap.
+    block: Option<Block<'a>>,
+    /// Row labels and their values, oldest first.
+    rows: &'a [(String, Vec<Option<f32>>)],
+    /// Width of the label column.
+    label_width: u16,
+    /// Labels for the start and end of the time axis.
+    axis: Option<(&'a str, &'a str)>,
+    /// Styles for values up to 0.5, up to 0.8 and above.
+    styles: [Style; 3],
+    /// Style of the labels and empty buckets.
+    label_style: Style,
+}
+
+impl<'a> Heatmap<'a> {
+    pub fn new(rows: &'a [(String, Vec<Option<f32>>)]) -> Self {
+        Self {
+            block: None,
+            rows,
+            label_width: 12,
+            axis: None,
+            styles: [
+                Style::default().fg(Color::Green),
+                Style::default().fg(Color::Yellow),
+                Style::default().fg(Color::Red),
+            ],
+            label_style: Style::default().fg(Color::Gray),
+        }
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn label_width(mut self, width: u16) -> Self {
+        self.label_width = width;
+        self
+    }
+
+    /// Label the time axis, for example "-10m" and "now".
+    pub fn axis(mut self, start: &'a str, end: &'a str) -> Self {
+        self.axis = Some((start, end));
+        self
+    }
+
+    /// Styles for low, elevated and high values.
+    pub fn styles(mut self, low: Style, medium: Style, high: Style) -> Self {
+        self.styles = [low, medium, high];
+        self
+    }
+
+    pub fn label_style(mut self, style: Style) -> Self {
+        self.label_style = style;
+        self
+    }
+
+    /// Number of buckets that fit in a row of the given width, for callers
+    /// sizing their data to the area.
+    pub fn columns(width: u16, label_width: u16) -> usize {
+        width.saturating_sub(label_width) as usize
+    }
+}
+
+/// Shade of a value from 0.0 to 1.0; any use at all is at least the
+/// lightest shade.
+pub fn shade(value: f32) -> &'static str {
+    if value <= 0.0 {
+        SHADES[0]
+    } else {
+        let level = (value.min(1.0) * (SHADES.len() - 1) as f32).ceil() as usize;
+        SHADES[level.max(1)]
+    }
+}
+
+impl<'a> Widget for Heatmap<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+
+        let plot_width = Heatmap::columns(area.width, self.label_width) as u16;
+        if area.height == 0 || plot_width == 0 {
+            return;
+        }
+
+        let axis_rows = u16::from(self.axis.is_some() && area.height > 1);
+        let plot_x = area.x + self.label_width;
+        for (i, (label, values)) in self.rows.iter().take((area.height - axis_rows) as usize).enumerate() {
+            let y = area.y + i as u16;
+            buf.set_stringn(area.x, y, label, self.label_width.saturating_sub(1) as usize, self.label_style);
+
+            // Newest bucket at the right edge
+            let shown = values.len().min(plot_width as usize);
+            let x = plot_x + plot_width - shown as u16;
+            for (dx, value) in values[values.len() - shown..].iter().enumerate() {
+                let (symbol, style) = match value {
+                    None => (NO_DATA, self.label_style),
+                    Some(v) if *v > 0.8 => (shade(*v), self.styles[2]),
+                    Some(v) if *v > 0.5 => (shade(*v), self.styles[1]),
+                    Some(v) => (shade(*v), self.styles[0]),
+                };
+                buf.get_mut(x + dx as u16, y).set_symbol(symbol).set_style(style);
+            }
+        }
+
+        if let (Some((start, end)), 1) = (self.axis, axis_rows) {
+            let y = area.y + area.height - 1;
+            let end_x = plot_x + plot_width.saturating_sub(end.chars().count() as u16);
+            buf.set_stringn(plot_x, y, start, plot_width as usize, self.label_style);
+            if end_x > plot_x + start.chars().count() as u16 {
+                buf.set_string(end_x, y, end, self.label_style);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(-1.0, " ")]
+    #[case(0.0, " ")]
+    #[case(0.001, "░")]
+    #[case(0.25, "░")]
+    #[case(0.26, "▒")]
+    #[case(0.5, "▒")]
+    #[case(0.75, "▓")]
+    #[case(1.0, "█")]
+    #[case(2.0, "█")]
+    fn shade_of_value(#[case] value: f32, #[case] expected: &str) {
+        assert_eq!(shade(value), expected);
+    }
+}
@@ -0,0 +1,170 @@
+//! Histogram widget for value distributions.
+//!
+//! Sorts samples into equal-width bins between their minimum and maximum and
+//! draws one vertical bar per bin, using eighth-block symbols for sub-cell
+//! heights. The bottom row labels the range and the tallest bin's count.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    symbols,
+    widgets::{Block, Widget},
+};
+
+/// A histogram widget showing how samples are distributed.
+pub struct Histogram<'a> {
+    /// Block drawn around the histogram.
+    block: Option<Block<'a>>,
+    /// Samples to bin.
+    data: &'a [f64],
+    /// Number of bins. Defaults to one per bar that fits.
+    bins: Option<usize>,
+    /// Width of each bar in cells.
+    bar_width: u16,
+    /// Style of the bars.
+    style: Style,
+    /// Style of the axis labels.
+    label_style: Style,
+    /// Formats bin edges for the axis labels.
+    format: fn(f64) -> String,
+}
+
+impl<'a> Histogram<'a> {
+    pub fn new(data: &'a [f64]) -> Self {
+        Self {
+            block: None,
+            data,
+            bins: None,
+            bar_width: 2,
+            style: Style::default().fg(Color::Cyan),
+            label_style: Style::default().fg(Color::Gray),
+            format: |value| format!("{:.1}", value),
+        }
+    }
+
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+
+    pub fn bins(mut self, bins: usize) -> Self {
+        self.bins = Some(bins.max(1));
+        self
+    }
+
+    pub fn bar_width(mut self, width: u16) -> Self {
+        self.bar_width = width.max(1);
+        self
+    }
+
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    pub fn label_style(mut self, style: Style) -> Self {
+        self.label_style = style;
+        self
+    }
+
+    /// Format the range labels, for example as durations.
+    pub fn format(mut self, format: fn(f64) -> String) -> Self {
+        self.format = format;
+        self
+    }
+}
+
+/// Count samples into `bins` equal-width bins spanning `min..=max`.
+pub fn bin_counts(data: &[f64], bins: usize, min: f64, max: f64) -> Vec<u64> {
+    let mut counts = vec![0; bins.max(1)];
+    let width = (max - min) / counts.len() as f64;
+    for &value in data.iter().filter(|v| v.is_finite()) {
+        let bin = if width > 0.0 {
+            (((value - min) / width) as usize).min(counts.len() - 1)
+        } else {
+            0
+        };
+        counts[bin] += 1;
+    }
+    counts
+}
+
+impl<'a> Widget for Histogram<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = match self.block {
+            Some(block) => {
+                let inner = block.inner(area);
+                block.render(area, buf);
+                inner
+            }
+            None => area,
+        };
+
+        // Need a row of bars and a row of labels
+        if area.height < 2 || area.width < 2 || self.data.is_empty() {
+            return;
+        }
+
+        let (min, max) = self.data.iter()
+            .filter(|v| v.is_finite())
+            .fold((f64::MAX, f64::MIN), |(lo, hi), &v| (lo.min(v), hi.max(v)));
+        if min > max {
+            return;
+        }
+
+        // Leave a one-cell gap between bars when there is room for it
+        let gap = u16::from(self.bar_width > 1);
+        let slot = self.bar_width + gap;
+        let fit = ((area.width + gap) / slot).max(1) as usize;
+        let bins = self.bins.unwrap_or(fit).min(fit);
+        let counts = bin_counts(self.data, bins, min, max);
+        let peak = counts.iter().copied().max().unwrap_or(0).max(1);
+
+        // Bars grow up from the row above the labels, in eighths of a cell
+        let rows = area.height - 1;
+        let bottom = area.y + rows - 1;
+        for (i, &count) in counts.iter().enumerate() {
+            let eighths = (count as f64 / peak as f64 * rows as f64 * 8.0).round() as u16;
+            let eighths = if count > 0 { eighths.max(1) } else { 0 };
+            let x = area.x + i as u16 * slot;
+            for row in 0..rows {
+                let symbol = match eighths.saturating_sub(row * 8).min(8) {
+                    0 => continue,
+                    1 => symbols::bar::ONE_EIGHTH,
+                    2 => symbols::bar::ONE_QUARTER,
+                    3 => symbols::bar::THREE_EIGHTHS,
+                    4 => symbols::bar::HALF,
+                    5 => symbols::bar::FIVE_EIGHTHS,
+                    6 => symbols::bar::THREE_QUARTERS,
+                    7 => symbols::bar::SEVEN_EIGHTHS,
+                    _ => symbols::bar::FULL,
+                };
+                for dx in 0..self.bar_width {
+                    if x + dx < area.x + area.width {
+                        buf.get_mut(x + dx, bottom - row)
+                            .set_symbol(symbol)
+                            .set_style(self.style);
+                    }
+                }
+            }
+        }
+
+        // Range at either end of the label row, the peak count in between
+        let label_y = area.y + area.height - 1;
+        let low = (self.format)(min);
+        let high = (self.format)(max);
+        let peak_label = format!("peak {}", peak);
+        buf.set_stringn(area.x, label_y, &low, area.width as usize, self.label_style);
+        let high_x = area.x + area.width.saturating_sub(high.chars().count() as u16);
+        if high_x > area.x + low.chars().count() as u16 {
+            buf.set_string(high_x, label_y, &high, self.label_style);
+        }
+        let middle_x = area.x + (area.width.saturating_sub(peak_label.len() as u16)) / 2;
+        if middle_x > area.x + low.chars().count() as u16 + 1
+            && middle_x + (peak_label.len() as u16) < high_x
+        {
+            buf.set_string(middle_x, label_y, &peak_label, self.label_style);
+        }
+    }
+}
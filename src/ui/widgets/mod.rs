@@ -8,11 +8,17 @@
 //! - Component-based design with clear separation of concerns
 
 pub mod sparkline;
+pub mod histogram;
+pub mod heatmap;
 pub mod progress;
+pub mod stacked_bar;
 pub mod stat_panel;
 pub mod tabbed_view;
 
 pub use sparkline::Sparkline;
+pub use histogram::Histogram;
+pub use heatmap::Heatmap;
 pub use progress::ProgressBar;
+pub use stacked_bar::StackedBar;
 pub use stat_panel::StatPanel;
 pub use tabbed_view::TabbedView;
\ No newline at end of file
@@ -10,7 +10,7 @@
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     widgets::{Block, Widget},
 };
 use unicode_width::UnicodeWidthStr;
@@ -29,6 +29,8 @@ pub struct ProgressBar<'a> {
     style: Style,
     /// Style for the empty portion of the progress bar
     empty_style: Style,
+    /// Style for the label while it sits over the empty portion
+    text_style: Style,
     /// Symbol used for the filled part (default: █)
     symbol_filled: &'a str,
     /// Symbol used for the empty part (default: ░)
@@ -50,6 +52,7 @@ impl<'a> Default for ProgressBar<'a> {
             progress: 0.0,
             style: Style::default().fg(Color::Green),
             empty_style: Style::default().fg(Color::DarkGray),
+            text_style: Style::default().fg(Color::White),
             symbol_filled: "█",
             symbol_empty: "░",
             label: None,
@@ -87,6 +90,12 @@ impl<'a> ProgressBar<'a> {
         self
     }
     
+    /// Set the style for the label over the empty portion
+    pub fn text_style(mut self, style: Style) -> Self {
+        self.text_style = style;
+        self
+    }
+    
     /// Set the label to display in the center
     pub fn label(mut self, label: impl Into<String>) -> Self {
         self.label = Some(label.into());
@@ -183,11 +192,12 @@ impl<'a> Widget for ProgressBar<'a> {
         let text_x = render_area.left() + (render_area.width - center_text.width() as u16) / 2;
         let text_y = render_area.top();
         
-        // Dynamic text styling for visibility
+        // Over the filled portion, reverse the fill style so the text stays
+        // readable with any theme, including one without colors
         let text_style = if self.progress > 0.5 {
-            Style::default().fg(Color::Black).bg(style.fg.unwrap_or(Color::Green))
+            style.add_modifier(Modifier::REVERSED)
         } else {
-            Style::default().fg(Color::White)
+            self.text_style
         };
         
         for (i, c) in center_text.chars().enumerate() {
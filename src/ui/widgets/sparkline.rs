@@ -16,6 +16,8 @@ use ratatui::{
     widgets::{Block, Widget},
 };
 
+use crate::state::Unit;
+
 /// A sparkline widget that shows a simplified line chart.
 /// 
 /// # Design Notes
@@ -37,6 +39,8 @@ pub struct Sparkline<'a> {
     min_style: Style,
     /// Style for the maximum value.
     max_style: Style,
+    /// Unit of the data, used to label the latest value.
+    unit: Option<Unit>,
 }
 
 impl<'a> Sparkline<'a> {
@@ -49,6 +53,7 @@ impl<'a> Sparkline<'a> {
             bar_set: symbols::bar::NINE_LEVELS,
             min_style: Style::default().fg(Color::Blue),
             max_style: Style::default().fg(Color::Red),
+            unit: None,
         }
     }
     
@@ -81,6 +86,13 @@ impl<'a> Sparkline<'a> {
         self.max_style = style;
         self
     }
+    
+    /// Label the latest value in the given unit, and scale to the unit's
+    /// fixed maximum (100 for percentages) unless `max` is set.
+    pub fn unit(mut self, unit: Unit) -> Self {
+        self.unit = Some(unit);
+        self
+    }
 }
 
 impl<'a> Widget for Sparkline<'a> {
@@ -98,9 +110,20 @@ impl<'a> Widget for Sparkline<'a> {
             area
         };
         
+        // Label the latest value at the right edge when it leaves room for the bars
+        let mut area = area;
+        if let (Some(unit), Some(&latest)) = (self.unit, self.data.last()) {
+            let label = format!(" {}", unit.format(latest));
+            let width = label.chars().count() as u16;
+            if area.height > 0 && area.width > width * 2 {
+                area.width -= width;
+                buf.set_string(area.x + area.width, area.y, &label, self.style);
+            }
+        }
+        
         // Calculate min and max values
         // Using a single pass algorithm to minimize CPU usage, just like tokio-console does
-        let mut max_value = self.max.unwrap_or_else(|| {
+        let mut max_value = self.max.or_else(|| self.unit.and_then(|u| u.chart_max())).unwrap_or_else(|| {
             self.data.iter().fold(f64::MIN, |max, &v| max.max(v))
         });
         let min_value = self.data.iter().fold(max_value, |min, &v| min.min(v));
@@ -0,0 +1,133 @@
+//! Stacked bar widget for showing the composition of a total.
+//!
+//! Each segment takes a share of the width proportional to its value.
+//! Widths are rounded with the largest-remainder method so the segments
+//! always fill the bar exactly, and any non-zero segment gets at least one
+//! cell when there is room for it.
+
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::{Block, Widget},
+};
+
+/// Horizontal bar made of proportionally sized, individually styled segments.
+pub struct StackedBar<'a> {
+    /// Optional block to display around the bar
+    block: Option<Block<'a>>,
+    /// Segment values and styles, drawn left to right
+    segments: Vec<(u64, Style)>,
+    /// Symbol used for filled cells (default: █)
+    symbol: &'a str,
+    /// Style used when all segments are zero
+    empty_style: Style,
+}
+
+impl<'a> StackedBar<'a> {
+    /// Create a new stacked bar from segment values and styles.
+    pub fn new(segments: Vec<(u64, Style)>) -> Self {
+        Self {
+            block: None,
+            segments,
+            symbol: "█",
+            empty_style: Style::default().fg(Color::DarkGray),
+        }
+    }
+    
+    /// Set the block to display around the bar
+    pub fn block(mut self, block: Block<'a>) -> Self {
+        self.block = Some(block);
+        self
+    }
+    
+    /// Set the symbol used for filled cells
+    pub fn symbol(mut self, symbol: &'a str) -> Self {
+        self.symbol = symbol;
+        self
+    }
+    
+    /// Set the style used when there is nothing to show
+    pub fn empty_style(mut self, style: Style) -> Self {
+        self.empty_style = style;
+        self
+    }
+    
+    /// Compute the width of each segment for the given total width.
+    fn segment_widths(&self, width: u16) -> Vec<u16> {
+        let total: u64 = self.segments.iter().map(|(value, _)| value).sum();
+        if total == 0 {
+            return vec![0; self.segments.len()];
+        }
+        
+        // Floor of each exact share, then hand out the remaining cells by largest remainder
+        let exact: Vec<f64> = self.segments.iter()
+            .map(|(value, _)| *value as f64 * width as f64 / total as f64)
+            .collect();
+        let mut widths: Vec<u16> = exact.iter().map(|w| w.floor() as u16).collect();
+        let mut remaining = width.saturating_sub(widths.iter().sum());
+        
+        let mut order: Vec<usize> = (0..widths.len()).collect();
+        order.sort_by(|a, b| (exact[*b] - exact[*b].floor()).total_cmp(&(exact[*a] - exact[*a].floor())));
+        for index in order {
+            if remaining == 0 {
+                break;
+            }
+            if self.segments[index].0 > 0 {
+                widths[index] += 1;
+                remaining -= 1;
+            }
+        }
+        
+        // Give tiny non-zero segments one cell, taken from the widest segment
+        for index in 0..widths.len() {
+            if self.segments[index].0 > 0 && widths[index] == 0 {
+                let widest = (0..widths.len()).max_by_key(|i| widths[*i]).unwrap_or(index);
+                if widths[widest] > 1 {
+                    widths[widest] -= 1;
+                    widths[index] = 1;
+                }
+            }
+        }
+        
+        widths
+    }
+}
+
+impl<'a> Widget for StackedBar<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Render block if specified
+        let render_area = if let Some(ref block) = self.block {
+            let inner_area = block.inner(area);
+            block.clone().render(area, buf);
+            inner_area
+        } else {
+            area
+        };
+        
+        if render_area.width < 1 || render_area.height < 1 {
+            return;
+        }
+        
+        let widths = self.segment_widths(render_area.width);
+        for y in render_area.top()..render_area.bottom() {
+            if widths.iter().all(|w| *w == 0) {
+                for x in render_area.left()..render_area.right() {
+                    buf.get_mut(x, y).set_symbol("░").set_style(self.empty_style);
+                }
+                continue;
+            }
+            
+            let mut x = render_area.left();
+            for ((_, style), width) in self.segments.iter().zip(&widths) {
+                for _ in 0..*width {
+                    if x >= render_area.right() {
+                        break;
+                    }
+                    buf.get_mut(x, y).set_symbol(self.symbol).set_style(*style);
+                    x += 1;
+                }
+            }
+        }
+    }
+}
@@ -12,6 +12,8 @@ use ratatui::{
     widgets::{Block, Paragraph, Widget},
 };
 
+use crate::state::Unit;
+
 /// Trend indicator for stats.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum Trend {
@@ -21,11 +23,34 @@ pub enum Trend {
     None,
 }
 
+/// Styles for good, warning, critical and secondary values.
+#[derive(Debug, Clone, Copy)]
+pub struct StatStyles {
+    pub healthy: Style,
+    pub warning: Style,
+    pub critical: Style,
+    /// Neutral trends and previous values
+    pub muted: Style,
+}
+
+impl Default for StatStyles {
+    fn default() -> Self {
+        Self {
+            healthy: Style::default().fg(Color::Green),
+            warning: Style::default().fg(Color::Yellow),
+            critical: Style::default().fg(Color::Red),
+            muted: Style::default().fg(Color::DarkGray),
+        }
+    }
+}
+
 /// A value with associated metadata for context-aware rendering.
 #[derive(Debug, Clone)]
 pub struct StatValue {
     /// The main value to display
     value: String,
+    /// Numeric value for threshold checks, when it differs from the text
+    numeric: Option<f64>,
     /// Optional trend indicator
     trend: Trend,
     /// Whether this value represents a healthy state
@@ -36,6 +61,8 @@ pub struct StatValue {
     warn_threshold: Option<f64>,
     /// Optional threshold for critical state
     crit_threshold: Option<f64>,
+    /// Whether a falling value is the good direction
+    lower_is_better: bool,
 }
 
 impl StatValue {
@@ -43,20 +70,37 @@ impl StatValue {
     pub fn new(value: impl Into<String>) -> Self {
         Self {
             value: value.into(),
+            numeric: None,
             trend: Trend::None,
             is_healthy: true,
             previous: None,
             warn_threshold: None,
             crit_threshold: None,
+            lower_is_better: false,
         }
     }
     
+    /// Create a stat value formatted in the given unit; thresholds still
+    /// apply to the raw number
+    pub fn with_unit(value: f64, unit: Unit) -> Self {
+        let mut stat = Self::new(unit.format(value));
+        stat.numeric = Some(value);
+        stat
+    }
+    
     /// Add a trend indicator to this stat
     pub fn trend(mut self, trend: Trend) -> Self {
         self.trend = trend;
         self
     }
     
+    /// Color a falling trend as good and a rising one as bad, for values
+    /// such as failure rates
+    pub fn lower_is_better(mut self) -> Self {
+        self.lower_is_better = true;
+        self
+    }
+    
     /// Mark the stat as healthy/unhealthy
     pub fn healthy(mut self, is_healthy: bool) -> Self {
         self.is_healthy = is_healthy;
@@ -81,35 +125,35 @@ impl StatValue {
         self
     }
     
-    /// Get appropriate color based on the value and thresholds
-    fn color_for_value(&self, value_f64: Option<f64>) -> Color {
+    /// Get appropriate style based on the value and thresholds
+    fn style_for_value(&self, value_f64: Option<f64>, styles: &StatStyles) -> Style {
         if !self.is_healthy {
-            return Color::Red;
+            return styles.critical;
         }
         
         if let Some(value) = value_f64 {
             if let Some(crit) = self.crit_threshold {
                 if value >= crit {
-                    return Color::Red;
+                    return styles.critical;
                 }
             }
             
             if let Some(warn) = self.warn_threshold {
                 if value >= warn {
-                    return Color::Yellow;
+                    return styles.warning;
                 }
             }
         }
         
-        Color::Green
+        styles.healthy
     }
     
     /// Get style for this stat
-    fn get_style(&self) -> Style {
+    fn get_style(&self, styles: &StatStyles) -> Style {
         // Try to parse value as f64 for threshold comparison
-        let value_f64 = self.value.parse::<f64>().ok();
+        let value_f64 = self.numeric.or_else(|| self.value.parse::<f64>().ok());
         
-        Style::default().fg(self.color_for_value(value_f64))
+        self.style_for_value(value_f64, styles)
     }
     
     /// Get trend indicator symbol
@@ -123,13 +167,13 @@ impl StatValue {
     }
     
     /// Get spans for rendering this stat
-    fn to_spans(&self) -> Vec<Span<'static>> {
+    fn to_spans(&self, styles: &StatStyles) -> Vec<Span<'static>> {
         let mut spans = Vec::with_capacity(3);
         
         // Add the value with appropriate style
         spans.push(Span::styled(
             self.value.clone(),
-            self.get_style().add_modifier(Modifier::BOLD)
+            self.get_style(styles).add_modifier(Modifier::BOLD)
         ));
         
         // Add trend symbol if present
@@ -137,10 +181,10 @@ impl StatValue {
         if !trend_symbol.is_empty() {
             spans.push(Span::styled(
                 format!(" {}", trend_symbol),
-                match self.trend {
-                    Trend::Up => Style::default().fg(Color::Green),
-                    Trend::Down => Style::default().fg(Color::Red),
-                    _ => Style::default().fg(Color::Gray),
+                match (self.trend, self.lower_is_better) {
+                    (Trend::Up, false) | (Trend::Down, true) => styles.healthy,
+                    (Trend::Down, false) | (Trend::Up, true) => styles.critical,
+                    _ => styles.muted,
                 }
             ));
         }
@@ -149,7 +193,7 @@ impl StatValue {
         if let Some(prev) = &self.previous {
             spans.push(Span::styled(
                 format!(" (was: {})", prev),
-                Style::default().fg(Color::DarkGray)
+                styles.muted
             ));
         }
         
@@ -165,6 +209,8 @@ pub struct StatPanel<'a> {
     stats: Vec<(&'a str, StatValue)>,
     /// Style for the labels
     label_style: Style,
+    /// Styles for the values, trends and previous values
+    value_styles: StatStyles,
     /// Whether to right-align values
     right_align: bool,
     /// Space between label and value
@@ -178,6 +224,7 @@ impl<'a> StatPanel<'a> {
             block: None,
             stats: Vec::new(),
             label_style: Style::default().add_modifier(Modifier::BOLD),
+            value_styles: StatStyles::default(),
             right_align: false,
             spacing: 2,
         }
@@ -201,6 +248,12 @@ impl<'a> StatPanel<'a> {
         self
     }
     
+    /// Set the styles for values by threshold, trends and previous values
+    pub fn value_styles(mut self, styles: StatStyles) -> Self {
+        self.value_styles = styles;
+        self
+    }
+    
     /// Set right alignment for values
     pub fn right_align(mut self, right_align: bool) -> Self {
         self.right_align = right_align;
@@ -264,7 +317,7 @@ impl<'a> Widget for StatPanel<'a> {
             spans.push(Span::raw(format!("{:spacing$}", "", spacing = self.spacing)));
             
             // Add value with appropriate styling
-            spans.extend(value.to_spans());
+            spans.extend(value.to_spans(&self.value_styles));
             
             text.push(Line::from(spans));
         }